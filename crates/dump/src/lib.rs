@@ -287,6 +287,8 @@ pub(crate) mod test {
                 sort_facet_values_by: Setting::Set(
                     btreemap! { S("age") => FacetValuesSort::Count },
                 ),
+                hierarchical_facets: Setting::NotSet,
+                exact_facets: Setting::NotSet,
             }),
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
@@ -294,6 +296,10 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            curation_rules: Setting::NotSet,
+            date_attributes: Setting::NotSet,
+            nested_attributes: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
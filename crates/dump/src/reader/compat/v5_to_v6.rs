@@ -368,6 +368,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Set(faceting) => v6::Setting::Set(v6::FacetingSettings {
                     max_values_per_facet: faceting.max_values_per_facet.into(),
                     sort_facet_values_by: v6::Setting::NotSet,
+                    hierarchical_facets: v6::Setting::NotSet,
+                    exact_facets: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -384,6 +386,10 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            infix_searchable_attributes: v6::Setting::NotSet,
+            curation_rules: v6::Setting::NotSet,
+            date_attributes: v6::Setting::NotSet,
+            nested_attributes: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
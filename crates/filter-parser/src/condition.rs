@@ -2,7 +2,9 @@
 //!
 //! ```text
 //! condition      = value ("==" | ">" ...) value
+//! comparison     = value ("<" | "<=" | ">" | ">=") field    where field is an unquoted word that is not a number or a date
 //! to             = value value TO value
+//! under          = value "UNDER" value
 //! ```
 
 use nom::branch::alt;
@@ -12,7 +14,7 @@ use nom::combinator::cut;
 use nom::sequence::{terminated, tuple};
 use Condition::*;
 
-use crate::{parse_value, FilterCondition, IResult, Span, Token};
+use crate::{parse_rfc3339_date, parse_value, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Condition<'a> {
@@ -28,12 +30,69 @@ pub enum Condition<'a> {
     Between { from: Token<'a>, to: Token<'a> },
     Contains { keyword: Token<'a>, word: Token<'a> },
     StartsWith { keyword: Token<'a>, word: Token<'a> },
+    Under(Token<'a>),
+    CompareField { comparison: Comparison, field: Token<'a> },
+}
+
+/// The ordering operators of a [`Condition::CompareField`], which compares the values
+/// of the field with the values of another field of the same document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    GreaterThan,
+    GreaterThanOrEqual,
+    LowerThan,
+    LowerThanOrEqual,
+}
+
+impl Comparison {
+    /// Returns whether there is a value in `left` that compares to a value in `right`,
+    /// both given as their `(min, max)` bounds.
+    pub fn any_match<T: PartialOrd>(&self, left: (T, T), right: (T, T)) -> bool {
+        let ((left_min, left_max), (right_min, right_max)) = (left, right);
+        match self {
+            Comparison::GreaterThan => left_max > right_min,
+            Comparison::GreaterThanOrEqual => left_max >= right_min,
+            Comparison::LowerThan => left_min < right_max,
+            Comparison::LowerThanOrEqual => left_min <= right_max,
+        }
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparison::GreaterThan => write!(f, ">"),
+            Comparison::GreaterThanOrEqual => write!(f, ">="),
+            Comparison::LowerThan => write!(f, "<"),
+            Comparison::LowerThanOrEqual => write!(f, "<="),
+        }
+    }
 }
 
 /// condition      = value ("==" | ">" ...) value
+/// comparison     = value ("<" | "<=" | ">" | ">=") field
 pub fn parse_condition(input: Span) -> IResult<FilterCondition> {
     let operator = alt((tag("<="), tag(">="), tag("!="), tag("<"), tag(">"), tag("=")));
-    let (input, (fid, op, value)) = tuple((parse_value, operator, cut(parse_value)))(input)?;
+    let (input, (fid, op)) = tuple((parse_value, operator))(input)?;
+    let quoted = input.fragment().trim_start().starts_with(['\'', '"']);
+    let (input, value) = cut(parse_value)(input)?;
+
+    // an ordering operator followed by an unquoted word that is neither a number nor a date
+    // compares the field with another field of the document
+    let is_literal = quoted
+        || value.value().parse::<f64>().is_ok()
+        || parse_rfc3339_date(value.value()).is_some();
+    let comparison = match *op.fragment() {
+        "<=" => Some(Comparison::LowerThanOrEqual),
+        ">=" => Some(Comparison::GreaterThanOrEqual),
+        "<" => Some(Comparison::LowerThan),
+        ">" => Some(Comparison::GreaterThan),
+        _ => None,
+    };
+    if let Some(comparison) = comparison.filter(|_| !is_literal) {
+        let op = CompareField { comparison, field: value };
+        return Ok((input, FilterCondition::Condition { fid, op }));
+    }
 
     let condition = match *op.fragment() {
         "<=" => FilterCondition::Condition { fid, op: LowerThanOrEqual(value) },
@@ -150,6 +209,24 @@ pub fn parse_not_starts_with(input: Span) -> IResult<FilterCondition> {
     ))
 }
 
+/// under          = value "UNDER" value
+pub fn parse_under(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, _under, value)) =
+        tuple((parse_value, tag("UNDER"), cut(parse_value)))(input)?;
+    Ok((input, FilterCondition::Condition { fid, op: Under(value) }))
+}
+
+/// not under      = value "NOT" WS+ "UNDER" value
+pub fn parse_not_under(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("UNDER")));
+    let (input, (fid, _keyword, value)) = tuple((parse_value, keyword, cut(parse_value)))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition { fid, op: Under(value) })),
+    ))
+}
+
 /// to             = value value "TO" WS+ value
 pub fn parse_to(input: Span) -> IResult<FilterCondition> {
     let (input, (key, from, _, _, to)) =
@@ -59,17 +59,22 @@ pub enum ErrorKind<'a> {
     ReservedGeo(&'a str),
     GeoRadius,
     GeoBoundingBox,
+    GeoPolygon,
+    GeoContains,
+    Size,
     MisusedGeoRadius,
     MisusedGeoBoundingBox,
+    MisusedGeoPolygon,
+    MisusedGeoContains,
     InvalidPrimary,
     InvalidEscapedNumber,
     ExpectedEof,
     ExpectedValue(ExpectedValueKind),
     MalformedValue,
-    InOpeningBracket,
-    InClosingBracket,
+    InOpeningBracket(&'static str),
+    InClosingBracket(&'static str),
     NonFiniteFloat,
-    InExpectedValue(ExpectedValueKind),
+    InExpectedValue(&'static str, ExpectedValueKind),
     ReservedKeyword(String),
     MissingClosingDelimiter(char),
     Char(char),
@@ -146,7 +151,7 @@ impl<'a> Display for Error<'a> {
             }
             ErrorKind::InvalidPrimary => {
                 let text = if input.trim().is_empty() { "but instead got nothing.".to_string() } else { format!("at `{}`.", escaped_input) };
-                writeln!(f, "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` {}", text)?
+                writeln!(f, "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` {}", text)?
             }
             ErrorKind::InvalidEscapedNumber => {
                 writeln!(f, "Found an invalid escaped sequence number: `{}`.", escaped_input)?
@@ -160,6 +165,15 @@ impl<'a> Display for Error<'a> {
             ErrorKind::GeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter expects two pairs of arguments: `_geoBoundingBox([latitude, longitude], [latitude, longitude])`.")?
             }
+            ErrorKind::GeoPolygon => {
+                writeln!(f, "The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude])`.")?
+            }
+            ErrorKind::GeoContains => {
+                writeln!(f, "The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.")?
+            }
+            ErrorKind::Size => {
+                writeln!(f, "The `SIZE` filter expects an attribute followed by a comparison: `SIZE(attribute) > 2` or `SIZE(attribute) 1 TO 3`.")?
+            }
             ErrorKind::ReservedGeo(name) => {
                 writeln!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.", name.escape_debug())?
             }
@@ -169,23 +183,29 @@ impl<'a> Display for Error<'a> {
             ErrorKind::MisusedGeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter is an operation and can't be used as a value.")?
             }
+            ErrorKind::MisusedGeoPolygon => {
+                writeln!(f, "The `_geoPolygon` filter is an operation and can't be used as a value.")?
+            }
+            ErrorKind::MisusedGeoContains => {
+                writeln!(f, "The `_geoContains` filter is an operation and can't be used as a value.")?
+            }
             ErrorKind::ReservedKeyword(word) => {
                 writeln!(f, "`{word}` is a reserved keyword and thus cannot be used as a field name unless it is put inside quotes. Use \"{word}\" or \'{word}\' instead.")?
             }
-            ErrorKind::InOpeningBracket => {
-                writeln!(f, "Expected `[` after `IN` keyword.")?
+            ErrorKind::InOpeningBracket(keyword) => {
+                writeln!(f, "Expected `[` after `{keyword}` keyword.")?
             }
-            ErrorKind::InClosingBracket => {
-                writeln!(f, "Expected matching `]` after the list of field names given to `IN[`")?
+            ErrorKind::InClosingBracket(keyword) => {
+                writeln!(f, "Expected matching `]` after the list of field names given to `{keyword}[`")?
             }
             ErrorKind::NonFiniteFloat => {
                 writeln!(f, "Non finite floats are not supported")?
             }
-            ErrorKind::InExpectedValue(ExpectedValueKind::ReservedKeyword) => {
-                writeln!(f, "Expected only comma-separated field names inside `IN[..]` but instead found `{escaped_input}`, which is a keyword. To use `{escaped_input}` as a field name or a value, surround it by quotes.")?
+            ErrorKind::InExpectedValue(keyword, ExpectedValueKind::ReservedKeyword) => {
+                writeln!(f, "Expected only comma-separated field names inside `{keyword}[..]` but instead found `{escaped_input}`, which is a keyword. To use `{escaped_input}` as a field name or a value, surround it by quotes.")?
             }
-            ErrorKind::InExpectedValue(ExpectedValueKind::Other) => {
-                writeln!(f, "Expected only comma-separated field names inside `IN[..]` but instead found `{escaped_input}`.")?
+            ErrorKind::InExpectedValue(keyword, ExpectedValueKind::Other) => {
+                writeln!(f, "Expected only comma-separated field names inside `{keyword}[..]` but instead found `{escaped_input}`.")?
             }
             ErrorKind::Char(c) => {
                 panic!("Tried to display a char error with `{}`", c)
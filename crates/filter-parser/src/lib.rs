@@ -6,19 +6,27 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | size | in | all | nested | comparison | condition | exists | not_exists | to
 //! in             = value "IN" WS* "[" value_list "]"
+//! all            = value "ALL" WS* "[" value_list "]"
+//! nested         = value "[" WS* expression WS* "]"    where the fields of the expression are relative to the value
+//! size           = "SIZE(" value ")" (("=" | "!=" | ">" | ">=" | "<" | "<=") value | value "TO" WS+ value)
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
+//! comparison     = value (">" | ">=" | "<" | "<=") value    where the second value is an unquoted word that is neither a number nor a date
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
 //! to             = value value "TO" WS+ value
-//! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
+//! under          = value "UNDER" value
+//! value          = WS* ( date | word | singleQuoted | doubleQuoted) WS+
 //! value_list     = (value ("," value)* ","?)?
 //! singleQuoted   = "'" .* all but quotes "'"
 //! doubleQuoted   = "\"" .* all but double quotes "\""
+//! date           = RFC 3339 date-time or YYYY-MM-DD date
 //! word           = (alphanumeric | _ | - | .)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoPolygon     = "_geoPolygon([" WS* float WS* "," WS* float WS* "]" ("," WS* "[" WS* float WS* "," WS* float WS* "]")+ ")"
+//! geoContains    = "_geoContains(" WS* float WS* "," WS* float WS* ")"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -46,22 +54,25 @@ mod value;
 
 use std::fmt::Debug;
 
-pub use condition::{parse_condition, parse_to, Condition};
+pub use condition::{parse_condition, parse_to, Comparison, Condition};
 use condition::{
     parse_contains, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
-    parse_is_null, parse_not_contains, parse_not_exists, parse_not_starts_with, parse_starts_with,
+    parse_is_null, parse_not_contains, parse_not_exists, parse_not_starts_with, parse_not_under,
+    parse_starts_with, parse_under,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0};
+use nom::character::complete::{char, multispace0, multispace1};
 use nom::combinator::{cut, eof, map, opt};
 use nom::multi::{many0, separated_list1};
 use nom::number::complete::recognize_float;
 use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom::Finish;
 use nom_locate::LocatedSpan;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 pub(crate) use value::parse_value;
 use value::word_exact;
 
@@ -121,6 +132,25 @@ impl<'a> Token<'a> {
             Err(Error::new_from_kind(self.span, ErrorKind::NonFiniteFloat))
         }
     }
+
+    /// Parses the token as a finite float or, failing that, as an RFC 3339 date
+    /// converted to a unix timestamp in seconds.
+    pub fn parse_finite_float_or_date(&self) -> Result<f64, Error> {
+        self.parse_finite_float().or_else(|e| parse_rfc3339_date(self.value()).ok_or(e))
+    }
+}
+
+/// Parses an RFC 3339 date-time, or a bare `YYYY-MM-DD` date taken at midnight UTC,
+/// and returns it as a unix timestamp in seconds.
+pub fn parse_rfc3339_date(value: &str) -> Option<f64> {
+    let date = match OffsetDateTime::parse(value, &Rfc3339) {
+        Ok(date) => date,
+        Err(_) if value.len() == 10 => {
+            OffsetDateTime::parse(&format!("{value}T00:00:00Z"), &Rfc3339).ok()?
+        }
+        Err(_) => return None,
+    };
+    Some(date.unix_timestamp() as f64 + date.nanosecond() as f64 / 1e9)
 }
 
 impl<'a> From<Span<'a>> for Token<'a> {
@@ -141,10 +171,15 @@ pub enum FilterCondition<'a> {
     Not(Box<Self>),
     Condition { fid: Token<'a>, op: Condition<'a> },
     In { fid: Token<'a>, els: Vec<Token<'a>> },
+    All { fid: Token<'a>, els: Vec<Token<'a>> },
+    Size { fid: Token<'a>, op: Condition<'a> },
+    Nested { fid: Token<'a>, filter: Box<Self> },
     Or(Vec<Self>),
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    GeoPolygon { points: Vec<[Token<'a>; 2]> },
+    GeoContains { point: [Token<'a>; 2] },
 }
 
 pub enum TraversedElement<'a> {
@@ -165,27 +200,49 @@ impl<'a> FilterCondition<'a> {
                 | Condition::Exists
                 | Condition::LowerThan(_)
                 | Condition::LowerThanOrEqual(_)
-                | Condition::Between { .. } => None,
+                | Condition::Between { .. }
+                | Condition::Under(_)
+                | Condition::CompareField { .. } => None,
                 Condition::Contains { keyword, word: _ }
                 | Condition::StartsWith { keyword, word: _ } => Some(keyword),
             },
-            FilterCondition::Not(this) => this.use_contains_operator(),
+            FilterCondition::Not(this) | FilterCondition::Nested { filter: this, .. } => {
+                this.use_contains_operator()
+            }
             FilterCondition::Or(seq) | FilterCondition::And(seq) => {
                 seq.iter().find_map(|filter| filter.use_contains_operator())
             }
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
-            | FilterCondition::In { .. } => None,
+            | FilterCondition::GeoPolygon { .. }
+            | FilterCondition::GeoContains { .. }
+            | FilterCondition::In { .. }
+            | FilterCondition::All { .. }
+            | FilterCondition::Size { .. } => None,
         }
     }
 
-    pub fn fids(&self, depth: usize) -> Box<dyn Iterator<Item = &Token> + '_> {
+    /// Returns the fields used by the filter, the fields filtered inside a nested filter
+    /// are named after their full path.
+    pub fn fids(&self, depth: usize) -> Box<dyn Iterator<Item = Token<'a>> + '_> {
         if depth == 0 {
             return Box::new(std::iter::empty());
         }
         match self {
-            FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
-                Box::new(std::iter::once(fid))
+            FilterCondition::Condition { fid, op: Condition::CompareField { field, .. } } => {
+                Box::new([fid.clone(), field.clone()].into_iter())
+            }
+            FilterCondition::Condition { fid, .. }
+            | FilterCondition::In { fid, .. }
+            | FilterCondition::All { fid, .. }
+            | FilterCondition::Size { fid, .. } => Box::new(std::iter::once(fid.clone())),
+            FilterCondition::Nested { fid, filter } => {
+                let depth = depth.saturating_sub(1);
+                let inner_fids = filter.fids(depth).map(move |inner| {
+                    let path = format!("{}.{}", fid.value(), inner.value());
+                    Token::new(inner.original_span(), Some(path))
+                });
+                Box::new(std::iter::once(fid.clone()).chain(inner_fids))
             }
             FilterCondition::Not(filter) => {
                 let depth = depth.saturating_sub(1);
@@ -258,22 +315,21 @@ fn parse_value_list(input: Span) -> IResult<Vec<Token>> {
     }
 }
 
-/// "IN" WS* "[" value_list "]"
-fn parse_in_body(input: Span) -> IResult<Vec<Token>> {
-    let (input, _) = ws(word_exact("IN"))(input)?;
+/// keyword WS* "[" value_list "]"
+fn parse_list_body<'a>(keyword: &'static str, input: Span<'a>) -> IResult<'a, Vec<Token<'a>>> {
+    let (input, _) = ws(word_exact(keyword))(input)?;
 
-    // everything after `IN` can be a failure
-    let (input, _) =
-        cut_with_err(tag("["), |_| Error::new_from_kind(input, ErrorKind::InOpeningBracket))(
-            input,
-        )?;
+    // everything after the keyword can be a failure
+    let (input, _) = cut_with_err(tag("["), |_| {
+        Error::new_from_kind(input, ErrorKind::InOpeningBracket(keyword))
+    })(input)?;
 
     let (input, content) = cut(parse_value_list)(input)?;
 
-    // everything after `IN` can be a failure
+    // everything after the keyword can be a failure
     let (input, _) = cut_with_err(ws(tag("]")), |_| {
         if eof::<_, ()>(input).is_ok() {
-            Error::new_from_kind(input, ErrorKind::InClosingBracket)
+            Error::new_from_kind(input, ErrorKind::InClosingBracket(keyword))
         } else {
             let expected_value_kind = match parse_value(input) {
                 Err(nom::Err::Error(e)) => match e.kind() {
@@ -282,7 +338,7 @@ fn parse_in_body(input: Span) -> IResult<Vec<Token>> {
                 },
                 _ => ExpectedValueKind::Other,
             };
-            Error::new_from_kind(input, ErrorKind::InExpectedValue(expected_value_kind))
+            Error::new_from_kind(input, ErrorKind::InExpectedValue(keyword, expected_value_kind))
         }
     })(input)?;
 
@@ -292,7 +348,7 @@ fn parse_in_body(input: Span) -> IResult<Vec<Token>> {
 /// in = value "IN" "[" value_list "]"
 fn parse_in(input: Span) -> IResult<FilterCondition> {
     let (input, value) = parse_value(input)?;
-    let (input, content) = parse_in_body(input)?;
+    let (input, content) = parse_list_body("IN", input)?;
 
     let filter = FilterCondition::In { fid: value, els: content };
     Ok((input, filter))
@@ -302,12 +358,78 @@ fn parse_in(input: Span) -> IResult<FilterCondition> {
 fn parse_not_in(input: Span) -> IResult<FilterCondition> {
     let (input, value) = parse_value(input)?;
     let (input, _) = word_exact("NOT")(input)?;
-    let (input, content) = parse_in_body(input)?;
+    let (input, content) = parse_list_body("IN", input)?;
 
     let filter = FilterCondition::Not(Box::new(FilterCondition::In { fid: value, els: content }));
     Ok((input, filter))
 }
 
+/// all = value "ALL" "[" value_list "]"
+fn parse_all(input: Span) -> IResult<FilterCondition> {
+    let (input, value) = parse_value(input)?;
+    let (input, content) = parse_list_body("ALL", input)?;
+
+    let filter = FilterCondition::All { fid: value, els: content };
+    Ok((input, filter))
+}
+
+/// all = value "NOT" WS* "ALL" "[" value_list "]"
+fn parse_not_all(input: Span) -> IResult<FilterCondition> {
+    let (input, value) = parse_value(input)?;
+    let (input, _) = word_exact("NOT")(input)?;
+    let (input, content) = parse_list_body("ALL", input)?;
+
+    let filter = FilterCondition::Not(Box::new(FilterCondition::All { fid: value, els: content }));
+    Ok((input, filter))
+}
+
+/// size = WS* "SIZE(" value ")" (("=" | "!=" | ">" | ">=" | "<" | "<=") value | value "TO" WS+ value)
+/// If we parse `SIZE(` we MUST parse the rest of the expression.
+fn parse_size(input: Span) -> IResult<FilterCondition> {
+    let operator = alt((tag("<="), tag(">="), tag("!="), tag("<"), tag(">"), tag("=")));
+    let comparison = map(tuple((operator, parse_value)), |(op, value)| match *op.fragment() {
+        "<=" => Condition::LowerThanOrEqual(value),
+        ">=" => Condition::GreaterThanOrEqual(value),
+        "!=" => Condition::NotEqual(value),
+        "<" => Condition::LowerThan(value),
+        ">" => Condition::GreaterThan(value),
+        "=" => Condition::Equal(value),
+        _ => unreachable!(),
+    });
+    let between = map(
+        tuple((parse_value, word_exact("TO"), multispace1, parse_value)),
+        |(from, _, _, to)| Condition::Between { from, to },
+    );
+
+    // we want to allow space BEFORE the SIZE but not between `SIZE` and its parenthesis
+    let (input, (fid, op)) = preceded(
+        tuple((multispace0, word_exact("SIZE"), char('('))),
+        // if we were able to parse `SIZE(` and can't parse the rest of the input we return a failure
+        cut(tuple((
+            terminated(parse_value, tuple((char(')'), multispace0))),
+            alt((comparison, between)),
+        ))),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::Size)))?;
+
+    Ok((input, FilterCondition::Size { fid, op }))
+}
+
+/// nested = value "[" WS* expression WS* "]"
+/// If we parse a value followed by `[` we MUST parse the rest of the expression.
+fn parse_nested(input: Span, depth: usize) -> IResult<FilterCondition> {
+    let (rest, fid) = parse_value(input)?;
+    let (rest, filter) = delimited(
+        ws(char('[')),
+        cut(|input| parse_expression(input, depth + 1)),
+        cut_with_err(ws(char(']')), |c| {
+            Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(c.char()))
+        }),
+    )(rest)?;
+
+    Ok((rest, FilterCondition::Nested { fid, filter: Box::new(filter) }))
+}
+
 /// or             = and ("OR" and)
 fn parse_or(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
@@ -422,6 +544,57 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// geoPolygon      = WS* "_geoPolygon([float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], ...)
+/// If we parse `_geoPolygon` we MUST parse the rest of the expression.
+fn parse_geo_polygon(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoPolygon but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoPolygon"))),
+        // if we were able to parse `_geoPolygon` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            char('('),
+            separated_list1(
+                tag(","),
+                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            ),
+            char(')'),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+
+    let (input, args) = parsed?;
+
+    if args.len() < 3 || args.iter().any(|point| point.len() != 2) {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+    }
+
+    let res = FilterCondition::GeoPolygon {
+        points: args.into_iter().map(|point| [point[0].into(), point[1].into()]).collect(),
+    };
+    Ok((input, res))
+}
+
+/// geoContains      = WS* "_geoContains(float WS* "," WS* float)
+/// If we parse `_geoContains` we MUST parse the rest of the expression.
+fn parse_geo_contains(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoContains but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoContains"))),
+        // if we were able to parse `_geoContains` and can't parse the rest of the input we return a failure
+        cut(delimited(char('('), separated_list1(tag(","), ws(recognize_float)), char(')'))),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoContains)));
+
+    let (input, args) = parsed?;
+
+    if args.len() != 2 {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoContains)));
+    }
+
+    let res = FilterCondition::GeoContains { point: [args[0].into(), args[1].into()] };
+    Ok((input, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -491,10 +664,11 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
                 Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(c.char()))
             }),
         ),
-        parse_geo_radius,
-        parse_geo_bounding_box,
-        parse_in,
-        parse_not_in,
+        // nom can't try more than 21 alternatives at once so we group some of them together
+        alt((parse_geo_radius, parse_geo_bounding_box, parse_geo_polygon, parse_geo_contains)),
+        parse_size,
+        alt((parse_in, parse_not_in, parse_all, parse_not_all)),
+        |input| parse_nested(input, depth),
         parse_condition,
         parse_is_null,
         parse_is_not_null,
@@ -507,11 +681,10 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         parse_not_contains,
         parse_starts_with,
         parse_not_starts_with,
+        parse_under,
+        parse_not_under,
         // the next lines are only for error handling and are written at the end to have the less possible performance impact
-        parse_geo,
-        parse_geo_distance,
-        parse_geo_point,
-        parse_error_reserved_keyword,
+        alt((parse_geo, parse_geo_distance, parse_geo_point, parse_error_reserved_keyword)),
     ))(input)
     // if the inner parsers did not match enough information to return an accurate error
     .map_err(|e| e.map_err(|_| Error::new_from_kind(input, ErrorKind::InvalidPrimary)))
@@ -536,6 +709,19 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
             FilterCondition::Condition { fid, op } => {
                 write!(f, "{fid} {op}")
             }
+            FilterCondition::All { fid, els } => {
+                write!(f, "{fid} ALL[")?;
+                for el in els {
+                    write!(f, "{el}, ")?;
+                }
+                write!(f, "]")
+            }
+            FilterCondition::Size { fid, op } => {
+                write!(f, "SIZE({fid}) {op}")
+            }
+            FilterCondition::Nested { fid, filter } => {
+                write!(f, "{fid}[{filter}]")
+            }
             FilterCondition::In { fid, els } => {
                 write!(f, "{fid} IN[")?;
                 for el in els {
@@ -573,6 +759,19 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoPolygon { points } => {
+                write!(f, "_geoPolygon(")?;
+                for (i, [lat, lng]) in points.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "[{lat}, {lng}]")?;
+                }
+                write!(f, ")")
+            }
+            FilterCondition::GeoContains { point } => {
+                write!(f, "_geoContains({}, {})", point[0], point[1])
+            }
         }
     }
 }
@@ -591,6 +790,10 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
             Condition::Contains { word, keyword: _ } => write!(f, "CONTAINS {word}"),
             Condition::StartsWith { word, keyword: _ } => write!(f, "STARTS WITH {word}"),
+            Condition::Under(token) => write!(f, "UNDER {token}"),
+            Condition::CompareField { comparison, field } => {
+                write!(f, "{comparison} FIELD({field})")
+            }
         }
     }
 }
@@ -650,6 +853,24 @@ pub mod tests {
         insta::assert_snapshot!(p("colour NOT IN[green,blue]"), @"NOT ({colour} IN[{green}, {blue}, ])");
         insta::assert_snapshot!(p(" colour IN [  green , blue , ]"), @"{colour} IN[{green}, {blue}, ]");
 
+        // Test ALL
+        insta::assert_snapshot!(p("tags ALL[]"), @"{tags} ALL[]");
+        insta::assert_snapshot!(p("tags ALL [red, 'dark blue']"), @"{tags} ALL[{red}, {dark blue}, ]");
+        insta::assert_snapshot!(p("tags NOT ALL [red, blue,]"), @"NOT ({tags} ALL[{red}, {blue}, ])");
+        insta::assert_snapshot!(p("ALL ALL [ALL]"), @"{ALL} ALL[{ALL}, ]");
+
+        // Test SIZE
+        insta::assert_snapshot!(p("SIZE(tags) > 2"), @"SIZE({tags}) > {2}");
+        insta::assert_snapshot!(p(" SIZE( 'my tags' )=0"), @"SIZE({my tags}) = {0}");
+        insta::assert_snapshot!(p("SIZE(tags) 1 TO 3"), @"SIZE({tags}) {1} TO {3}");
+        insta::assert_snapshot!(p("NOT SIZE(tags) != 2 AND SIZE = 3"), @"AND[NOT (SIZE({tags}) != {2}), {SIZE} = {3}, ]");
+
+        // Test nested
+        insta::assert_snapshot!(p("variants[color = red AND size = L]"), @"{variants}[AND[{color} = {red}, {size} = {L}, ]]");
+        insta::assert_snapshot!(p(" variants [ color IN [red, blue] ] OR id = 1"), @"OR[{variants}[{color} IN[{red}, {blue}, ]], {id} = {1}, ]");
+        insta::assert_snapshot!(p("NOT variants[NOT size EXISTS]"), @"NOT ({variants}[NOT ({size} EXISTS)])");
+        insta::assert_snapshot!(p("'product variants'[price > 10]"), @"{product variants}[{price} > {10}]");
+
         // Test IN + OR/AND/()
         insta::assert_snapshot!(p(" colour IN [green, blue]  AND color = green "), @"AND[{colour} IN[{green}, {blue}, ], {color} = {green}, ]");
         insta::assert_snapshot!(p("NOT (colour IN [green, blue])  AND color = green "), @"AND[NOT ({colour} IN[{green}, {blue}, ]), {color} = {green}, ]");
@@ -711,6 +932,12 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT subscribers NOT STARTS WITH 'hel'"), @"{subscribers} STARTS WITH {hel}");
         insta::assert_snapshot!(p("subscribers NOT   STARTS WITH 'hel'"), @"NOT ({subscribers} STARTS WITH {hel})");
 
+        // Test UNDER + NOT UNDER
+        insta::assert_snapshot!(p("category UNDER 'Electronics > Audio'"), @"{category} UNDER {Electronics > Audio}");
+        insta::assert_snapshot!(p("NOT category UNDER Electronics"), @"NOT ({category} UNDER {Electronics})");
+        insta::assert_snapshot!(p("category NOT UNDER Electronics"), @"NOT ({category} UNDER {Electronics})");
+        insta::assert_snapshot!(p("NOT category NOT UNDER 'Electronics'"), @"{category} UNDER {Electronics}");
+
         // Test nested NOT
         insta::assert_snapshot!(p("NOT NOT NOT NOT x = 5"), @"{x} = {5}");
         insta::assert_snapshot!(p("NOT NOT (NOT NOT x = 5)"), @"{x} = {5}");
@@ -725,6 +952,30 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test geo polygon
+        insta::assert_snapshot!(p("_geoPolygon([12, 13], [14, 15], [16, 17])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}])");
+        insta::assert_snapshot!(p("NOT _geoPolygon([12, 13], [14, 15], [16, 17])"), @"NOT (_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}]))");
+        insta::assert_snapshot!(p("_geoPolygon([12,13],[14,15],[16,17],[18,19])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}], [{18}, {19}])");
+
+        // Test geo contains
+        insta::assert_snapshot!(p("_geoContains(12, 13)"), @"_geoContains({12}, {13})");
+        insta::assert_snapshot!(p("NOT _geoContains(12, 13)"), @"NOT (_geoContains({12}, {13}))");
+        insta::assert_snapshot!(p("_geoContains(12,13)"), @"_geoContains({12}, {13})");
+
+        // Test dates
+        insta::assert_snapshot!(p("createdAt > 2024-01-01T00:00:00Z"), @"{createdAt} > {2024-01-01T00:00:00Z}");
+        insta::assert_snapshot!(p("createdAt >= 2024-01-01"), @"{createdAt} >= {2024-01-01}");
+        insta::assert_snapshot!(p("createdAt 2024-01-01 TO 2024-12-31T23:59:59+02:00"), @"{createdAt} {2024-01-01} TO {2024-12-31T23:59:59+02:00}");
+        insta::assert_snapshot!(p("createdAt < 2024-06-01T12:30:00.5Z AND id = 1"), @"AND[{createdAt} < {2024-06-01T12:30:00.5Z}, {id} = {1}, ]");
+
+        // Test field comparisons
+        insta::assert_snapshot!(p("salePrice < listPrice"), @"{salePrice} < FIELD({listPrice})");
+        insta::assert_snapshot!(p("stock >= 'reserved stock'"), @"{stock} >= {reserved stock}");
+        insta::assert_snapshot!(p("code >= \"A100\" AND code < \"B\""), @"AND[{code} >= {A100}, {code} < {B}, ]");
+        insta::assert_snapshot!(p("NOT stock > reserved"), @"NOT ({stock} > FIELD({reserved}))");
+        insta::assert_snapshot!(p("stock > 12"), @"{stock} > {12}");
+        insta::assert_snapshot!(p("stock = reserved"), @"{stock} = {reserved}");
+
         // Test OR + AND
         insta::assert_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -782,7 +1033,7 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -792,12 +1043,12 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -826,6 +1077,31 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_snapshot!(p("_geoPolygon(1.0, 1.0)"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude])`.
+        1:22 _geoPolygon(1.0, 1.0)
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1, 2], [3, 4])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude])`.
+        28:28 _geoPolygon([1, 2], [3, 4])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1, 2], [3, 4], [5])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude])`.
+        33:33 _geoPolygon([1, 2], [3, 4], [5])
+        "###);
+
+        insta::assert_snapshot!(p("_geoContains(1.0)"), @r###"
+        The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.
+        18:18 _geoContains(1.0)
+        "###);
+
+        insta::assert_snapshot!(p("_geoContains([1, 2])"), @r###"
+        The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.
+        1:21 _geoContains([1, 2])
+        "###);
+
         insta::assert_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
         `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
@@ -861,6 +1137,16 @@ pub mod tests {
         13:35 position <= _geoRadius(12, 13, 14)
         "###);
 
+        insta::assert_snapshot!(p("position <= _geoPolygon([1, 2], [3, 4], [5, 6])"), @r###"
+        The `_geoPolygon` filter is an operation and can't be used as a value.
+        13:48 position <= _geoPolygon([1, 2], [3, 4], [5, 6])
+        "###);
+
+        insta::assert_snapshot!(p("position <= _geoContains(1, 2)"), @r###"
+        The `_geoContains` filter is an operation and can't be used as a value.
+        13:31 position <= _geoContains(1, 2)
+        "###);
+
         insta::assert_snapshot!(p("channel = 'ponce"), @r###"
         Expression `\'ponce` is missing the following closing delimiter: `'`.
         11:17 channel = 'ponce
@@ -876,18 +1162,23 @@ pub mod tests {
         17:35 channel = mv OR (followers >= 1000
         "###);
 
+        insta::assert_snapshot!(p("variants[color = red"), @r###"
+        Expression `variants[color = red` is missing the following closing delimiter: `]`.
+        1:21 variants[color = red
+        "###);
+
         insta::assert_snapshot!(p("channel = mv OR followers >= 1000)"), @r###"
         Found unexpected characters at the end of the filter: `)`. You probably forgot an `OR` or an `AND` rule.
         34:35 channel = mv OR followers >= 1000)
         "###);
 
         insta::assert_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -916,6 +1207,26 @@ pub mod tests {
         23:23 colour IN [blue, green
         "###);
 
+        insta::assert_snapshot!(p("tags ALL blue]"), @r###"
+        Expected `[` after `ALL` keyword.
+        10:15 tags ALL blue]
+        "###);
+
+        insta::assert_snapshot!(p("tags ALL [blue, green"), @r###"
+        Expected matching `]` after the list of field names given to `ALL[`
+        22:22 tags ALL [blue, green
+        "###);
+
+        insta::assert_snapshot!(p("SIZE(tags) ~ 2"), @r###"
+        The `SIZE` filter expects an attribute followed by a comparison: `SIZE(attribute) > 2` or `SIZE(attribute) 1 TO 3`.
+        1:15 SIZE(tags) ~ 2
+        "###);
+
+        insta::assert_snapshot!(p("SIZE(tags > 2"), @r###"
+        The `SIZE` filter expects an attribute followed by a comparison: `SIZE(attribute) > 2` or `SIZE(attribute) 1 TO 3`.
+        1:14 SIZE(tags > 2
+        "###);
+
         insta::assert_snapshot!(p("colour IN ['blue, green"), @r###"
         Expression `\'blue, green` is missing the following closing delimiter: `'`.
         12:24 colour IN ['blue, green
@@ -950,35 +1261,35 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p(r#"value NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value NULL`.
         1:11 value NULL
         "###);
         insta::assert_snapshot!(p(r#"value NOT NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value NOT NULL`.
         1:15 value NOT NULL
         "###);
         insta::assert_snapshot!(p(r#"value EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value EMPTY`.
         1:12 value EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value NOT EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value NOT EMPTY`.
         1:16 value NOT EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value IS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value IS`.
         1:9 value IS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value IS NOT`.
         1:13 value IS NOT
         "###);
         insta::assert_snapshot!(p(r#"value IS EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value IS EXISTS`.
         1:16 value IS EXISTS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `value IS NOT EXISTS`.
         1:20 value IS NOT EXISTS
         "###);
     }
@@ -1027,6 +1338,14 @@ pub mod tests {
         assert!(fids[0].value() == "field1");
         assert!(fids[1].value() == "field2");
 
+        let filter = Fc::parse("variants[color = red AND size > stock]").unwrap().unwrap();
+        let fids: Vec<_> = filter.fids(MAX_FILTER_DEPTH).collect();
+        assert_eq!(fids.len(), 4);
+        assert_eq!(fids[0].value(), "variants");
+        assert_eq!(fids[1].value(), "variants.color");
+        assert_eq!(fids[2].value(), "variants.size");
+        assert_eq!(fids[3].value(), "variants.stock");
+
         let depth = 2;
         let filter =
             Fc::parse("field1 = value1 AND (field2 = value2 OR field3 = value3)").unwrap().unwrap();
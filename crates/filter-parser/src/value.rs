@@ -7,8 +7,8 @@ use nom::{InputIter, InputLength, InputTake, Slice};
 
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
-    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_radius,
-    Error, ErrorKind, IResult, Span, Token,
+    parse_geo, parse_geo_bounding_box, parse_geo_contains, parse_geo_distance, parse_geo_point,
+    parse_geo_polygon, parse_geo_radius, Error, ErrorKind, IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -80,7 +80,22 @@ pub fn word_exact<'a, 'b: 'a>(tag: &'b str) -> impl Fn(Span<'a>) -> IResult<'a,
     }
 }
 
-/// value          = WS* ( word | singleQuoted | doubleQuoted) WS+
+// date           = RFC 3339 date-time, which may contain `:` and `+` unlike a word
+fn parse_date_literal(input: Span) -> IResult<Token> {
+    let (rest, date): (_, Token) =
+        take_while1(|c| is_value_component(c) || [':', '+'].contains(&c))(input)
+            .map(|(s, t)| (s, t.into()))?;
+    if crate::parse_rfc3339_date(date.value()).is_some() {
+        Ok((rest, date))
+    } else {
+        Err(nom::Err::Error(Error::new_from_kind(
+            input,
+            ErrorKind::InternalError(nom::error::ErrorKind::Verify),
+        )))
+    }
+}
+
+/// value          = WS* ( date | word | singleQuoted | doubleQuoted) WS+
 pub fn parse_value(input: Span) -> IResult<Token> {
     // to get better diagnostic message we are going to strip the left whitespaces from the input right now
     let (input, _) = take_while(char::is_whitespace)(input)?;
@@ -128,6 +143,42 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         _ => (),
     }
 
+    match parse_geo_polygon(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoPolygon filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        _ => (),
+    }
+
+    match parse_geo_contains(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoContains,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoContains filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoContains,
+            )))
+        }
+        _ => (),
+    }
+
     // this parser is only used when an error is encountered and it parse the
     // largest string possible that do not contain any “language” syntax.
     // If we try to parse `name = 🦀 AND language = rust` we want to return an
@@ -141,6 +192,7 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         alt((
             delimited(char('\''), cut(|input| quoted_by('\'', input)), cut(char('\''))),
             delimited(char('"'), cut(|input| quoted_by('"', input)), cut(char('"'))),
+            parse_date_literal,
             word_not_keyword,
         )),
         multispace0,
@@ -214,8 +266,11 @@ fn is_keyword(s: &str) -> bool {
             | "CONTAINS"
             | "STARTS"
             | "WITH"
+            | "UNDER"
             | "_geoRadius"
             | "_geoBoundingBox"
+            | "_geoPolygon"
+            | "_geoContains"
     )
 }
 
@@ -273,6 +273,12 @@ InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetRanges              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetHistograms          , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExtendedFacetStats       , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetChildren            , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetGroups              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
@@ -282,6 +288,7 @@ InvalidSimilarFilter                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPostTag         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPreTag          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSearchAfter              , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarLimit                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , InvalidRequest       , BAD_REQUEST ;
@@ -298,6 +305,11 @@ InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowSuggestedQuery       , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestQ                       , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestFilter                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestAttributesToSearchOn    , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestLimit                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
@@ -305,6 +317,10 @@ InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFacetSearch            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsInfixSearchableAttributes , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsCurationRules          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDateAttributes         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsNestedAttributes       , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
@@ -425,8 +441,11 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
+                    UserError::InvalidSearchCursor => Code::InvalidSearchSearchAfter,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetRanges { .. } => Code::InvalidSearchFacetRanges,
+                    UserError::InvalidFacetHistograms { .. } => Code::InvalidSearchFacetHistograms,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
@@ -440,10 +459,17 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
                     UserError::TooManyVectors(_, _) => Code::TooManyVectors,
+                    UserError::TooManyNestedElements { .. } => Code::InvalidDocumentFields,
                     UserError::SortError(_) => Code::InvalidSearchSort,
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidSettingsTypoTolerance
                     }
+                    UserError::InvalidFacetChildren { .. }
+                    | UserError::NotHierarchicalFacet { .. } => Code::InvalidSearchFacetChildren,
+                    UserError::InvalidFacetGroups { .. } => Code::InvalidSearchFacetGroups,
+                    UserError::InvalidHierarchicalFacetSeparator { .. } => {
+                        Code::InvalidSettingsFaceting
+                    }
                     UserError::InvalidSearchEmbedder(_) => Code::InvalidSearchEmbedder,
                     UserError::InvalidSimilarEmbedder(_) => Code::InvalidSimilarEmbedder,
                     UserError::VectorEmbeddingError(_) | UserError::DocumentEmbeddingError(_) => {
@@ -550,6 +576,12 @@ impl fmt::Display for deserr_codes::InvalidSearchSemanticRatio {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchFacetHistograms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the histogram interval is invalid, expected a strictly positive float (> 0.0).")
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidMultiSearchWeight {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "the value of `weight` is invalid, expected a positive float (>= 0.0).")
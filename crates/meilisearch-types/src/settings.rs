@@ -11,7 +11,10 @@ use fst::IntoStreamer;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, CurationRule, Index, PinnedDocument, QueryMatchType,
+    DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
 
@@ -118,6 +121,14 @@ pub struct FacetingSettings {
     #[deserr(default)]
     #[schema(value_type = Option<BTreeMap<String, FacetValuesSort>>, example = json!({ "genre": FacetValuesSort::Count }))]
     pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<BTreeMap<String, String>>, example = json!({ "category": " > " }))]
+    pub hierarchical_facets: Setting<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["sku"]))]
+    pub exact_facets: Setting<BTreeSet<String>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
@@ -288,6 +299,26 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Attributes in which the words can also be matched by their middle or their end, e.g. `*4471*`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsInfixSearchableAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["sku"]))]
+    pub infix_searchable_attributes: Setting<BTreeSet<String>>,
+    /// Rules pinning documents at given positions or hiding documents for the matching queries.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsCurationRules>)]
+    #[schema(value_type = Option<Vec<CurationRuleView>>, example = json!([{ "query": "iphone", "pinned": [{ "id": "iphone-16", "position": 0 }], "hidden": ["iphone-case"] }]))]
+    pub curation_rules: Setting<Vec<CurationRuleView>>,
+    /// Attributes whose RFC 3339 string values are indexed as dates, usable in range filters and sort.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDateAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["createdAt"]))]
+    pub date_attributes: Setting<BTreeSet<String>>,
+    /// Arrays of objects whose elements can be filtered one by one, each condition of the filter applying to the same element.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNestedAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["variants"]))]
+    pub nested_attributes: Setting<BTreeSet<String>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -354,6 +385,10 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            infix_searchable_attributes: Setting::Reset,
+            curation_rules: Setting::Reset,
+            date_attributes: Setting::Reset,
+            nested_attributes: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -380,6 +415,10 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            infix_searchable_attributes,
+            curation_rules,
+            date_attributes,
+            nested_attributes,
             _kind,
         } = self;
 
@@ -404,6 +443,10 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            infix_searchable_attributes,
+            curation_rules,
+            date_attributes,
+            nested_attributes,
             _kind: PhantomData,
         }
     }
@@ -454,6 +497,10 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            infix_searchable_attributes: self.infix_searchable_attributes,
+            curation_rules: self.curation_rules,
+            date_attributes: self.date_attributes,
+            nested_attributes: self.nested_attributes,
             _kind: PhantomData,
         }
     }
@@ -529,6 +576,13 @@ impl Settings<Unchecked> {
                 }
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
+            infix_searchable_attributes: other
+                .infix_searchable_attributes
+                .clone()
+                .or(self.infix_searchable_attributes.clone()),
+            curation_rules: other.curation_rules.clone().or(self.curation_rules.clone()),
+            date_attributes: other.date_attributes.clone().or(self.date_attributes.clone()),
+            nested_attributes: other.nested_attributes.clone().or(self.nested_attributes.clone()),
             facet_search: other.facet_search.or(self.facet_search),
             _kind: PhantomData,
         }
@@ -568,6 +622,10 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        infix_searchable_attributes,
+        curation_rules,
+        date_attributes,
+        nested_attributes,
         _kind,
     } = settings;
 
@@ -714,7 +772,12 @@ pub fn apply_settings_to_builder(
     }
 
     match faceting {
-        Setting::Set(FacetingSettings { max_values_per_facet, sort_facet_values_by }) => {
+        Setting::Set(FacetingSettings {
+            max_values_per_facet,
+            sort_facet_values_by,
+            hierarchical_facets,
+            exact_facets,
+        }) => {
             match max_values_per_facet {
                 Setting::Set(val) => builder.set_max_values_per_facet(*val),
                 Setting::Reset => builder.reset_max_values_per_facet(),
@@ -727,10 +790,22 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_sort_facet_values_by(),
                 Setting::NotSet => (),
             }
+            match hierarchical_facets {
+                Setting::Set(val) => builder.set_hierarchical_facets(val.clone()),
+                Setting::Reset => builder.reset_hierarchical_facets(),
+                Setting::NotSet => (),
+            }
+            match exact_facets {
+                Setting::Set(attrs) => builder.set_exact_facets(attrs.iter().cloned().collect()),
+                Setting::Reset => builder.reset_exact_facets(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             builder.reset_max_values_per_facet();
             builder.reset_sort_facet_values_by();
+            builder.reset_hierarchical_facets();
+            builder.reset_exact_facets();
         }
         Setting::NotSet => (),
     }
@@ -772,6 +847,34 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match infix_searchable_attributes {
+        Setting::Set(ref attrs) => {
+            builder.set_infix_searchable_attributes(attrs.iter().cloned().collect())
+        }
+        Setting::Reset => builder.reset_infix_searchable_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match curation_rules {
+        Setting::Set(rules) => {
+            builder.set_curation_rules(rules.iter().cloned().map(|r| r.into()).collect())
+        }
+        Setting::Reset => builder.reset_curation_rules(),
+        Setting::NotSet => (),
+    }
+
+    match date_attributes {
+        Setting::Set(ref attrs) => builder.set_date_attributes(attrs.iter().cloned().collect()),
+        Setting::Reset => builder.reset_date_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match nested_attributes {
+        Setting::Set(ref attrs) => builder.set_nested_attributes(attrs.iter().cloned().collect()),
+        Setting::Reset => builder.reset_nested_attributes(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -848,6 +951,10 @@ pub fn settings(
                 .map(|(name, sort)| (name, sort.into()))
                 .collect(),
         ),
+        hierarchical_facets: Setting::Set(index.hierarchical_facets(rtxn)?),
+        exact_facets: Setting::Set(
+            index.exact_facets(rtxn)?.into_iter().map(String::from).collect(),
+        ),
     };
 
     let pagination = PaginationSettings {
@@ -876,6 +983,15 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let infix_searchable_attributes =
+        index.infix_searchable_attributes(rtxn)?.into_iter().map(String::from).collect();
+
+    let curation_rules = index.curation_rules(rtxn)?.into_iter().map(|r| r.into()).collect();
+
+    let date_attributes = index.date_attributes(rtxn)?.into_iter().map(String::from).collect();
+
+    let nested_attributes = index.nested_attributes(rtxn)?.into_iter().map(String::from).collect();
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -913,6 +1029,10 @@ pub fn settings(
             None => Setting::Reset,
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
+        infix_searchable_attributes: Setting::Set(infix_searchable_attributes),
+        curation_rules: Setting::Set(curation_rules),
+        date_attributes: Setting::Set(date_attributes),
+        nested_attributes: Setting::Set(nested_attributes),
         facet_search: Setting::Set(facet_search),
         _kind: PhantomData,
     };
@@ -945,6 +1065,10 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by how close the value of the field specified is to an origin.
+    Decay(milli::Decay),
+    /// The documents matching the filter expression are ranked before the others.
+    Boost(String),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1001,6 +1125,8 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Decay(x) => RankingRuleView::Decay(x),
+            Criterion::Boost(x) => RankingRuleView::Boost(x),
         }
     }
 }
@@ -1015,6 +1141,8 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Decay(x) => Criterion::Decay(x),
+            RankingRuleView::Boost(x) => Criterion::Boost(x),
         }
     }
 }
@@ -1045,6 +1173,80 @@ impl From<ProximityPrecisionView> for ProximityPrecision {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct CurationRuleView {
+    /// The query the rule applies to.
+    pub query: String,
+    /// Whether the search query must be the rule query or only contain its words.
+    #[serde(default)]
+    #[deserr(default)]
+    pub match_type: QueryMatchTypeView,
+    /// The documents to pin at given positions in the results.
+    #[serde(default)]
+    #[deserr(default)]
+    pub pinned: Vec<PinnedDocumentView>,
+    /// The ids of the documents to hide from the results.
+    #[serde(default)]
+    #[deserr(default)]
+    pub hidden: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum QueryMatchTypeView {
+    #[default]
+    Exact,
+    ContainsWords,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocumentView {
+    pub id: String,
+    /// The 0-based position of the document in the results.
+    pub position: usize,
+}
+
+impl From<CurationRule> for CurationRuleView {
+    fn from(rule: CurationRule) -> Self {
+        Self {
+            query: rule.query,
+            match_type: match rule.match_type {
+                QueryMatchType::Exact => QueryMatchTypeView::Exact,
+                QueryMatchType::ContainsWords => QueryMatchTypeView::ContainsWords,
+            },
+            pinned: rule
+                .pinned
+                .into_iter()
+                .map(|PinnedDocument { id, position }| PinnedDocumentView { id, position })
+                .collect(),
+            hidden: rule.hidden,
+        }
+    }
+}
+
+impl From<CurationRuleView> for CurationRule {
+    fn from(view: CurationRuleView) -> Self {
+        Self {
+            query: view.query,
+            match_type: match view.match_type {
+                QueryMatchTypeView::Exact => QueryMatchType::Exact,
+                QueryMatchTypeView::ContainsWords => QueryMatchType::ContainsWords,
+            },
+            pinned: view
+                .pinned
+                .into_iter()
+                .map(|PinnedDocumentView { id, position }| PinnedDocument { id, position })
+                .collect(),
+            hidden: view.hidden,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
 pub struct WildcardSetting(Setting<Vec<String>>);
 
@@ -1141,6 +1343,10 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            curation_rules: Setting::NotSet,
+            date_attributes: Setting::NotSet,
+            nested_attributes: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1172,6 +1378,10 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            curation_rules: Setting::NotSet,
+            date_attributes: Setting::NotSet,
+            nested_attributes: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -301,6 +301,7 @@ impl From<FacetSearchQuery> for SearchQuery {
             limit: DEFAULT_SEARCH_LIMIT(),
             page: None,
             hits_per_page: None,
+            search_after: None,
             attributes_to_retrieve: None,
             retrieve_vectors: false,
             attributes_to_crop: None,
@@ -309,10 +310,17 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_matches_position: false,
             show_ranking_score: false,
             show_ranking_score_details: false,
+            show_suggested_query: false,
             filter,
             sort: None,
             distinct: None,
             facets: None,
+            facet_ranges: None,
+            facet_histograms: None,
+            facet_children: None,
+            facet_groups: None,
+            disjunctive_facets: false,
+            extended_facet_stats: false,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
@@ -38,6 +38,7 @@ pub mod settings;
 mod settings_analytics;
 pub mod similar;
 mod similar_analytics;
+pub mod suggest;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -45,6 +46,7 @@ mod similar_analytics;
         (path = "/", api = documents::DocumentsApi),
         (path = "/", api = facet_search::FacetSearchApi),
         (path = "/", api = similar::SimilarApi),
+        (path = "/", api = suggest::SuggestApi),
         (path = "/", api = settings::SettingsApi),
     ),
     paths(list_indexes, create_index, get_index, update_index, delete_index, get_index_stats),
@@ -77,6 +79,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
             .service(web::scope("/similar").configure(similar::configure))
+            .service(web::scope("/suggest").configure(suggest::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
 }
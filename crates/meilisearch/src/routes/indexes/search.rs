@@ -74,6 +74,8 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchHitsPerPage>)]
     #[param(value_type = Option<usize>)]
     hits_per_page: Option<Param<usize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAfter>)]
+    search_after: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToRetrieve>)]
     #[param(value_type = Vec<String>, explode = false)]
     attributes_to_retrieve: Option<CS<String>>,
@@ -104,9 +106,18 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScoreDetails>)]
     #[param(value_type = bool)]
     show_ranking_score_details: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowSuggestedQuery>)]
+    #[param(value_type = bool)]
+    show_suggested_query: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     #[param(value_type = Vec<String>, explode = false)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    #[param(value_type = bool)]
+    disjunctive_facets: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchExtendedFacetStats>)]
+    #[param(value_type = bool)]
+    extended_facet_stats: Param<bool>,
     #[deserr(default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     #[param(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     highlight_pre_tag: String,
@@ -210,6 +221,7 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
             hits_per_page: other.hits_per_page.as_deref().copied(),
+            search_after: other.search_after,
             attributes_to_retrieve: other.attributes_to_retrieve.map(|o| o.into_iter().collect()),
             retrieve_vectors: other.retrieve_vectors.0,
             attributes_to_crop: other.attributes_to_crop.map(|o| o.into_iter().collect()),
@@ -221,7 +233,15 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
+            show_suggested_query: other.show_suggested_query.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            // the facet ranges and histograms are only available on the POST route
+            facet_ranges: None,
+            facet_histograms: None,
+            facet_children: None,
+            facet_groups: None,
+            disjunctive_facets: other.disjunctive_facets.0,
+            extended_facet_stats: other.extended_facet_stats.0,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
@@ -329,7 +349,9 @@ pub async fn search_with_url_query(
     let mut query: SearchQuery = params.into_inner().try_into()?;
 
     // Tenant token search_rules.
+    let mut search_rules_filter = None;
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        search_rules_filter = search_rules.filter.clone();
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -346,6 +368,7 @@ pub async fn search_with_url_query(
             index_uid.to_string(),
             &index,
             query,
+            search_rules_filter,
             search_kind,
             retrieve_vector,
             index_scheduler.features(),
@@ -435,7 +458,9 @@ pub async fn search_with_post(
     debug!(parameters = ?query, "Search post");
 
     // Tenant token search_rules.
+    let mut search_rules_filter = None;
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        search_rules_filter = search_rules.filter.clone();
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -453,6 +478,7 @@ pub async fn search_with_post(
             index_uid.to_string(),
             &index,
             query,
+            search_rules_filter,
             search_kind,
             retrieve_vectors,
             index_scheduler.features(),
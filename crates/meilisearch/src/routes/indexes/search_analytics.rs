@@ -53,6 +53,8 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
+    // Whether a suggested query was requested
+    show_suggested_query: bool,
 
     // vector
     // The maximum number of floats in a vector request
@@ -72,6 +74,8 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     max_limit: usize,
     max_offset: usize,
     finite_pagination: usize,
+    // every time a search is resumed after a cursor
+    total_search_after: usize,
 
     // formatting
     max_attributes_to_retrieve: usize,
@@ -86,6 +90,12 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // facets
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
+    facet_ranges: bool,
+    facet_histograms: bool,
+    facet_children: bool,
+    facet_groups: bool,
+    disjunctive_facets: bool,
+    extended_facet_stats: bool,
 
     // scoring
     show_ranking_score: bool,
@@ -105,6 +115,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve: _,
             retrieve_vectors,
             attributes_to_crop: _,
@@ -113,10 +124,17 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             show_matches_position,
             show_ranking_score,
             show_ranking_score_details,
+            show_suggested_query,
             filter,
             sort,
             distinct,
             facets: _,
+            facet_ranges,
+            facet_histograms,
+            facet_children,
+            facet_groups,
+            disjunctive_facets,
+            extended_facet_stats,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -138,6 +156,12 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         }
 
         ret.distinct = distinct.is_some();
+        ret.facet_ranges = facet_ranges.is_some();
+        ret.facet_histograms = facet_histograms.is_some();
+        ret.facet_children = facet_children.is_some();
+        ret.facet_groups = facet_groups.is_some();
+        ret.disjunctive_facets = *disjunctive_facets;
+        ret.extended_facet_stats = *extended_facet_stats;
 
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
@@ -152,6 +176,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
                         "array".to_string()
                     }
                 }
+                Value::Object(_) => "object".to_string(),
                 _ => "none".to_string(),
             };
             // convert the string to a HashMap
@@ -171,6 +196,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
+        ret.show_suggested_query = *show_suggested_query;
 
         if let Some(ref vector) = vector {
             ret.max_vector_size = vector.len();
@@ -187,6 +213,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             ret.max_offset = *offset;
             ret.finite_pagination = 0;
         }
+        ret.total_search_after = search_after.is_some() as usize;
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
 
@@ -218,9 +245,15 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             query: _,
             processing_time_ms,
             hits_info: _,
+            search_after: _,
             semantic_hit_count: _,
+            suggested_query: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_ranges: _,
+            facet_histograms: _,
+            facet_children: _,
+            facet_groups: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -257,12 +290,14 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            show_suggested_query,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
             max_limit,
             max_offset,
             finite_pagination,
+            total_search_after,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
@@ -273,6 +308,12 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facet_ranges,
+            facet_histograms,
+            facet_children,
+            facet_groups,
+            disjunctive_facets,
+            extended_facet_stats,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
@@ -321,6 +362,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
 
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
+        self.show_suggested_query |= show_suggested_query;
 
         // vector
         self.max_vector_size = self.max_vector_size.max(max_vector_size);
@@ -332,6 +374,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
         self.finite_pagination += finite_pagination;
+        self.total_search_after = self.total_search_after.saturating_add(total_search_after);
 
         // formatting
         self.max_attributes_to_retrieve =
@@ -349,6 +392,12 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.facets_sum_of_terms = self.facets_sum_of_terms.saturating_add(facets_sum_of_terms);
         self.facets_total_number_of_facets =
             self.facets_total_number_of_facets.saturating_add(facets_total_number_of_facets);
+        self.facet_ranges |= facet_ranges;
+        self.facet_histograms |= facet_histograms;
+        self.facet_children |= facet_children;
+        self.facet_groups |= facet_groups;
+        self.disjunctive_facets |= disjunctive_facets;
+        self.extended_facet_stats |= extended_facet_stats;
 
         // matching strategy
         for (key, value) in matching_strategy.into_iter() {
@@ -383,12 +432,14 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            show_suggested_query,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
             max_limit,
             max_offset,
             finite_pagination,
+            total_search_after,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
@@ -399,6 +450,12 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facet_ranges,
+            facet_histograms,
+            facet_children,
+            facet_groups,
+            disjunctive_facets,
+            extended_facet_stats,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
@@ -442,6 +499,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             },
             "q": {
                "max_terms_number": max_terms_number,
+               "show_suggested_query": show_suggested_query,
             },
             "vector": {
                 "max_vector_size": max_vector_size,
@@ -455,6 +513,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
                "max_limit": max_limit,
                "max_offset": max_offset,
                "most_used_navigation": if finite_pagination > (total_received / 2) { "exhaustive" } else { "estimated" },
+               "total_search_after": total_search_after,
             },
             "formatting": {
                 "max_attributes_to_retrieve": max_attributes_to_retrieve,
@@ -468,6 +527,12 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             },
             "facets": {
                 "avg_facets_number": format!("{:.2}", facets_sum_of_terms as f64 / facets_total_number_of_facets as f64),
+                "ranges": facet_ranges,
+                "histograms": facet_histograms,
+                "children": facet_children,
+                "groups": facet_groups,
+                "disjunctive": disjunctive_facets,
+                "extended_stats": extended_facet_stats,
             },
             "matching_strategy": {
                 "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
@@ -502,6 +502,50 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/infix-searchable-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsInfixSearchableAttributes,
+        >,
+        attr: infix_searchable_attributes,
+        camelcase_attr: "infixSearchableAttributes",
+        analytics: InfixSearchableAttributesAnalytics
+    },
+    {
+        route: "/curation-rules",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::settings::CurationRuleView>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsCurationRules,
+        >,
+        attr: curation_rules,
+        camelcase_attr: "curationRules",
+        analytics: CurationRulesAnalytics
+    },
+    {
+        route: "/date-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsDateAttributes,
+        >,
+        attr: date_attributes,
+        camelcase_attr: "dateAttributes",
+        analytics: DateAttributesAnalytics
+    },
+    {
+        route: "/nested-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsNestedAttributes,
+        >,
+        attr: nested_attributes,
+        camelcase_attr: "nestedAttributes",
+        analytics: NestedAttributesAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -591,6 +635,16 @@ pub async fn update_all(
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            infix_searchable_attributes: InfixSearchableAttributesAnalytics::new(
+                new_settings.infix_searchable_attributes.as_ref().set(),
+            ),
+            curation_rules: CurationRulesAnalytics::new(new_settings.curation_rules.as_ref().set()),
+            date_attributes: DateAttributesAnalytics::new(
+                new_settings.date_attributes.as_ref().set(),
+            ),
+            nested_attributes: NestedAttributesAnalytics::new(
+                new_settings.nested_attributes.as_ref().set(),
+            ),
         },
         &req,
     );
@@ -9,8 +9,8 @@ use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
 use meilisearch_types::milli::update::Setting;
 use meilisearch_types::settings::{
-    FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
-    RankingRuleView, SettingEmbeddingSettings, TypoSettings,
+    CurationRuleView, FacetingSettings, PaginationSettings, PrefixSearchSettings,
+    ProximityPrecisionView, RankingRuleView, SettingEmbeddingSettings, TypoSettings,
 };
 use serde::Serialize;
 
@@ -38,6 +38,10 @@ pub struct SettingsAnalytics {
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub infix_searchable_attributes: InfixSearchableAttributesAnalytics,
+    pub curation_rules: CurationRulesAnalytics,
+    pub date_attributes: DateAttributesAnalytics,
+    pub nested_attributes: NestedAttributesAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -129,6 +133,14 @@ impl Aggregate for SettingsAnalytics {
                     .faceting
                     .sort_facet_values_by_total
                     .or(self.faceting.sort_facet_values_by_total),
+                hierarchical_facets_total: new
+                    .faceting
+                    .hierarchical_facets_total
+                    .or(self.faceting.hierarchical_facets_total),
+                exact_facets_total: new
+                    .faceting
+                    .exact_facets_total
+                    .or(self.faceting.exact_facets_total),
             },
             pagination: PaginationAnalytics {
                 max_total_hits: new.pagination.max_total_hits.or(self.pagination.max_total_hits),
@@ -193,6 +205,23 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            infix_searchable_attributes: InfixSearchableAttributesAnalytics {
+                total: new
+                    .infix_searchable_attributes
+                    .total
+                    .or(self.infix_searchable_attributes.total),
+            },
+            curation_rules: CurationRulesAnalytics {
+                total: new.curation_rules.total.or(self.curation_rules.total),
+                total_pinned: new.curation_rules.total_pinned.or(self.curation_rules.total_pinned),
+                total_hidden: new.curation_rules.total_hidden.or(self.curation_rules.total_hidden),
+            },
+            date_attributes: DateAttributesAnalytics {
+                total: new.date_attributes.total.or(self.date_attributes.total),
+            },
+            nested_attributes: NestedAttributesAnalytics {
+                total: new.nested_attributes.total.or(self.nested_attributes.total),
+            },
         })
     }
 
@@ -249,6 +278,8 @@ impl RankingRulesAnalytics {
                             s,
                             meilisearch_types::settings::RankingRuleView::Asc(_)
                                 | meilisearch_types::settings::RankingRuleView::Desc(_)
+                                | meilisearch_types::settings::RankingRuleView::Decay(_)
+                                | meilisearch_types::settings::RankingRuleView::Boost(_)
                         )
                     })
                     .map(|x| x.to_string())
@@ -413,6 +444,8 @@ pub struct FacetingAnalytics {
     pub max_values_per_facet: Option<usize>,
     pub sort_facet_values_by_star_count: Option<bool>,
     pub sort_facet_values_by_total: Option<usize>,
+    pub hierarchical_facets_total: Option<usize>,
+    pub exact_facets_total: Option<usize>,
 }
 
 impl FacetingAnalytics {
@@ -428,6 +461,12 @@ impl FacetingAnalytics {
             sort_facet_values_by_total: setting
                 .as_ref()
                 .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+            hierarchical_facets_total: setting
+                .as_ref()
+                .and_then(|s| s.hierarchical_facets.as_ref().set().map(|s| s.len())),
+            exact_facets_total: setting
+                .as_ref()
+                .and_then(|s| s.exact_facets.as_ref().set().map(|s| s.len())),
         }
     }
 
@@ -662,3 +701,69 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct InfixSearchableAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl InfixSearchableAttributesAnalytics {
+    pub fn new(setting: Option<&BTreeSet<String>>) -> Self {
+        Self { total: setting.as_ref().map(|attrs| attrs.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { infix_searchable_attributes: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct CurationRulesAnalytics {
+    pub total: Option<usize>,
+    pub total_pinned: Option<usize>,
+    pub total_hidden: Option<usize>,
+}
+
+impl CurationRulesAnalytics {
+    pub fn new(rules: Option<&Vec<CurationRuleView>>) -> Self {
+        Self {
+            total: rules.map(|rules| rules.len()),
+            total_pinned: rules.map(|rules| rules.iter().map(|rule| rule.pinned.len()).sum()),
+            total_hidden: rules.map(|rules| rules.iter().map(|rule| rule.hidden.len()).sum()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { curation_rules: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct DateAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl DateAttributesAnalytics {
+    pub fn new(setting: Option<&BTreeSet<String>>) -> Self {
+        Self { total: setting.as_ref().map(|attrs| attrs.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { date_attributes: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct NestedAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl NestedAttributesAnalytics {
+    pub fn new(setting: Option<&BTreeSet<String>>) -> Self {
+        Self { total: setting.as_ref().map(|attrs| attrs.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { nested_attributes: self, ..Default::default() }
+    }
+}
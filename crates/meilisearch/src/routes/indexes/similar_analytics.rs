@@ -80,6 +80,7 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
                         "array".to_string()
                     }
                 }
+                Value::Object(_) => "object".to_string(),
                 _ => "none".to_string(),
             };
             // convert the string to a HashMap
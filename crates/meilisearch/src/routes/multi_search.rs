@@ -243,12 +243,19 @@ pub async fn multi_search_with_post(
                     )
                     .with_index(query_index)?;
                     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
+                    // the search rules were already merged in the filter, but disjunctive facets
+                    // must keep applying them on their own
+                    let search_rules_filter = index_scheduler
+                        .filters()
+                        .get_index_search_rules(&index_uid_str)
+                        .and_then(|search_rules| search_rules.filter);
 
                     let search_result = tokio::task::spawn_blocking(move || {
                         perform_search(
                             index_uid_str.clone(),
                             &index,
                             query,
+                            search_rules_filter,
                             search_kind,
                             retrieve_vector,
                             features,
@@ -46,6 +46,7 @@ impl MultiSearchAggregator {
                     limit: _,
                     page: _,
                     hits_per_page: _,
+                    search_after: _,
                     attributes_to_retrieve: _,
                     retrieve_vectors: _,
                     attributes_to_crop: _,
@@ -53,11 +54,18 @@ impl MultiSearchAggregator {
                     attributes_to_highlight: _,
                     show_ranking_score: _,
                     show_ranking_score_details: _,
+                    show_suggested_query: _,
                     show_matches_position: _,
                     filter: _,
                     sort: _,
                     distinct: _,
                     facets: _,
+                    facet_ranges: _,
+                    facet_histograms: _,
+                    facet_children: _,
+                    facet_groups: _,
+                    disjunctive_facets: _,
+                    extended_facet_stats: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
@@ -482,6 +482,14 @@ pub fn perform_federated_search(
             .into());
         }
 
+        if let Some(facet_buckets_field) = federated_query.has_facet_buckets() {
+            return Err(MeilisearchHttpError::FacetBucketsInFederatedQuery(
+                query_index,
+                facet_buckets_field,
+            )
+            .into());
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         queries_by_index.entry(index_uid.into_inner()).or_default().push(QueryByIndex {
@@ -668,6 +676,7 @@ pub fn perform_federated_search(
                     candidates: query_candidates,
                     documents_ids,
                     document_scores,
+                    cursor: _,
                     degraded: query_degraded,
                     used_negative_operator: query_used_negative_operator,
                 } = result;
@@ -744,6 +753,7 @@ pub fn perform_federated_search(
                     &index,
                     &rtxn,
                     candidates,
+                    false,
                     super::Route::MultiSearch,
                 )
             })
@@ -806,6 +816,7 @@ pub fn perform_federated_search(
                 &index,
                 &rtxn,
                 Default::default(),
+                false,
                 super::Route::MultiSearch,
             ) {
                 error.message =
@@ -5,6 +5,8 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use deserr::Deserr;
 use either::Either;
 use index_scheduler::RoFeatures;
@@ -20,14 +22,16 @@ use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::vector::Embedder;
 use meilisearch_types::milli::{
-    FacetValueHit, InternalError, OrderBy, SearchForFacetValues, TimeBudget,
+    FacetRange, FacetRangeCount, FacetValueHit, InternalError, OrderBy, SearchForFacetValues,
+    SuggestionHit, TimeBudget,
 };
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, FacetNumberStats, FieldId, FieldsIdsMap, Filter, FormatOptions, Index,
+    LocalizedAttributesRule, MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::Serialize;
@@ -55,6 +59,13 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_SUGGEST_LIMIT: fn() -> usize = || 10;
+
+/// A suggested query is only computed when the search returns fewer hits than this.
+pub const SUGGESTED_QUERY_MAX_HITS: usize = 5;
+
+/// The percentiles returned in the facet stats with `extendedFacetStats`.
+const EXTENDED_STATS_PERCENTILES: [f64; 7] = [1.0, 5.0, 25.0, 50.0, 75.0, 95.0, 99.0];
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -75,6 +86,8 @@ pub struct SearchQuery {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
@@ -92,6 +105,8 @@ pub struct SearchQuery {
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>)]
     pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowSuggestedQuery>)]
+    pub show_suggested_query: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
@@ -100,6 +115,20 @@ pub struct SearchQuery {
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    #[schema(value_type = Option<BTreeMap<String, Vec<FacetRangeQuery>>>)]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeQuery>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistograms>)]
+    #[schema(value_type = Option<BTreeMap<String, f64>>)]
+    pub facet_histograms: Option<BTreeMap<String, HistogramInterval>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetChildren>)]
+    pub facet_children: Option<BTreeMap<String, String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetGroups>)]
+    pub facet_groups: Option<BTreeMap<String, Vec<String>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExtendedFacetStats>)]
+    pub extended_facet_stats: bool,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     #[schema(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     pub highlight_pre_tag: String,
@@ -136,6 +165,38 @@ impl std::convert::TryFrom<f64> for RankingScoreThreshold {
     }
 }
 
+/// A range of values of a numeric facet, `from` being included and `to` excluded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetRangeQuery {
+    #[deserr(default)]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    pub to: Option<f64>,
+}
+
+impl From<FacetRangeQuery> for FacetRange {
+    fn from(FacetRangeQuery { from, to }: FacetRangeQuery) -> Self {
+        FacetRange { from, to }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidSearchFacetHistograms)]
+pub struct HistogramInterval(f64);
+
+impl std::convert::TryFrom<f64> for HistogramInterval {
+    type Error = InvalidSearchFacetHistograms;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f.is_finite() && f > 0.0 {
+            Ok(HistogramInterval(f))
+        } else {
+            Err(InvalidSearchFacetHistograms)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserr)]
 #[deserr(try_from(f64) = TryFrom::try_from -> InvalidSimilarRankingScoreThreshold)]
 pub struct RankingScoreThresholdSimilar(f64);
@@ -167,6 +228,7 @@ impl fmt::Debug for SearchQuery {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             retrieve_vectors,
             attributes_to_crop,
@@ -175,10 +237,17 @@ impl fmt::Debug for SearchQuery {
             show_matches_position,
             show_ranking_score,
             show_ranking_score_details,
+            show_suggested_query,
             filter,
             sort,
             distinct,
             facets,
+            facet_ranges,
+            facet_histograms,
+            facet_children,
+            facet_groups,
+            disjunctive_facets,
+            extended_facet_stats,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -198,6 +267,9 @@ impl fmt::Debug for SearchQuery {
         if let Some(hits_per_page) = hits_per_page {
             debug.field("hits_per_page", &hits_per_page);
         }
+        if let Some(search_after) = search_after {
+            debug.field("search_after", &search_after);
+        }
 
         // Then, everything related to the queries
         if let Some(q) = q {
@@ -234,6 +306,24 @@ impl fmt::Debug for SearchQuery {
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
+        if let Some(facet_histograms) = facet_histograms {
+            debug.field("facet_histograms", &facet_histograms);
+        }
+        if let Some(facet_children) = facet_children {
+            debug.field("facet_children", &facet_children);
+        }
+        if let Some(facet_groups) = facet_groups {
+            debug.field("facet_groups", &facet_groups);
+        }
+        if *disjunctive_facets {
+            debug.field("disjunctive_facets", disjunctive_facets);
+        }
+        if *extended_facet_stats {
+            debug.field("extended_facet_stats", extended_facet_stats);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -247,6 +337,9 @@ impl fmt::Debug for SearchQuery {
         if *show_ranking_score_details {
             debug.field("self.show_ranking_score_details", show_ranking_score_details);
         }
+        if *show_suggested_query {
+            debug.field("show_suggested_query", show_suggested_query);
+        }
         debug.field("crop_length", &crop_length);
         if let Some(facets) = facets {
             debug.field("facets", &facets);
@@ -431,6 +524,8 @@ pub struct SearchQueryWithIndex {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
@@ -445,6 +540,8 @@ pub struct SearchQueryWithIndex {
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
     pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowSuggestedQuery>, default)]
+    pub show_suggested_query: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
@@ -455,6 +552,20 @@ pub struct SearchQueryWithIndex {
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    #[schema(value_type = Option<BTreeMap<String, Vec<FacetRangeQuery>>>)]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeQuery>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistograms>)]
+    #[schema(value_type = Option<BTreeMap<String, f64>>)]
+    pub facet_histograms: Option<BTreeMap<String, HistogramInterval>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetChildren>)]
+    pub facet_children: Option<BTreeMap<String, String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetGroups>)]
+    pub facet_groups: Option<BTreeMap<String, Vec<String>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExtendedFacetStats>)]
+    pub extended_facet_stats: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -484,6 +595,8 @@ impl SearchQueryWithIndex {
             Some("page")
         } else if self.hits_per_page.is_some() {
             Some("hitsPerPage")
+        } else if self.search_after.is_some() {
+            Some("searchAfter")
         } else {
             None
         }
@@ -493,6 +606,22 @@ impl SearchQueryWithIndex {
         self.facets.as_deref().filter(|v| !v.is_empty())
     }
 
+    pub fn has_facet_buckets(&self) -> Option<&'static str> {
+        if self.facet_ranges.is_some() {
+            Some("facetRanges")
+        } else if self.facet_histograms.is_some() {
+            Some("facetHistograms")
+        } else if self.facet_children.is_some() {
+            Some("facetChildren")
+        } else if self.facet_groups.is_some() {
+            Some("facetGroups")
+        } else if self.extended_facet_stats {
+            Some("extendedFacetStats")
+        } else {
+            None
+        }
+    }
+
     pub fn into_index_query_federation(self) -> (IndexUid, SearchQuery, Option<FederationOptions>) {
         let SearchQueryWithIndex {
             index_uid,
@@ -503,6 +632,7 @@ impl SearchQueryWithIndex {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             retrieve_vectors,
             attributes_to_crop,
@@ -510,11 +640,18 @@ impl SearchQueryWithIndex {
             attributes_to_highlight,
             show_ranking_score,
             show_ranking_score_details,
+            show_suggested_query,
             show_matches_position,
             filter,
             sort,
             distinct,
             facets,
+            facet_ranges,
+            facet_histograms,
+            facet_children,
+            facet_groups,
+            disjunctive_facets,
+            extended_facet_stats,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -533,6 +670,7 @@ impl SearchQueryWithIndex {
                 limit: limit.unwrap_or(DEFAULT_SEARCH_LIMIT()),
                 page,
                 hits_per_page,
+                search_after,
                 attributes_to_retrieve,
                 retrieve_vectors,
                 attributes_to_crop,
@@ -540,11 +678,18 @@ impl SearchQueryWithIndex {
                 attributes_to_highlight,
                 show_ranking_score,
                 show_ranking_score_details,
+                show_suggested_query,
                 show_matches_position,
                 filter,
                 sort,
                 distinct,
                 facets,
+                facet_ranges,
+                facet_histograms,
+                facet_children,
+                facet_groups,
+                disjunctive_facets,
+                extended_facet_stats,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
@@ -604,6 +749,20 @@ impl ExternalDocumentId {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SuggestQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestQ>)]
+    pub q: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestAttributesToSearchOn>)]
+    pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default = DEFAULT_SUGGEST_LIMIT(), error = DeserrJsonError<InvalidSuggestLimit>)]
+    #[schema(default = DEFAULT_SUGGEST_LIMIT)]
+    pub limit: usize,
+}
+
 impl TryFrom<String> for ExternalDocumentId {
     type Error = InvalidSimilarId;
 
@@ -681,6 +840,9 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Whether the document was pinned at its position by a curation rule
+    #[serde(rename = "_pinned", skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
 }
 
 #[derive(Serialize, Clone, PartialEq, ToSchema)]
@@ -692,15 +854,39 @@ pub struct SearchResult {
     pub processing_time_ms: u128,
     #[serde(flatten)]
     pub hits_info: HitsInfo,
+    /// An opaque cursor to pass as `searchAfter` to get the hits following this page,
+    /// returned when the page is full
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_after: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<BTreeMap<String, Value>>)]
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    /// The number of hits within each of the requested `facetRanges`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
+    /// The number of hits within each bucket of the requested `facetHistograms`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_histograms: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
+    /// The number of hits under each child of the nodes requested in `facetChildren`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<BTreeMap<String, BTreeMap<String, u64>>>)]
+    pub facet_children: Option<BTreeMap<String, IndexMap<String, u64>>>,
+    /// The hits grouped by each value of the facets requested in `facetGroups`, along with
+    /// the stats of their metric facets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<BTreeMap<String, BTreeMap<String, FacetGroup>>>)]
+    pub facet_groups: Option<BTreeMap<String, IndexMap<String, FacetGroup>>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    /// A corrected version of the query, returned when `showSuggestedQuery` is set
+    /// and the query returns few or no hits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_query: Option<String>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -715,9 +901,15 @@ impl fmt::Debug for SearchResult {
             query,
             processing_time_ms,
             hits_info,
+            search_after,
             facet_distribution,
             facet_stats,
+            facet_ranges,
+            facet_histograms,
+            facet_children,
+            facet_groups,
             semantic_hit_count,
+            suggested_query,
             degraded,
             used_negative_operator,
         } = self;
@@ -728,6 +920,9 @@ impl fmt::Debug for SearchResult {
         debug.field("hits", &format!("[{} hits returned]", hits.len()));
         debug.field("query", &query);
         debug.field("hits_info", &hits_info);
+        if let Some(search_after) = search_after {
+            debug.field("search_after", &search_after);
+        }
         if *used_negative_operator {
             debug.field("used_negative_operator", used_negative_operator);
         }
@@ -740,9 +935,24 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
+        if let Some(facet_histograms) = facet_histograms {
+            debug.field("facet_histograms", &facet_histograms);
+        }
+        if let Some(facet_children) = facet_children {
+            debug.field("facet_children", &facet_children);
+        }
+        if let Some(facet_groups) = facet_groups {
+            debug.field("facet_groups", &facet_groups);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(suggested_query) = suggested_query {
+            debug.field("suggested_query", &suggested_query);
+        }
 
         debug.finish()
     }
@@ -779,9 +989,61 @@ pub enum HitsInfo {
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct FacetStats {
     pub min: f64,
     pub max: f64,
+    /// Only returned with `extendedFacetStats` or in the `facetGroups`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<BTreeMap<String, f64>>)]
+    pub percentiles: Option<IndexMap<String, f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct_count: Option<u64>,
+}
+
+impl FacetStats {
+    fn new(min: f64, max: f64) -> Self {
+        FacetStats { min, max, sum: None, avg: None, percentiles: None, distinct_count: None }
+    }
+}
+
+impl From<FacetNumberStats> for FacetStats {
+    fn from(stats: FacetNumberStats) -> Self {
+        let FacetNumberStats { min, max, sum, count, distinct_count, percentiles } = stats;
+        let percentiles = (!percentiles.is_empty()).then(|| {
+            percentiles
+                .into_iter()
+                .map(|(percentile, value)| (format!("p{percentile}"), value))
+                .collect()
+        });
+        FacetStats {
+            min,
+            max,
+            sum: Some(sum),
+            avg: Some(sum / count as f64),
+            percentiles,
+            distinct_count: Some(distinct_count),
+        }
+    }
+}
+
+/// The hits sharing a value of a facet.
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FacetGroup {
+    /// The number of hits having the value
+    pub count: u64,
+    /// The stats of the metric facets over these hits
+    pub stats: BTreeMap<String, FacetStats>,
+}
+
+impl From<milli::FacetGroup> for FacetGroup {
+    fn from(milli::FacetGroup { count, stats }: milli::FacetGroup) -> Self {
+        FacetGroup { count, stats: stats.into_iter().map(|(k, stats)| (k, stats.into())).collect() }
+    }
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -792,6 +1054,14 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<SuggestionHit>,
+    pub query: String,
+    pub processing_time_ms: u128,
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     *filter = match (filter.take(), rules.filter) {
@@ -852,6 +1122,10 @@ fn prepare_search<'t>(
                 }
             };
 
+            // the query only selects the curation rules applied to the results
+            if let Some(q) = &query.q {
+                search.query(q);
+            }
             search.semantic(embedder_name.clone(), embedder.clone(), *quantized, Some(vector));
         }
         SearchKind::Hybrid { embedder_name, embedder, quantized, semantic_ratio: _ } => {
@@ -912,6 +1186,23 @@ fn prepare_search<'t>(
     search.offset(offset);
     search.limit(limit);
 
+    // The maximum number of hits limits each page, but not how deep a cursor can go.
+    if let Some(ref search_after) = query.search_after {
+        if is_finite_pagination {
+            return Err(ResponseError::from_msg(
+                "`searchAfter` cannot be used with `page` or `hitsPerPage`.".to_string(),
+                Code::InvalidSearchSearchAfter,
+            ));
+        }
+        if let SearchKind::Hybrid { .. } = search_kind {
+            return Err(ResponseError::from_msg(
+                "`searchAfter` cannot be used with a hybrid search.".to_string(),
+                Code::InvalidSearchSearchAfter,
+            ));
+        }
+        search.search_after(decode_search_cursor(search_after)?);
+    }
+
     if let Some(ref filter) = query.filter {
         if let Some(facets) = parse_filter(filter, Code::InvalidSearchFilter, features)? {
             search.filter(facets);
@@ -936,23 +1227,62 @@ fn prepare_search<'t>(
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
+/// Encodes a search cursor as the opaque string returned to the user.
+fn encode_search_cursor(cursor: &milli::SearchCursor) -> String {
+    // PANICS: a cursor only contains integers
+    let cursor = serde_json::to_vec(cursor).unwrap();
+    URL_SAFE_NO_PAD.encode(cursor)
+}
+
+fn decode_search_cursor(search_after: &str) -> Result<milli::SearchCursor, ResponseError> {
+    URL_SAFE_NO_PAD
+        .decode(search_after)
+        .ok()
+        .and_then(|cursor| serde_json::from_slice(&cursor).ok())
+        .ok_or_else(|| {
+            ResponseError::from_msg(
+                format!("Invalid value in parameter `searchAfter`: `{search_after}` is not a cursor returned by a previous search."),
+                Code::InvalidSearchSearchAfter,
+            )
+        })
+}
+
 pub fn perform_search(
     index_uid: String,
     index: &Index,
     query: SearchQuery,
+    search_rules_filter: Option<Value>,
     search_kind: SearchKind,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
 ) -> Result<SearchResult, ResponseError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
-    let time_budget = match index.search_cutoff(&rtxn)? {
+    let search_cutoff = index.search_cutoff(&rtxn)?;
+    let new_time_budget = || match search_cutoff {
         Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
         None => TimeBudget::default(),
     };
 
+    // the disjunctive candidates are computed with their own time budget
+    // so that the main search is never degraded because of them
+    let disjunctive_candidates = if query.disjunctive_facets {
+        compute_disjunctive_candidates(
+            &index_uid,
+            index,
+            &rtxn,
+            &query,
+            search_rules_filter,
+            &search_kind,
+            new_time_budget(),
+            features,
+        )?
+    } else {
+        Vec::new()
+    };
+
     let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+        prepare_search(index, &rtxn, &query, &search_kind, new_time_budget(), features)?;
 
     let (
         milli::SearchResult {
@@ -960,6 +1290,7 @@ pub fn perform_search(
             matching_words,
             candidates,
             document_scores,
+            cursor,
             degraded,
             used_negative_operator,
         },
@@ -972,6 +1303,8 @@ pub fn perform_search(
         page,
         hits_per_page,
         attributes_to_retrieve,
+        // already used in prepare_search
+        search_after: _,
         // use the enum passed as parameter
         retrieve_vectors: _,
         attributes_to_crop,
@@ -980,8 +1313,16 @@ pub fn perform_search(
         show_matches_position,
         show_ranking_score,
         show_ranking_score_details,
+        show_suggested_query,
         sort,
         facets,
+        facet_ranges,
+        facet_histograms,
+        facet_children,
+        facet_groups,
+        // already used to compute the disjunctive candidates
+        disjunctive_facets: _,
+        extended_facet_stats,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
@@ -1021,6 +1362,12 @@ pub fn perform_search(
         documents_ids.iter().copied().zip(document_scores.iter()),
     )?;
 
+    // more hits may follow a full page
+    let page_limit = min(limit, max_total_hits.saturating_sub(offset));
+    let search_after = cursor
+        .filter(|_| !is_finite_pagination && page_limit > 0 && documents_ids.len() == page_limit)
+        .map(|cursor| encode_search_cursor(&cursor));
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -1039,9 +1386,52 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
+    let suggested_query = match q.as_deref() {
+        Some(q) if show_suggested_query && number_of_hits < SUGGESTED_QUERY_MAX_HITS => {
+            milli::DidYouMean::new(q, &rtxn, index).execute()?
+        }
+        _ => None,
+    };
+
+    let (facet_ranges, facet_histograms) = if facet_ranges.is_some() || facet_histograms.is_some() {
+        compute_facet_ranges_histograms(facet_ranges, facet_histograms, index, &rtxn, &candidates)?
+    } else {
+        (None, None)
+    };
+
+    let facet_children = facet_children
+        .map(|facet_children| compute_facet_children(&facet_children, index, &rtxn, &candidates))
+        .transpose()?;
+
+    let facet_groups = facet_groups
+        .map(|facet_groups| compute_facet_groups(&facet_groups, index, &rtxn, &candidates))
+        .transpose()?;
+
     let (facet_distribution, facet_stats) = facets
         .map(move |facets| {
-            compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
+            let mut computed = compute_facet_distribution_stats(
+                &facets,
+                index,
+                &rtxn,
+                candidates,
+                extended_facet_stats,
+                Route::Search,
+            )?;
+            // disjunctive facets are counted without the filter clauses on themselves
+            for (facet, candidates) in disjunctive_candidates {
+                let ComputedFacets { distribution, stats } = compute_facet_distribution_stats(
+                    &[&facet],
+                    index,
+                    &rtxn,
+                    candidates,
+                    extended_facet_stats,
+                    Route::Search,
+                )?;
+                computed.stats.remove(&facet);
+                computed.distribution.extend(distribution);
+                computed.stats.extend(stats);
+            }
+            Ok::<_, ResponseError>(computed)
         })
         .transpose()?
         .map(|ComputedFacets { distribution, stats }| (distribution, stats))
@@ -1050,13 +1440,19 @@ pub fn perform_search(
     let result = SearchResult {
         hits: documents,
         hits_info,
+        search_after,
         query: q.unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_ranges,
+        facet_histograms,
+        facet_children,
+        facet_groups,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        suggested_query,
     };
     Ok(result)
 }
@@ -1079,6 +1475,7 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
     index: &Index,
     rtxn: &RoTxn,
     candidates: roaring::RoaringBitmap,
+    extended_stats: bool,
     route: Route,
 ) -> Result<ComputedFacets, ResponseError> {
     let mut facet_distribution = index.facets_distribution(rtxn);
@@ -1113,11 +1510,193 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
             ) => ResponseError::from_msg(error.to_string(), Code::InvalidMultiSearchFacets),
             (error, _) => error.into(),
         })?;
-    let stats = facet_distribution.compute_stats()?;
-    let stats = stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect();
+    let stats = if extended_stats {
+        let stats = facet_distribution.compute_number_stats(&EXTENDED_STATS_PERCENTILES)?;
+        stats.into_iter().map(|(k, stats)| (k, stats.into())).collect()
+    } else {
+        let stats = facet_distribution.compute_stats()?;
+        stats.into_iter().map(|(k, (min, max))| (k, FacetStats::new(min, max))).collect()
+    };
     Ok(ComputedFacets { distribution, stats })
 }
 
+/// Computes, for every requested facet constrained by the filter, the documents matching the
+/// query and the filter stripped from the conditions on this facet.
+///
+/// The tenant token search rules are always enforced, even on the facet itself.
+/// The documents of a hybrid search are the ones matching the keywords of the query.
+#[allow(clippy::too_many_arguments)]
+fn compute_disjunctive_candidates(
+    index_uid: &str,
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &SearchQuery,
+    search_rules_filter: Option<Value>,
+    search_kind: &SearchKind,
+    time_budget: TimeBudget,
+    features: RoFeatures,
+) -> Result<Vec<(String, roaring::RoaringBitmap)>, ResponseError> {
+    let (Some(facets), Some(filter)) = (&query.facets, &query.filter) else {
+        return Ok(Vec::new());
+    };
+    let Some(filter) = parse_filter(filter, Code::InvalidSearchFilter, features)? else {
+        return Ok(Vec::new());
+    };
+
+    let facets = if facets.iter().any(|facet| facet == "*") {
+        index.filterable_fields(rtxn)?.into_iter().collect()
+    } else {
+        facets.clone()
+    };
+
+    let relaxed_filters: Vec<_> = facets
+        .into_iter()
+        .filter_map(|facet| {
+            let relaxed = filter.without_facet(&facet);
+            (relaxed.as_ref() != Some(&filter)).then_some((facet, relaxed))
+        })
+        .collect();
+    if relaxed_filters.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // the documents matching the query and the search rules, but none of the user filter
+    let base_candidates = match search_kind {
+        // a semantic search matches every document
+        SearchKind::SemanticOnly { .. } => {
+            let search_rules_filter = match &search_rules_filter {
+                Some(filter) => parse_filter(filter, Code::InvalidSearchFilter, features)?,
+                None => None,
+            };
+            milli::filtered_universe(index, rtxn, &search_rules_filter)?
+        }
+        // only the keyword part of a hybrid search is run, the semantic
+        // part would embed the query a second time
+        SearchKind::KeywordOnly | SearchKind::Hybrid { .. } => {
+            let base_query = SearchQuery {
+                filter: search_rules_filter,
+                offset: 0,
+                limit: 0,
+                page: None,
+                hits_per_page: None,
+                search_after: None,
+                sort: None,
+                ..query.clone()
+            };
+            let search_kind = SearchKind::KeywordOnly;
+            let (search, ..) =
+                prepare_search(index, rtxn, &base_query, &search_kind, time_budget, features)?;
+            let (result, _) = search_from_kind(index_uid.to_string(), search_kind, search)?;
+            result.candidates
+        }
+    };
+
+    relaxed_filters
+        .into_iter()
+        .map(|(facet, relaxed)| {
+            let candidates = match relaxed {
+                Some(relaxed) => relaxed.evaluate(rtxn, index)? & &base_candidates,
+                None => base_candidates.clone(),
+            };
+            Ok((facet, candidates))
+        })
+        .collect()
+}
+
+type FacetRangeCounts = BTreeMap<String, Vec<FacetRangeCount>>;
+
+fn compute_facet_ranges_histograms(
+    facet_ranges: Option<BTreeMap<String, Vec<FacetRangeQuery>>>,
+    facet_histograms: Option<BTreeMap<String, HistogramInterval>>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: &roaring::RoaringBitmap,
+) -> Result<(Option<FacetRangeCounts>, Option<FacetRangeCounts>), ResponseError> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    facet_distribution.max_values_per_facet(max_values_by_facet).candidates(candidates.clone());
+
+    let facet_ranges = facet_ranges
+        .map(|facet_ranges| {
+            let facet_ranges = facet_ranges
+                .into_iter()
+                .map(|(facet, ranges)| (facet, ranges.into_iter().map(Into::into).collect()))
+                .collect();
+            facet_distribution.compute_ranges(&facet_ranges)
+        })
+        .transpose()?;
+    let facet_histograms = facet_histograms
+        .map(|facet_histograms| {
+            let facet_histograms = facet_histograms
+                .into_iter()
+                .map(|(facet, HistogramInterval(interval))| (facet, interval))
+                .collect();
+            facet_distribution.compute_histograms(&facet_histograms)
+        })
+        .transpose()?;
+
+    Ok((facet_ranges, facet_histograms))
+}
+
+fn compute_facet_children(
+    facet_children: &BTreeMap<String, String>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: &roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, IndexMap<String, u64>>, ResponseError> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
+
+    Ok(facet_distribution
+        .max_values_per_facet(max_values_by_facet)
+        .facets(facet_children.keys().map(|name| (name, sort_facet_values_by.get(name))))
+        .default_order_by(sort_facet_values_by.get("*"))
+        .candidates(candidates.clone())
+        .compute_children(facet_children)?)
+}
+
+fn compute_facet_groups(
+    facet_groups: &BTreeMap<String, Vec<String>>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: &roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, IndexMap<String, FacetGroup>>, ResponseError> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
+
+    let groups = facet_distribution
+        .max_values_per_facet(max_values_by_facet)
+        .facets(facet_groups.keys().map(|name| (name, sort_facet_values_by.get(name))))
+        .default_order_by(sort_facet_values_by.get("*"))
+        .candidates(candidates.clone())
+        .compute_groups(facet_groups)?;
+
+    Ok(groups
+        .into_iter()
+        .map(|(name, groups)| (name, groups.into_iter().map(|(v, g)| (v, g.into())).collect()))
+        .collect())
+}
+
 pub fn search_from_kind(
     index_uid: String,
     search_kind: SearchKind,
@@ -1399,6 +1978,7 @@ impl<'a> HitMaker<'a> {
             self.show_ranking_score.then(|| ScoreDetails::global_score(score.iter()));
         let ranking_score_details =
             self.show_ranking_score_details.then(|| ScoreDetails::to_json_map(score.iter()));
+        let pinned = score.iter().any(|details| matches!(details, ScoreDetails::Pinned(_)));
 
         let hit = SearchHit {
             document,
@@ -1406,6 +1986,7 @@ impl<'a> HitMaker<'a> {
             matches_position,
             ranking_score_details,
             ranking_score,
+            pinned,
         };
 
         Ok(hit)
@@ -1502,6 +2083,45 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_suggest(
+    index: &Index,
+    query: SuggestQuery,
+    features: RoFeatures,
+) -> Result<SuggestResult, ResponseError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let SuggestQuery { q, filter, attributes_to_search_on, limit } = query;
+    let q = q.unwrap_or_default();
+
+    let mut suggest = milli::Suggest::new(&q, &rtxn, index);
+    suggest.limit(limit);
+    if let Some(ref filter) = filter {
+        if let Some(facets) = parse_filter(filter, Code::InvalidSuggestFilter, features)? {
+            suggest.filter(facets);
+        }
+    }
+    if let Some(ref attributes_to_search_on) = attributes_to_search_on {
+        suggest.searchable_attributes(attributes_to_search_on);
+    }
+
+    let suggestions = suggest.execute().map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidSuggestFilter)
+        }
+        milli::Error::UserError(milli::UserError::InvalidSearchableAttribute { .. }) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidSuggestAttributesToSearchOn)
+        }
+        err => err.into(),
+    })?;
+
+    Ok(SuggestResult {
+        suggestions,
+        query: q,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
 pub fn perform_similar(
     index: &Index,
     query: SimilarQuery,
@@ -1562,6 +2182,7 @@ pub fn perform_similar(
         matching_words: _,
         candidates,
         document_scores,
+        cursor: _,
         degraded: _,
         used_negative_operator: _,
     } = similar.execute().map_err(|err| match err {
@@ -1621,11 +2242,20 @@ pub fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
         // TODO: TAMO: milli encountered an internal error, what do we want to do?
         let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
-            extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+        let geo_points = match document.get("_geo") {
+            Some(Value::Array(geo_points)) => geo_points.iter().collect(),
+            Some(geo_point) => vec![geo_point],
+            None => Vec::new(),
+        };
+        // a document located at several places is as far as its closest point
+        let distance = geo_points
+            .into_iter()
+            .filter_map(|geo_point| {
+                extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
+            })
+            .map(|(lat, lng)| milli::distance_between_two_points(&base, &[lat, lng]))
+            .min_by(|left, right| left.total_cmp(right));
+        if let Some(distance) = distance {
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
         }
     }
@@ -1904,7 +2534,11 @@ pub(crate) fn parse_filter(
     let filter = match facets {
         Value::String(expr) => Filter::from_str(expr).map_err(|e| e.into()),
         Value::Array(arr) => parse_filter_array(arr).map_err(|e| e.into()),
-        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone()).into()),
+        Value::Object(_) => Filter::from_object(facets).map_err(|e| e.into()),
+        v => {
+            Err(MeilisearchHttpError::InvalidExpression(&["String", "Array", "Object"], v.clone())
+                .into())
+        }
     };
     let filter = filter.map_err(|err: ResponseError| {
         ResponseError::from_msg(err.to_string(), filter_parsing_error_code)
@@ -1926,18 +2560,25 @@ pub(crate) fn parse_filter(
 }
 
 fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpError> {
+    let from_milli = |e| MeilisearchHttpError::from_milli(e, None);
     let mut ands = Vec::new();
     for value in arr {
         match value {
-            Value::String(s) => ands.push(Either::Right(s.as_str())),
+            Value::String(s) => ands.push(Either::Right(Filter::from_str(s).map_err(from_milli)?)),
+            Value::Object(_) => {
+                ands.push(Either::Right(Filter::from_object(value).map_err(from_milli)?))
+            }
             Value::Array(arr) => {
                 let mut ors = Vec::new();
                 for value in arr {
                     match value {
-                        Value::String(s) => ors.push(s.as_str()),
+                        Value::String(s) => ors.push(Filter::from_str(s).map_err(from_milli)?),
+                        Value::Object(_) => {
+                            ors.push(Filter::from_object(value).map_err(from_milli)?)
+                        }
                         v => {
                             return Err(MeilisearchHttpError::InvalidExpression(
-                                &["String"],
+                                &["String", "Object"],
                                 v.clone(),
                             ))
                         }
@@ -1947,12 +2588,12 @@ fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpEr
             }
             v => {
                 return Err(MeilisearchHttpError::InvalidExpression(
-                    &["String", "[String]"],
+                    &["String", "Object", "[String | Object]"],
                     v.clone(),
                 ))
             }
         }
     }
 
-    Filter::from_array(ands).map_err(|e| MeilisearchHttpError::from_milli(e, None))
+    Filter::from_filters(ands).map_err(from_milli)
 }
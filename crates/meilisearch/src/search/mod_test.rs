@@ -112,3 +112,29 @@ fn test_insert_geo_distance_with_coords_as_string() {
     insert_geo_distance(sorters, &mut document);
     assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 }
+
+#[test]
+fn test_insert_geo_distance_with_multiple_points() {
+    let value: Document = serde_json::from_str(
+        r#"{
+          "_geo": [
+            { "lat": 51, "lng": 3 },
+            { "lat": "50", "lng": "3" },
+            { "lat": 0, "lng": 0 }
+          ],
+          "id": "1"
+        }"#,
+    )
+    .unwrap();
+
+    // the distance is the one of the closest point
+    let sorters = &["_geoPoint(50,3):desc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    let sorters = &["_geoPoint(0,0):asc".to_string()];
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+}
@@ -236,6 +236,9 @@ impl RankingRules {
                         })
                     }
                 }
+                Criterion::Decay(_) | Criterion::Boost(_) => {
+                    canonical_criteria.push(criterion.clone())
+                }
                 Criterion::Asc(s) | Criterion::Desc(s) => match sorted_fields.entry(s) {
                     std::collections::hash_map::Entry::Occupied(entry) => canonicalization_actions
                         .push(CanonicalizationAction::RemovedDuplicate {
@@ -316,6 +319,9 @@ impl RankingRules {
                         })
                     }
                 }
+                Criterion::Decay(_) | Criterion::Boost(_) => {
+                    canonical_criteria.push(criterion.clone())
+                }
                 Criterion::Asc(s) | Criterion::Desc(s) => match sorted_fields.entry(s) {
                     std::collections::hash_map::Entry::Occupied(entry) => canonicalization_actions
                         .push(CanonicalizationAction::RemovedDuplicate {
@@ -450,6 +456,7 @@ impl RankingRules {
                         })
                     }
                 }
+                Criterion::Decay(_) | Criterion::Boost(_) => canonical_criteria.push(criterion),
                 Criterion::Asc(s) | Criterion::Desc(s) => match sorted_fields.entry(s) {
                     std::collections::hash_map::Entry::Occupied(entry) => canonicalization_actions
                         .push(CanonicalizationAction::RemovedDuplicate {
@@ -724,6 +731,8 @@ impl RankingRule {
             | Criterion::Proximity
             | Criterion::Attribute
             | Criterion::Exactness => RankingRuleKind::Relevancy,
+            // the decay and boost scores are merged with the relevancy score
+            Criterion::Decay(_) | Criterion::Boost(_) => RankingRuleKind::Relevancy,
             Criterion::Asc(s) if s == "_geo" => RankingRuleKind::AscendingGeoSort,
 
             Criterion::Asc(_) => RankingRuleKind::AscendingSort,
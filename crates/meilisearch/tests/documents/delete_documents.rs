@@ -429,3 +429,60 @@ async fn delete_document_by_complex_filter() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn delete_document_by_geo_polygon() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    let (task, _status_code) = index
+        .add_documents(
+            json!([
+                { "id": 0, "_geo": { "lat": 45.4777599, "lng": 9.1967508 } },
+                { "id": 1, "_geo": { "lat": 0, "lng": 179 } },
+                { "id": 2, "_geo": { "lat": 0, "lng": -179 } },
+                { "id": 3 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .delete_document_by_filter(
+            json!({ "filter": "_geoPolygon([1, 178], [1, -178], [-1, -178], [-1, 178])" }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "providedIds": 0,
+      "deletedDocuments": 2,
+      "originalFilter": "\"_geoPolygon([1, 178], [1, -178], [-1, -178], [-1, 178])\""
+    }
+    "###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "_geo": {
+            "lat": 45.4777599,
+            "lng": 9.1967508
+          }
+        },
+        {
+          "id": 3
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+}
@@ -73,7 +73,9 @@ async fn import_dump_v1_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -82,7 +84,11 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -239,7 +245,9 @@ async fn import_dump_v1_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -248,7 +256,11 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -391,7 +403,9 @@ async fn import_dump_v1_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -400,7 +414,11 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -529,7 +547,9 @@ async fn import_dump_v2_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -538,7 +558,11 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -679,7 +703,9 @@ async fn import_dump_v2_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -688,7 +714,11 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -828,7 +858,9 @@ async fn import_dump_v2_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -837,7 +869,11 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -966,7 +1002,9 @@ async fn import_dump_v3_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -975,7 +1013,11 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -1116,7 +1158,9 @@ async fn import_dump_v3_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1125,7 +1169,11 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -1265,7 +1313,9 @@ async fn import_dump_v3_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1274,7 +1324,11 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -1403,7 +1457,9 @@ async fn import_dump_v4_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1412,7 +1468,11 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -1553,7 +1613,9 @@ async fn import_dump_v4_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1562,7 +1624,11 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -1702,7 +1768,9 @@ async fn import_dump_v4_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1711,7 +1779,11 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###
     );
@@ -1951,7 +2023,9 @@ async fn import_dump_v6_containing_experimental_features() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1960,7 +2034,11 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###);
 
@@ -2115,7 +2193,9 @@ async fn generate_and_import_dump_containing_vectors() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -2132,7 +2212,11 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "###);
 
@@ -311,7 +311,7 @@ async fn search_bad_filter() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid syntax for the filter parameter: `expected String, Array, found: true`.",
+      "message": "Invalid syntax for the filter parameter: `expected String, Array, Object, found: true`.",
       "code": "invalid_search_filter",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -384,6 +384,110 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_disjunctive_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"disjunctiveFacets": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.disjunctiveFacets`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?disjunctiveFacets=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `disjunctiveFacets`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_extended_facet_stats() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"extendedFacetStats": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.extendedFacetStats`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_extended_facet_stats",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_extended_facet_stats"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?extendedFacetStats=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `extendedFacetStats`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_extended_facet_stats",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_extended_facet_stats"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_groups() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"facetGroups": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetGroups`: expected an object, but found an array: `[\"doggo\"]`",
+      "code": "invalid_search_facet_groups",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_groups"
+    }
+    "###);
+
+    let (task, _status_code) = index.update_settings_filterable_attributes(json!(["brand"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({"facetGroups": {"brand": ["price"]}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet groups, attribute `price` is not filterable. The available filterable attribute is `brand`.",
+      "code": "invalid_search_facet_groups",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_groups"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_children() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"facetChildren": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetChildren`: expected an object, but found a string: `\"doggo\"`",
+      "code": "invalid_search_facet_children",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_children"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new_shared();
@@ -418,6 +522,60 @@ async fn search_invalid_threshold() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_facet_ranges_and_histograms() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (response, _code) = index.update_settings(json!({"filterableAttributes": ["title"]})).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"title": [{"from": 0, "until": 10}]}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `until` inside `.facetRanges.title[0]`: expected one of `from`, `to`",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"doggo": [{"from": 0}]}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet ranges, attribute `doggo` is not filterable. The available filterable attribute is `title`.",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"facetHistograms": {"title": 0}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetHistograms.title`: the histogram interval is invalid, expected a strictly positive float (> 0.0).",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"facetHistograms": {"doggo": 10}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet histograms, attribute `doggo` is not filterable. The available filterable attribute is `title`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_non_filterable_facets() {
     let server = Server::new_shared();
@@ -646,7 +804,7 @@ async fn filter_invalid_syntax_object() {
         .search(json!({"filter": "title & Glass"}), |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -669,7 +827,7 @@ async fn filter_invalid_syntax_array() {
         .search(json!({"filter": ["title & Glass"]}), |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `ALL`, `NOT ALL`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `UNDER`, `NOT UNDER`, `SIZE`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoContains` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
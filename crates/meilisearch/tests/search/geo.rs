@@ -118,6 +118,220 @@ async fn geo_bounding_box_with_string_and_number() {
         .await;
 }
 
+#[actix_rt::test]
+async fn geo_polygon_with_string_and_number() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([46, 9], [46, 10], [45, 10], [45, 9])",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "name": "La Bella Italia",
+                    "address": "456 Elm Street, Townsville",
+                    "type": "Italian",
+                    "rating": 9,
+                    "_geo": {
+                      "lat": "45.4777599",
+                      "lng": "9.1967508"
+                    }
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([46, 9], [46, 10], [45, 10])",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_filter_and_sort_with_multiple_points() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "name": "Coffee Chain",
+            "_geo": [
+                { "lat": 45.4777599, "lng": 9.1967508 },
+                { "lat": "48.8566", "lng": "2.3522" }
+            ]
+        },
+        {
+            "id": 2,
+            "name": "Bakery",
+            "_geo": { "lat": 48.86, "lng": 2.35 }
+        }
+    ]);
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.update_settings_sortable_attributes(json!(["_geo"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await.succeeded();
+
+    // a document matches as soon as one of its points matches
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.4777599, 9.1967508, 1000)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the documents are sorted by their closest point
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(48.8566, 2.3522):asc"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"][0]), @r###"
+                {
+                  "id": 1,
+                  "name": "Coffee Chain",
+                  "_geo": [
+                    {
+                      "lat": 45.4777599,
+                      "lng": 9.1967508
+                    },
+                    {
+                      "lat": "48.8566",
+                      "lng": "2.3522"
+                    }
+                  ],
+                  "_geoDistance": 0
+                }
+                "###);
+                assert_eq!(response["hits"][1]["id"], json!(2));
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geojson_contains_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "name": "Paris",
+            "_geojson": {
+                "type": "Polygon",
+                "coordinates": [[[2.22, 48.81], [2.47, 48.81], [2.47, 48.90], [2.22, 48.90], [2.22, 48.81]]]
+            }
+        },
+        {
+            "id": 2,
+            "name": "Seine",
+            "_geojson": {
+                "type": "LineString",
+                "coordinates": [[2.30, 48.86], [2.40, 48.86]]
+            }
+        },
+        {
+            "id": 3,
+            "name": "Nowhere"
+        }
+    ]);
+    index.update_settings_filterable_attributes(json!(["_geojson"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "_geoContains(48.86, 2.35)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  },
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoContains(48.85, 2.35)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    let (ret, _code) = index
+        .add_documents(
+            json!([{ "id": 4, "_geojson": { "type": "LineString", "coordinates": [[2.30, 48.86]] } }]),
+            None,
+        )
+        .await;
+    let ret = index.wait_task(ret.uid()).await.failed();
+    snapshot!(json_string!(ret["error"]), @r###"
+    {
+      "message": "Index `test`: The `_geojson` field in the document with the id: `\"4\"` is not a valid GeoJSON geometry: a `LineString` must contain at least two positions.",
+      "code": "invalid_document_geo_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn bug_4640() {
     // https://github.com/meilisearch/meilisearch/issues/4640
@@ -625,3 +625,44 @@ async fn retrieve_vectors() {
     ]
     "###);
 }
+
+#[actix_rt::test]
+async fn curation_rules() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    let (response, code) = index
+        .update_settings(json!({ "curationRules": [{
+            "query": "Captain",
+            "pinned": [{ "id": "1", "position": 0 }],
+            "hidden": ["3"]
+        }]}))
+        .await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    // without the rule, the semantic search returns 3, 2, 1
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "semanticRatio": 1.0}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"1"},{"id":"2"}]"###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "semanticRatio": 0.5}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"1"},{"id":"2"}]"###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "semanticRatio": 0.5}, "attributesToRetrieve": ["id"], "offset": 1, "limit": 1}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"}]"###);
+}
@@ -14,6 +14,7 @@ mod multi;
 mod pagination;
 mod restrict_searchable;
 mod search_queue;
+mod suggest;
 
 use meili_snap::{json_string, snapshot};
 use meilisearch::Opt;
@@ -284,6 +285,33 @@ async fn test_thai_language() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_with_suggested_query() {
+    let index = shared_index_with_documents().await;
+    index
+        .search(json!({"q": "Captian Marvell", "showSuggestedQuery": true}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["suggestedQuery"], @r###""captain marvel""###);
+        })
+        .await;
+
+    // the query is only corrected when requested
+    index
+        .search(json!({"q": "Captian Marvell"}), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert!(response.get("suggestedQuery").is_none(), "{}", response);
+        })
+        .await;
+
+    // nothing to correct
+    index
+        .search(json!({"q": "Captain Marvel", "showSuggestedQuery": true}), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert!(response.get("suggestedQuery").is_none(), "{}", response);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn search_multiple_params() {
     let index = shared_index_with_documents().await;
@@ -433,6 +461,133 @@ async fn search_with_contains_filter() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn search_with_field_comparison_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) =
+        index.update_settings(json!({"filterableAttributes": ["salePrice", "listPrice"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "salePrice": 8, "listPrice": 10 },
+        { "id": 2, "salePrice": 10, "listPrice": 10 },
+        { "id": 3, "salePrice": 12, "listPrice": 10 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "salePrice < listPrice OR salePrice > 11",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      3
+    ]
+    "###);
+
+    let (response, code) = index.search_post(json!({ "filter": "salePrice < stock" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index `test`: Attribute `stock` is not filterable. Available filterable attributes are: `listPrice`, `salePrice`.\n13:18 salePrice < stock",
+      "code": "invalid_search_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_string_range_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index.update_settings(json!({"filterableAttributes": ["code"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "code": "A099" },
+        { "id": 2, "code": "A100" },
+        { "id": 3, "code": "A150" },
+        { "id": 4, "code": "B200" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "code >= \"A100\" AND code < \"B\"",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      2,
+      3
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_all_and_size_filters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index.update_settings(json!({"filterableAttributes": ["tags"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "tags": ["electronics", "audio", "wireless"] },
+        { "id": 2, "tags": ["electronics", "audio"] },
+        { "id": 3, "tags": "electronics" },
+        { "id": 4, "tags": [] },
+        { "id": 5 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "tags ALL [electronics, audio]" })).await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      2
+    ]
+    "###);
+
+    let (response, code) = index.search_post(json!({ "filter": "SIZE(tags) < 2" })).await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      3,
+      4,
+      5
+    ]
+    "###);
+
+    let (response, code) = index.search_post(json!({ "filter": "SIZE(tags > 2" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `SIZE` filter expects an attribute followed by a comparison: `SIZE(attribute) > 2` or `SIZE(attribute) 1 TO 3`.\n1:14 SIZE(tags > 2",
+      "code": "invalid_search_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_with_sort_on_numbers() {
     let index = shared_index_with_documents().await;
@@ -503,6 +658,168 @@ async fn search_with_multiple_sort() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 5);
 }
 
+#[actix_rt::test]
+async fn search_with_filter_and_sort_on_dates() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["createdAt"],
+            "sortableAttributes": ["createdAt"],
+            "dateAttributes": ["createdAt"],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "createdAt": "2024-06-15T12:00:00+02:00" },
+        { "id": 2, "createdAt": "2024-06-15T11:00:00Z" },
+        { "id": 3, "createdAt": "2024-01-01" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "createdAt >= 2024-06-15T10:00:00Z",
+            "sort": ["createdAt:asc"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      2
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "createdAt 2024-01-01 TO 2024-06-15T10:30:00Z",
+            "sort": ["createdAt:desc"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      3
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_filter_on_nested_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["variants"],
+            "nestedAttributes": ["variants"],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "variants": [{ "color": "red", "size": "L" }, { "color": "blue", "size": "M" }] },
+        { "id": 2, "variants": [{ "color": "red", "size": "M" }, { "color": "blue", "size": "L" }] },
+        { "id": 3, "variants": { "color": "red", "size": "L" } },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "variants[color = red AND size = L]" })).await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      3
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "variants.color = red AND variants.size = L" })).await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      2,
+      3
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_filter_object() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["title", "price", "variants"],
+            "nestedAttributes": ["variants"],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "title": "Mister \"Mv\" O'Neil", "price": 10, "variants": [{ "color": "red", "size": "L" }] },
+        { "id": 2, "title": "Hello", "price": 20, "variants": [{ "color": "red", "size": "M" }] },
+        { "id": 3, "title": "World", "price": 30 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({ "filter": { "or": [
+            { "field": "title", "op": "=", "value": "Mister \"Mv\" O'Neil" },
+            { "and": [
+                { "field": "price", "op": ">", "value": 15 },
+                { "not": { "nested": "variants", "filter": { "field": "color", "op": "=", "value": "red" } } },
+            ] },
+        ] } }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      1,
+      3
+    ]
+    "###);
+
+    // objects can be mixed with filter expressions, as when a tenant token adds its own rules
+    let (response, code) = index
+        .search_post(json!({ "filter": ["price < 25", { "field": "price", "op": "TO", "value": [15, 40] }] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      2
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "filter": { "field": "price", "op": "=" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing `value` in the filter object `{\"field\":\"price\",\"op\":\"=\"}`.",
+      "code": "invalid_search_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_facet_distribution() {
     let index = shared_index_with_documents().await;
@@ -791,6 +1108,474 @@ async fn faceting_max_values_per_facet() {
         .await;
 }
 
+#[actix_rt::test]
+async fn facet_ranges_and_histograms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["price"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents: Vec<_> = (0..20).map(|id| json!({ "id": id, "price": id * 5 })).collect();
+    let (task, _status_code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "price >= 30",
+            "facetRanges": { "price": [{ "to": 25 }, { "from": 25, "to": 50 }, { "from": 50 }] },
+            "facetHistograms": { "price": 40 },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetRanges"]), @r###"
+    {
+      "price": [
+        {
+          "to": 25.0,
+          "count": 0
+        },
+        {
+          "from": 25.0,
+          "to": 50.0,
+          "count": 4
+        },
+        {
+          "from": 50.0,
+          "count": 10
+        }
+      ]
+    }
+    "###);
+    snapshot!(json_string!(response["facetHistograms"]), @r###"
+    {
+      "price": [
+        {
+          "from": 0.0,
+          "to": 40.0,
+          "count": 2
+        },
+        {
+          "from": 40.0,
+          "to": 80.0,
+          "count": 8
+        },
+        {
+          "from": 80.0,
+          "to": 120.0,
+          "count": 4
+        }
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn disjunctive_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["brand", "color"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "brand": "A", "color": "red" },
+        { "id": 2, "brand": "A", "color": "blue" },
+        { "id": 3, "brand": "B", "color": "red" },
+        { "id": 4, "brand": "C", "color": "red" },
+        { "id": 5, "brand": "B", "color": "blue" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": [["brand = A", "brand = B"], "color = red"],
+            "facets": ["brand", "color"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "brand": {
+        "A": 1,
+        "B": 1
+      },
+      "color": {
+        "red": 2
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": [["brand = A", "brand = B"], "color = red"],
+            "facets": ["brand", "color"],
+            "disjunctiveFacets": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"2");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "brand": {
+        "A": 1,
+        "B": 1,
+        "C": 1
+      },
+      "color": {
+        "blue": 2,
+        "red": 2
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn hierarchical_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["id", "category"],
+            "faceting": { "hierarchicalFacets": { "category": " > " } },
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "category": "Electronics > Audio > Headphones" },
+        { "id": 2, "category": "Electronics > Audio > Speakers" },
+        { "id": 3, "category": "Electronics > TV" },
+        { "id": 4, "category": ["Electronics > Audio", "Home > Kitchen"] },
+        { "id": 5, "category": "Home" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "category UNDER 'Electronics > Audio'",
+            "facetChildren": { "category": "Electronics > Audio" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "category": "Electronics > Audio > Headphones"
+      },
+      {
+        "id": 2,
+        "category": "Electronics > Audio > Speakers"
+      },
+      {
+        "id": 4,
+        "category": [
+          "Electronics > Audio",
+          "Home > Kitchen"
+        ]
+      }
+    ]
+    "###);
+    snapshot!(json_string!(response["facetChildren"]), @r###"
+    {
+      "category": {
+        "Electronics > Audio > Headphones": 1,
+        "Electronics > Audio > Speakers": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "facetChildren": { "category": "" } })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetChildren"]), @r###"
+    {
+      "category": {
+        "Electronics": 4,
+        "Home": 2
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "filter": "id UNDER 1" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_filter""###);
+}
+
+#[actix_rt::test]
+async fn exact_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["sku"],
+            "faceting": { "exactFacets": ["sku"] },
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "sku": "AbC" },
+        { "id": 2, "sku": "abc" },
+        { "id": 3, "sku": "abc" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "sku = AbC", "facets": ["sku"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "sku": "AbC"
+      }
+    ]
+    "###);
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "sku": {
+        "AbC": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "facets": ["sku"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "sku": {
+        "AbC": 1,
+        "abc": 2
+      }
+    }
+    "###);
+
+    // the facet search is case-sensitive on the exact facets
+    let (response, code) =
+        index.facet_search(json!({ "facetName": "sku", "facetQuery": "Ab" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHits"]), @r###"
+    [
+      {
+        "value": "AbC",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, _code) = index.settings().await;
+    snapshot!(json_string!(response["faceting"]["exactFacets"]), @r###"
+    [
+      "sku"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn extended_facet_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["price"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "price": 10 },
+        { "id": 2, "price": 20 },
+        { "id": 3, "price": 20 },
+        { "id": 4, "price": 30 },
+        { "id": 5, "price": 40 },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "facets": ["price"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 10.0,
+        "max": 40.0
+      }
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "facets": ["price"], "extendedFacetStats": true })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 10.0,
+        "max": 40.0,
+        "sum": 120.0,
+        "avg": 24.0,
+        "percentiles": {
+          "p1": 10.0,
+          "p5": 10.0,
+          "p25": 20.0,
+          "p50": 20.0,
+          "p75": 30.0,
+          "p95": 40.0,
+          "p99": 40.0
+        },
+        "distinctCount": 4
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({ "filter": "price > 15", "facets": ["price"], "extendedFacetStats": true }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 20.0,
+        "max": 40.0,
+        "sum": 110.0,
+        "avg": 27.5,
+        "percentiles": {
+          "p1": 20.0,
+          "p5": 20.0,
+          "p25": 20.0,
+          "p50": 20.0,
+          "p75": 30.0,
+          "p95": 40.0,
+          "p99": 40.0
+        },
+        "distinctCount": 3
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_groups() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _status_code) = index
+        .update_settings(json!({ "filterableAttributes": ["brand", "rating", "price"] }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "brand": "A", "rating": 4, "price": 10 },
+        { "id": 2, "brand": "A", "rating": 2, "price": 30 },
+        { "id": 3, "brand": "B", "rating": 5, "price": 20 },
+        { "id": 4, "brand": "C", "rating": 3 },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "facetGroups": { "brand": ["rating", "price"] } })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetGroups"]), @r###"
+    {
+      "brand": {
+        "A": {
+          "count": 2,
+          "stats": {
+            "price": {
+              "min": 10.0,
+              "max": 30.0,
+              "sum": 40.0,
+              "avg": 20.0,
+              "distinctCount": 2
+            },
+            "rating": {
+              "min": 2.0,
+              "max": 4.0,
+              "sum": 6.0,
+              "avg": 3.0,
+              "distinctCount": 2
+            }
+          }
+        },
+        "B": {
+          "count": 1,
+          "stats": {
+            "price": {
+              "min": 20.0,
+              "max": 20.0,
+              "sum": 20.0,
+              "avg": 20.0,
+              "distinctCount": 1
+            },
+            "rating": {
+              "min": 5.0,
+              "max": 5.0,
+              "sum": 5.0,
+              "avg": 5.0,
+              "distinctCount": 1
+            }
+          }
+        },
+        "C": {
+          "count": 1,
+          "stats": {
+            "rating": {
+              "min": 3.0,
+              "max": 3.0,
+              "sum": 3.0,
+              "avg": 3.0,
+              "distinctCount": 1
+            }
+          }
+        }
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": "price >= 20", "facetGroups": { "brand": ["price"] } }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetGroups"]), @r###"
+    {
+      "brand": {
+        "A": {
+          "count": 1,
+          "stats": {
+            "price": {
+              "min": 30.0,
+              "max": 30.0,
+              "sum": 30.0,
+              "avg": 30.0,
+              "distinctCount": 1
+            }
+          }
+        },
+        "B": {
+          "count": 1,
+          "stats": {
+            "price": {
+              "min": 20.0,
+              "max": 20.0,
+              "sum": 20.0,
+              "avg": 20.0,
+              "distinctCount": 1
+            }
+          }
+        }
+      }
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn test_score_details() {
     let server = Server::new().await;
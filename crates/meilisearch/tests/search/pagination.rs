@@ -1,3 +1,5 @@
+use meili_snap::{json_string, snapshot};
+
 use super::shared_index_with_documents;
 use crate::common::Server;
 use crate::json;
@@ -133,3 +135,71 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn search_after_goes_beyond_max_total_hits() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents: Vec<_> = (0..10).map(|id| json!({ "id": id, "rank": 10 - id })).collect();
+    let (task, _code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, _code) = index
+        .update_settings(
+            json!({ "sortableAttributes": ["rank"], "pagination": { "maxTotalHits": 4 } }),
+        )
+        .await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let mut ids = Vec::new();
+    let mut search_after = None;
+    loop {
+        let (response, code) = index
+            .search_post(json!({ "sort": ["rank:asc"], "limit": 3, "searchAfter": search_after }))
+            .await;
+        assert_eq!(code, 200, "{}", response);
+        assert_eq!(response["estimatedTotalHits"], 4);
+        let hits = response["hits"].as_array().unwrap();
+        ids.extend(hits.iter().map(|hit| hit["id"].as_u64().unwrap()));
+        match response.get("searchAfter") {
+            Some(cursor) => search_after = Some(cursor.clone()),
+            None => break,
+        }
+    }
+    assert_eq!(ids, (0..10).rev().collect::<Vec<_>>());
+
+    // the cursor can only resume the search that returned it
+    let (response, code) = index
+        .search_post(json!({ "sort": ["rank:desc"], "limit": 3, "searchAfter": search_after }))
+        .await;
+    let expected_response = json!({
+        "message": format!("Index `{}`: The search cannot be resumed from this cursor, it was returned by a search with other parameters or the index was updated since.", index.uid),
+        "code": "invalid_search_search_after",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    });
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 400);
+
+    let (response, code) = index.search_post(json!({ "searchAfter": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `searchAfter`: `doggo` is not a cursor returned by a previous search.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "searchAfter": "doggo", "page": 2 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`searchAfter` cannot be used with `page` or `hitsPerPage`.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+}
@@ -0,0 +1,109 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "Harry Potter and the Chamber of Secrets", "genre": "fantasy" },
+        { "id": 1, "title": "Harry Potter and the Half-Blood Prince", "genre": "fantasy" },
+        { "id": 2, "title": "Hard Times", "genre": "novel" },
+        { "id": 3, "title": "Harry Potter and the Prisoner of Azkaban", "genre": "fantasy" },
+        { "id": 4, "title": "The Harvest", "genre": "novel" },
+    ])
+});
+
+#[actix_rt::test]
+async fn suggest() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genre"])).await;
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.suggest(json!({"q": "har"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[duration]" }), @r###"
+    {
+      "suggestions": [
+        {
+          "suggestion": "harry",
+          "count": 3
+        },
+        {
+          "suggestion": "hard",
+          "count": 1
+        },
+        {
+          "suggestion": "harvest",
+          "count": 1
+        }
+      ],
+      "query": "har",
+      "processingTimeMs": "[duration]"
+    }
+    "###);
+
+    // the completions must match the other words of the query
+    let (response, code) =
+        index.suggest(json!({"q": "Harry Potter and the pri", "limit": 1})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "Harry Potter and the prince",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({"q": "har", "filter": "genre = novel"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "hard",
+        "count": 1
+      },
+      {
+        "suggestion": "harvest",
+        "count": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_bad_parameters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.suggest(json!({"q": "har", "limit": "ten"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.limit`: expected a positive integer, but found a string: `\"ten\"`",
+      "code": "invalid_suggest_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_limit"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({"q": "har", "filter": "genre = novel"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `genre` is not filterable. This index does not have configured filterable attributes.\n1:6 genre = novel",
+      "code": "invalid_suggest_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_filter"
+    }
+    "###);
+}
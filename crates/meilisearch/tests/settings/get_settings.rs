@@ -142,7 +142,7 @@ test_setting_routes!(
     {
         setting: faceting,
         update_verb: patch,
-        default_value: {"maxValuesPerFacet": 100, "sortFacetValuesBy": {"*": "alpha"}}
+        default_value: {"maxValuesPerFacet": 100, "sortFacetValuesBy": {"*": "alpha"}, "hierarchicalFacets": {}, "exactFacets": []}
     },
     {
         setting: search_cutoff_ms,
@@ -164,6 +164,26 @@ test_setting_routes!(
         update_verb: put,
         default_value: "indexingTime"
     },
+    {
+        setting: infix_searchable_attributes,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: curation_rules,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: date_attributes,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: nested_attributes,
+        update_verb: put,
+        default_value: []
+    },
     {
         setting: proximity_precision,
         update_verb: put,
@@ -197,7 +217,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 24);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -217,7 +237,9 @@ async fn get_settings() {
             "maxValuesPerFacet": 100,
             "sortFacetValuesBy": {
                 "*": "alpha"
-            }
+            },
+            "hierarchicalFacets": {},
+            "exactFacets": []
         })
     );
     assert_eq!(
@@ -229,6 +251,10 @@ async fn get_settings() {
     assert_eq!(settings["proximityPrecision"], json!("byWord"));
     assert_eq!(settings["searchCutoffMs"], json!(null));
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
+    assert_eq!(settings["infixSearchableAttributes"], json!([]));
+    assert_eq!(settings["curationRules"], json!([]));
+    assert_eq!(settings["dateAttributes"], json!([]));
+    assert_eq!(settings["nestedAttributes"], json!([]));
     assert_eq!(settings["facetSearch"], json!(true));
     assert_eq!(settings["embedders"], json!({}));
 }
@@ -312,7 +338,9 @@ async fn secrets_are_hidden_in_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "exactFacets": []
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -333,7 +361,11 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "infixSearchableAttributes": [],
+      "curationRules": [],
+      "dateAttributes": [],
+      "nestedAttributes": []
     }
     "#);
 
@@ -45,6 +45,12 @@ impl From<AscDescError> for CriterionError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 CriterionError::ReservedNameForFilter { name: "_geoBoundingBox".to_string() }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoPolygon") => {
+                CriterionError::ReservedNameForFilter { name: "_geoPolygon".to_string() }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoContains") => {
+                CriterionError::ReservedNameForFilter { name: "_geoContains".to_string() }
+            }
             AscDescError::ReservedKeyword { name } => CriterionError::ReservedName { name },
         }
     }
@@ -82,6 +88,8 @@ impl FromStr for Member {
                 if is_reserved_keyword(text)
                     || text.starts_with("_geoRadius(")
                     || text.starts_with("_geoBoundingBox(")
+                    || text.starts_with("_geoPolygon(")
+                    || text.starts_with("_geoContains(")
                     || text.starts_with("_geo(")
                     || text.starts_with("_geoDistance(")
                 {
@@ -185,6 +193,12 @@ impl From<AscDescError> for SortError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 SortError::ReservedNameForFilter { name: String::from("_geoBoundingBox") }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoPolygon") => {
+                SortError::ReservedNameForFilter { name: String::from("_geoPolygon") }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoContains") => {
+                SortError::ReservedNameForFilter { name: String::from("_geoContains") }
+            }
             AscDescError::ReservedKeyword { name } => SortError::ReservedName { name },
         }
     }
@@ -1,2 +1,3 @@
 pub const RESERVED_VECTORS_FIELD_NAME: &str = "_vectors";
 pub const RESERVED_GEO_FIELD_NAME: &str = "_geo";
+pub const RESERVED_GEOJSON_FIELD_NAME: &str = "_geojson";
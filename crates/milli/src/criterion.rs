@@ -3,8 +3,9 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
 
-use crate::{AscDesc, Member};
+use crate::{AscDesc, Filter, Member};
 
 #[derive(Error, Debug)]
 pub enum CriterionError {
@@ -22,6 +23,10 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error("`{name}` ranking rule is invalid. {reason}")]
+    InvalidDecay { name: String, reason: String },
+    #[error("`{name}` ranking rule is invalid. {reason}")]
+    InvalidBoost { name: String, reason: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +50,13 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by how close the value of the field specified is to an origin.
+    Decay(Decay),
+    /// The documents matching the filter expression are ranked before the others.
+    ///
+    /// Filters are not weighted: several boosts are applied in the order of the ranking
+    /// rules, the documents matching the first one being ranked before all the others.
+    Boost(String),
 }
 
 impl Criterion {
@@ -52,11 +64,134 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Decay(decay) => Some(&decay.field),
             _otherwise => None,
         }
     }
 }
 
+/// A ranking rule scoring the documents by the distance between the value of a numeric
+/// field and an origin, e.g. `decay(publishedAt, origin=now, scale=7d)` or `closeTo(price, 100)`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Decay {
+    pub field: String,
+    pub origin: DecayOrigin,
+    /// The distance to the origin at which the score is equal to `decay`.
+    ///
+    /// `None` for `closeTo`, that only orders the documents by increasing distance to the origin.
+    pub scale: Option<DecayScale>,
+    pub function: DecayFunction,
+    /// The score of the documents at `scale` from the origin, between 0 and 1 excluded.
+    pub decay: f64,
+}
+
+// The parameters of a decay are always finite numbers, see `parse_decay`.
+impl Eq for Decay {}
+
+impl Decay {
+    pub const DEFAULT_DECAY: f64 = 0.5;
+
+    /// Returns the score, between 0 and 1, of a value of the field.
+    pub fn score(&self, origin: f64, value: f64) -> f64 {
+        let distance = (value - origin).abs();
+        let Some(scale) = self.scale else {
+            return 1.0 / (1.0 + distance);
+        };
+        let scale = scale.as_f64();
+        match self.function {
+            DecayFunction::Gauss => {
+                let sigma_squared = -(scale * scale) / (2.0 * self.decay.ln());
+                (-(distance * distance) / (2.0 * sigma_squared)).exp()
+            }
+            DecayFunction::Exp => (self.decay.ln() / scale * distance).exp(),
+            DecayFunction::Linear => {
+                let s = scale / (1.0 - self.decay);
+                ((s - distance) / s).max(0.0)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+pub enum DecayOrigin {
+    /// The current Unix timestamp, in seconds, at search time.
+    Now,
+    Value(f64),
+}
+
+impl DecayOrigin {
+    pub fn resolve(&self) -> f64 {
+        match self {
+            DecayOrigin::Now => time::OffsetDateTime::now_utc().unix_timestamp() as f64,
+            DecayOrigin::Value(value) => *value,
+        }
+    }
+}
+
+/// A scale, optionally expressed as a duration that is converted to seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+pub struct DecayScale {
+    pub value: f64,
+    pub unit: Option<DurationUnit>,
+}
+
+impl DecayScale {
+    pub fn as_f64(&self) -> f64 {
+        self.value * self.unit.map_or(1.0, DurationUnit::seconds)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum DurationUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+}
+
+impl DurationUnit {
+    const ALL: [(&'static str, DurationUnit); 5] = [
+        ("s", DurationUnit::Seconds),
+        ("m", DurationUnit::Minutes),
+        ("h", DurationUnit::Hours),
+        ("d", DurationUnit::Days),
+        ("w", DurationUnit::Weeks),
+    ];
+
+    pub fn seconds(self) -> f64 {
+        match self {
+            DurationUnit::Seconds => 1.0,
+            DurationUnit::Minutes => 60.0,
+            DurationUnit::Hours => 3600.0,
+            DurationUnit::Days => 86_400.0,
+            DurationUnit::Weeks => 604_800.0,
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        Self::ALL.iter().find(|(_, unit)| *unit == self).map(|(suffix, _)| *suffix).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+pub enum DecayFunction {
+    #[default]
+    Gauss,
+    Linear,
+    Exp,
+}
+
+impl DecayFunction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DecayFunction::Gauss => "gauss",
+            DecayFunction::Linear => "linear",
+            DecayFunction::Exp => "exp",
+        }
+    }
+}
+
 impl FromStr for Criterion {
     type Err = CriterionError;
 
@@ -68,6 +203,10 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            text if text.starts_with("decay(") || text.starts_with("closeTo(") => {
+                parse_decay(text).map(Criterion::Decay)
+            }
+            text if text.starts_with("boost(") => parse_boost(text).map(Criterion::Boost),
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
@@ -79,6 +218,108 @@ impl FromStr for Criterion {
     }
 }
 
+fn parse_decay(text: &str) -> Result<Decay, CriterionError> {
+    let invalid = |reason: &str| CriterionError::InvalidDecay {
+        name: text.to_string(),
+        reason: reason.to_string(),
+    };
+    let (name, args) = text.split_once('(').unwrap();
+    let args = args.strip_suffix(')').ok_or_else(|| invalid("Missing closing parenthesis."))?;
+    let mut args = args.split(',').map(str::trim);
+    let field = args.next().filter(|field| !field.is_empty());
+    let field = field.ok_or_else(|| invalid("Missing the field name."))?.to_string();
+
+    let parse_number = |value: &str| match value.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(invalid(&format!("`{value}` is not a valid number."))),
+    };
+    let parse_origin = |value: &str| match value {
+        "now" => Ok(DecayOrigin::Now),
+        value => parse_number(value).map(DecayOrigin::Value),
+    };
+
+    if name == "closeTo" {
+        let origin = args.next().ok_or_else(|| invalid("Missing the origin."))?;
+        let origin = parse_origin(origin)?;
+        if args.next().is_some() {
+            return Err(invalid("`closeTo` expects a field name and an origin."));
+        }
+        return Ok(Decay {
+            field,
+            origin,
+            scale: None,
+            function: DecayFunction::default(),
+            decay: Decay::DEFAULT_DECAY,
+        });
+    }
+
+    let mut origin = None;
+    let mut scale = None;
+    let mut function = DecayFunction::default();
+    let mut decay = Decay::DEFAULT_DECAY;
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            return Err(invalid(&format!("Expected a `key=value` parameter, got `{arg}`.")));
+        };
+        match (key.trim(), value.trim()) {
+            ("origin", value) => origin = Some(parse_origin(value)?),
+            ("scale", value) => {
+                let unit = DurationUnit::ALL.iter().find(|(suffix, _)| value.ends_with(suffix));
+                let (value, unit) = match unit {
+                    Some((suffix, unit)) => (value.strip_suffix(suffix).unwrap(), Some(*unit)),
+                    None => (value, None),
+                };
+                let value = parse_number(value)?;
+                if value <= 0.0 {
+                    return Err(invalid("The `scale` must be strictly positive."));
+                }
+                scale = Some(DecayScale { value, unit });
+            }
+            ("function", "gauss") => function = DecayFunction::Gauss,
+            ("function", "linear") => function = DecayFunction::Linear,
+            ("function", "exp") => function = DecayFunction::Exp,
+            ("function", value) => {
+                return Err(invalid(&format!(
+                    "Unknown function `{value}`, expected `gauss`, `linear` or `exp`."
+                )))
+            }
+            ("decay", value) => {
+                decay = parse_number(value)?;
+                if decay <= 0.0 || decay >= 1.0 {
+                    return Err(invalid("The `decay` must be between 0 and 1 excluded."));
+                }
+            }
+            (key, _) => {
+                return Err(invalid(&format!(
+                    "Unknown parameter `{key}`, expected `origin`, `scale`, `function` or `decay`."
+                )))
+            }
+        }
+    }
+
+    Ok(Decay {
+        field,
+        origin: origin.ok_or_else(|| invalid("Missing the `origin` parameter."))?,
+        scale: Some(scale.ok_or_else(|| invalid("Missing the `scale` parameter."))?),
+        function,
+        decay,
+    })
+}
+
+fn parse_boost(text: &str) -> Result<String, CriterionError> {
+    let invalid = |reason: String| CriterionError::InvalidBoost { name: text.to_string(), reason };
+    let filter = text
+        .strip_prefix("boost(")
+        .and_then(|filter| filter.strip_suffix(')'))
+        .ok_or_else(|| invalid("Missing closing parenthesis.".to_string()))?
+        .trim();
+    match Filter::from_str(filter) {
+        Ok(Some(_)) => Ok(filter.to_string()),
+        Ok(None) => Err(invalid("Missing the filter expression.".to_string())),
+        Err(error) => Err(invalid(error.to_string())),
+    }
+}
+
 pub fn default_criteria() -> Vec<Criterion> {
     vec![
         Criterion::Words,
@@ -103,7 +344,36 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Decay(decay) => fmt::Display::fmt(decay, f),
+            Boost(filter) => write!(f, "boost({filter})"),
+        }
+    }
+}
+
+impl fmt::Display for DecayOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayOrigin::Now => f.write_str("now"),
+            DecayOrigin::Value(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl fmt::Display for Decay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Decay { field, origin, scale, function, decay } = self;
+        let Some(DecayScale { value, unit }) = scale else {
+            return write!(f, "closeTo({field}, {origin})");
+        };
+        let unit = unit.map_or("", DurationUnit::suffix);
+        write!(f, "decay({field}, origin={origin}, scale={value}{unit}")?;
+        if *function != DecayFunction::default() {
+            write!(f, ", function={}", function.as_str())?;
         }
+        if *decay != Decay::DEFAULT_DECAY {
+            write!(f, ", decay={decay}")?;
+        }
+        f.write_str(")")
     }
 }
 
@@ -130,6 +400,41 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            (
+                "decay(publishedAt, origin=now, scale=7d)",
+                Criterion::Decay(Decay {
+                    field: S("publishedAt"),
+                    origin: DecayOrigin::Now,
+                    scale: Some(DecayScale { value: 7.0, unit: Some(DurationUnit::Days) }),
+                    function: DecayFunction::Gauss,
+                    decay: 0.5,
+                }),
+            ),
+            (
+                "decay(price,origin=10.5,scale=2,function=linear,decay=0.2)",
+                Criterion::Decay(Decay {
+                    field: S("price"),
+                    origin: DecayOrigin::Value(10.5),
+                    scale: Some(DecayScale { value: 2.0, unit: None }),
+                    function: DecayFunction::Linear,
+                    decay: 0.2,
+                }),
+            ),
+            ("boost(inStock = true)", Criterion::Boost(S("inStock = true"))),
+            (
+                "boost( brand IN [acme, globex] AND price < 10 )",
+                Criterion::Boost(S("brand IN [acme, globex] AND price < 10")),
+            ),
+            (
+                "closeTo(price, 100)",
+                Criterion::Decay(Decay {
+                    field: S("price"),
+                    origin: DecayOrigin::Value(100.0),
+                    scale: None,
+                    function: DecayFunction::Gauss,
+                    decay: 0.5,
+                }),
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -165,6 +470,59 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("_geoPolygon:asc", ReservedNameForFilter { name: S("_geoPolygon") }),
+            (
+                "_geoPolygon([42, 75], [75, 59], [12, 13]):asc",
+                ReservedNameForFilter { name: S("_geoPolygon") },
+            ),
+            ("_geoContains:asc", ReservedNameForFilter { name: S("_geoContains") }),
+            ("_geoContains(42, 75):asc", ReservedNameForFilter { name: S("_geoContains") }),
+            (
+                "decay(price, scale=2",
+                InvalidDecay {
+                    name: S("decay(price, scale=2"),
+                    reason: S("Missing closing parenthesis."),
+                },
+            ),
+            (
+                "decay(price, scale=2)",
+                InvalidDecay {
+                    name: S("decay(price, scale=2)"),
+                    reason: S("Missing the `origin` parameter."),
+                },
+            ),
+            (
+                "decay(price, origin=now, scale=-1d)",
+                InvalidDecay {
+                    name: S("decay(price, origin=now, scale=-1d)"),
+                    reason: S("The `scale` must be strictly positive."),
+                },
+            ),
+            (
+                "decay(price, origin=0, scale=1, function=cubic)",
+                InvalidDecay {
+                    name: S("decay(price, origin=0, scale=1, function=cubic)"),
+                    reason: S("Unknown function `cubic`, expected `gauss`, `linear` or `exp`."),
+                },
+            ),
+            (
+                "boost()",
+                InvalidBoost { name: S("boost()"), reason: S("Missing the filter expression.") },
+            ),
+            (
+                "boost(inStock = true",
+                InvalidBoost {
+                    name: S("boost(inStock = true"),
+                    reason: S("Missing closing parenthesis."),
+                },
+            ),
+            (
+                "closeTo(price, cheap)",
+                InvalidDecay {
+                    name: S("closeTo(price, cheap)"),
+                    reason: S("`cheap` is not a valid number."),
+                },
+            ),
         ];
 
         for (input, expected) in invalid_criteria {
@@ -186,4 +544,32 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn display_decay_criterion() {
+        for text in [
+            "decay(publishedAt, origin=now, scale=7d)",
+            "decay(price, origin=10.5, scale=2, function=linear, decay=0.2)",
+            "closeTo(price, 100)",
+        ] {
+            let criterion: Criterion = text.parse().unwrap();
+            assert_eq!(criterion.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn decay_scores() {
+        for function in [DecayFunction::Gauss, DecayFunction::Linear, DecayFunction::Exp] {
+            let decay = Decay {
+                field: S("price"),
+                origin: DecayOrigin::Value(100.0),
+                scale: Some(DecayScale { value: 10.0, unit: None }),
+                function,
+                decay: 0.5,
+            };
+            assert_eq!(decay.score(100.0, 100.0), 1.0, "{function:?}");
+            assert!((decay.score(100.0, 110.0) - 0.5).abs() < 1e-9, "{function:?}");
+            assert!((decay.score(100.0, 90.0) - 0.5).abs() < 1e-9, "{function:?}");
+            assert!(decay.score(100.0, 105.0) > decay.score(100.0, 115.0), "{function:?}");
+        }
+    }
 }
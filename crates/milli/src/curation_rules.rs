@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A merchandising rule that pins documents at given positions or hides documents
+/// from the results of the queries matching it.
+///
+/// The documents are referred to by their external ids.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CurationRule {
+    pub query: String,
+    pub match_type: QueryMatchType,
+    pub pinned: Vec<PinnedDocument>,
+    pub hidden: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryMatchType {
+    /// The rule applies when the search query is the rule query.
+    #[default]
+    Exact,
+    /// The rule applies when the search query contains the words of the rule query,
+    /// next to each other and in the same order.
+    ContainsWords,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedDocument {
+    pub id: String,
+    /// The 0-based position of the document in the results.
+    pub position: usize,
+}
+
+impl CurationRule {
+    /// Returns whether the rule applies to the search query.
+    ///
+    /// The queries are compared case-insensitively and regardless of the whitespaces.
+    pub fn matches(&self, query: &str) -> bool {
+        let rule_words = normalized_words(&self.query);
+        if rule_words.is_empty() {
+            return false;
+        }
+        let query_words = normalized_words(query);
+        match self.match_type {
+            QueryMatchType::Exact => query_words == rule_words,
+            QueryMatchType::ContainsWords => {
+                query_words.windows(rule_words.len()).any(|window| window == rule_words)
+            }
+        }
+    }
+}
+
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace().map(str::to_lowercase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::*;
+
+    #[test]
+    fn curation_rule_matches() {
+        let rule = |query: &str, match_type| CurationRule {
+            query: query.to_string(),
+            match_type,
+            pinned: vec![PinnedDocument { id: S("1"), position: 0 }],
+            hidden: vec![],
+        };
+
+        let exact = rule("Red  Shoes", QueryMatchType::Exact);
+        assert!(exact.matches("red shoes"));
+        assert!(exact.matches(" RED shoes "));
+        assert!(!exact.matches("red shoes for kids"));
+        assert!(!exact.matches("red"));
+
+        let contains = rule("red shoes", QueryMatchType::ContainsWords);
+        assert!(contains.matches("red shoes"));
+        assert!(contains.matches("cheap red shoes for kids"));
+        assert!(!contains.matches("shoes red"));
+        assert!(!contains.matches("redshoes"));
+
+        assert!(!rule(" ", QueryMatchType::ContainsWords).matches("red shoes"));
+    }
+}
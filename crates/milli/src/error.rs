@@ -16,8 +16,16 @@ use crate::thread_pool_no_abort::PanicCatched;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    [RESERVED_GEO_FIELD_NAME, "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"]
-        .contains(&keyword)
+    [
+        RESERVED_GEO_FIELD_NAME,
+        "_geoDistance",
+        "_geoPoint",
+        "_geoRadius",
+        "_geoBoundingBox",
+        "_geoPolygon",
+        "_geoContains",
+    ]
+    .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -124,6 +132,28 @@ and can not be more than 511 bytes.", .document_id.to_string()
         invalid_facets_name: BTreeSet<String>,
         valid_facets_name: BTreeSet<String>,
     },
+    #[error("Invalid facet ranges, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetRanges {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
+    #[error("Invalid facet histograms, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetHistograms {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
+    #[error("Invalid facet children, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetChildren {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
+    #[error("Invalid facet groups, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetGroups {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
+    #[error("Attribute `{attribute}` is not a hierarchical facet. Declare it with its separator in the `faceting.hierarchicalFacets` index setting.")]
+    NotHierarchicalFacet { attribute: String },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
@@ -158,6 +188,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
         }
     )]
     InvalidDistinctAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("The search cannot be resumed from this cursor, it was returned by a search with other parameters or the index was updated since.")]
+    InvalidSearchCursor,
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -211,6 +243,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     UnknownInternalDocumentId { document_id: DocumentId },
     #[error("`minWordSizeForTypos` setting is invalid. `oneTypo` and `twoTypos` fields should be between `0` and `255`, and `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and twoTypos: {1}`.")]
     InvalidMinTypoWordLenSetting(u8, u8),
+    #[error("`.faceting.hierarchicalFacets.{attribute}`: The separator of a hierarchical facet cannot be empty.")]
+    InvalidHierarchicalFacetSeparator { attribute: String },
     #[error(transparent)]
     VectorEmbeddingError(#[from] crate::vector::Error),
     #[error(transparent)]
@@ -227,6 +261,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSimilarEmbedder(String),
     #[error("Too many vectors for document with id {0}: found {1}, but limited to 256.")]
     TooManyVectors(String, usize),
+    #[error("The nested attribute `{attribute}` contains {count} elements, but a nested attribute cannot contain more than 65,536 elements.")]
+    TooManyNestedElements { attribute: String, count: usize },
     #[error("`.embedders.{embedder_name}`: Field `{field}` unavailable for source `{source_}` (only available for sources: {}). Available fields: {}",
         allowed_sources_for_field
          .iter()
@@ -340,6 +376,8 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not a valid GeoJSON geometry: {reason}.")]
+    BadGeoJson { document_id: Value, reason: String },
 }
 
 fn format_invalid_filter_distribution(
@@ -0,0 +1,177 @@
+use serde_json::Value;
+
+use crate::error::UserError;
+use crate::heed_codec::ElementFacet;
+use crate::{normalize_facet_unless_exact, truncate_facet_value, Result};
+
+/// Calls `f` with the position, the path relative to the element and the facet value
+/// of everything contained in the elements of a nested field.
+///
+/// An object is considered to be the single element of the field and every element
+/// is registered as existing under the empty path. Booleans are registered as strings
+/// and arrays are flattened into the path of the field containing them.
+///
+/// The strings of the fields for which `is_exact` returns `true`, given their path
+/// relative to the element, are not normalized.
+///
+/// The positions are stored on a `u16`, so a nested field containing more
+/// than 65,536 elements is refused.
+pub fn for_each_element_facet(
+    attribute: &str,
+    value: &Value,
+    is_exact: &impl Fn(&str) -> bool,
+    f: &mut impl FnMut(u16, &str, ElementFacet<'_>) -> Result<()>,
+) -> Result<()> {
+    let elements = match value {
+        Value::Array(elements) => elements.as_slice(),
+        element => std::slice::from_ref(element),
+    };
+    if elements.len() > u16::MAX as usize + 1 {
+        return Err(UserError::TooManyNestedElements {
+            attribute: attribute.to_string(),
+            count: elements.len(),
+        }
+        .into());
+    }
+
+    for (position, element) in elements.iter().enumerate() {
+        let position = position as u16;
+        match element {
+            Value::Null => continue,
+            Value::Object(object) => {
+                f(position, "", ElementFacet::Exists)?;
+                for (key, value) in object {
+                    for_each_field_facet(position, key, value, is_exact, f)?;
+                }
+            }
+            _ => f(position, "", ElementFacet::Exists)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn for_each_field_facet(
+    position: u16,
+    path: &str,
+    value: &Value,
+    is_exact: &impl Fn(&str) -> bool,
+    f: &mut impl FnMut(u16, &str, ElementFacet<'_>) -> Result<()>,
+) -> Result<()> {
+    f(position, path, ElementFacet::Exists)?;
+    for_each_value_facet(position, path, value, is_exact, f)
+}
+
+fn for_each_value_facet(
+    position: u16,
+    path: &str,
+    value: &Value,
+    is_exact: &impl Fn(&str) -> bool,
+    f: &mut impl FnMut(u16, &str, ElementFacet<'_>) -> Result<()>,
+) -> Result<()> {
+    match value {
+        Value::Null => Ok(()),
+        Value::Bool(boolean) => {
+            f(position, path, ElementFacet::String(if *boolean { "true" } else { "false" }))
+        }
+        Value::Number(number) => match number.as_f64() {
+            Some(number) if number.is_finite() => f(position, path, ElementFacet::Number(number)),
+            _ => Ok(()),
+        },
+        Value::String(string) => {
+            let normalized = normalize_facet_unless_exact(string, is_exact(path));
+            let normalized = truncate_facet_value(&normalized);
+            if normalized.is_empty() {
+                Ok(())
+            } else {
+                f(position, path, ElementFacet::String(normalized))
+            }
+        }
+        Value::Array(values) => values
+            .iter()
+            .try_for_each(|value| for_each_value_facet(position, path, value, is_exact, f)),
+        Value::Object(object) => object.iter().try_for_each(|(key, value)| {
+            for_each_field_facet(position, &format!("{path}.{key}"), value, is_exact, f)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn element_facets() {
+        let value = json!([
+            { "color": "Red", "size": 42, "stock": { "available": true } },
+            null,
+            { "color": ["blue", ""], "tags": [{ "name": "new" }] },
+        ]);
+
+        let mut facets = Vec::new();
+        for_each_element_facet("variants", &value, &|_| false, &mut |position, path, facet| {
+            facets.push(format!("{position} {path:?} {facet:?}"));
+            Ok(())
+        })
+        .unwrap();
+
+        insta::assert_snapshot!(facets.join("\n"), @r###"
+        0 "" Exists
+        0 "color" Exists
+        0 "color" String("red")
+        0 "size" Exists
+        0 "size" Number(42.0)
+        0 "stock" Exists
+        0 "stock.available" Exists
+        0 "stock.available" String("true")
+        2 "" Exists
+        2 "color" Exists
+        2 "color" String("blue")
+        2 "tags" Exists
+        2 "tags.name" Exists
+        2 "tags.name" String("new")
+        "###);
+    }
+
+    #[test]
+    fn exact_element_facets() {
+        let value = json!([{ "color": "Red", "name": "Red" }]);
+
+        let mut facets = Vec::new();
+        let is_exact = |path: &str| path == "color";
+        for_each_element_facet("variants", &value, &is_exact, &mut |_, path, facet| {
+            if let ElementFacet::String(string) = facet {
+                facets.push(format!("{path:?} {string:?}"));
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        insta::assert_snapshot!(facets.join("\n"), @r###"
+        "color" "Red"
+        "name" "red"
+        "###);
+    }
+
+    #[test]
+    fn too_many_elements() {
+        let value = Value::Array(vec![json!({ "color": "red" }); u16::MAX as usize + 1]);
+        let mut last_position = 0;
+        for_each_element_facet("variants", &value, &|_| false, &mut |position, _, _| {
+            last_position = position;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(last_position, u16::MAX);
+
+        let value = Value::Array(vec![Value::Null; u16::MAX as usize + 2]);
+        let error = for_each_element_facet("variants", &value, &|_| false, &mut |_, _, _| Ok(()))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The nested attribute `variants` contains 65537 elements, but a nested attribute cannot contain more than 65,536 elements."
+        );
+    }
+}
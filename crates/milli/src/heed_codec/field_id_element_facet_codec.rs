@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+
+use heed::{BoxedError, BytesDecode, BytesEncode};
+
+use super::SliceTooShortError;
+use crate::heed_codec::facet::OrderedF64Codec;
+use crate::{try_split_array_at, FieldId};
+
+/// A facet value of an element of a nested field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElementFacet<'a> {
+    /// The field exists in the element.
+    Exists,
+    Number(f64),
+    /// A normalized string.
+    String(&'a str),
+}
+
+impl ElementFacet<'_> {
+    pub(crate) fn tag(&self) -> u8 {
+        match self {
+            ElementFacet::Exists => 0,
+            ElementFacet::Number(_) => 1,
+            ElementFacet::String(_) => 2,
+        }
+    }
+}
+
+/// Encodes the field id, the position of the element in the nested field and the facet value.
+pub struct FieldIdElementFacetCodec;
+
+impl<'a> BytesDecode<'a> for FieldIdElementFacetCodec {
+    type DItem = (FieldId, u16, ElementFacet<'a>);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (field_id_bytes, bytes) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        let field_id = u16::from_be_bytes(field_id_bytes);
+        let (position_bytes, bytes) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        let position = u16::from_be_bytes(position_bytes);
+        let (tag, bytes) = bytes.split_first().ok_or(SliceTooShortError)?;
+        let facet = match tag {
+            0 => ElementFacet::Exists,
+            1 => ElementFacet::Number(OrderedF64Codec::bytes_decode(bytes)?),
+            _ => ElementFacet::String(std::str::from_utf8(bytes)?),
+        };
+        Ok((field_id, position, facet))
+    }
+}
+
+impl<'a> BytesEncode<'a> for FieldIdElementFacetCodec {
+    type EItem = (FieldId, u16, ElementFacet<'a>);
+
+    fn bytes_encode(
+        (field_id, position, facet): &Self::EItem,
+    ) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(2 + 2 + 1 + 16);
+        bytes.extend_from_slice(&field_id.to_be_bytes());
+        bytes.extend_from_slice(&position.to_be_bytes());
+        bytes.push(facet.tag());
+        match facet {
+            ElementFacet::Exists => (),
+            ElementFacet::Number(number) => {
+                bytes.extend_from_slice(&OrderedF64Codec::bytes_encode(number)?)
+            }
+            ElementFacet::String(string) => bytes.extend_from_slice(string.as_bytes()),
+        }
+        Ok(Cow::Owned(bytes))
+    }
+}
@@ -18,15 +18,19 @@ use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
     FieldIdCodec, OrderedF64Codec,
 };
-use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
+use crate::heed_codec::{
+    BEU16StrCodec, FieldIdElementFacetCodec, FieldIdSizeCodec, FstSetCodec, StrBEU16Codec,
+    StrRefCodec,
+};
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
 use crate::vector::{ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, GeoPoint, LocalizedAttributesRule, ObkvCodec, Result, RoaringBitmapCodec,
-    RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
+    default_criteria, CboRoaringBitmapCodec, Criterion, CurationRule, DocumentId,
+    ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry,
+    FieldIdWordCountCodec, FieldidsWeightsMap, GeoJsonGeometry, GeoJsonShape, GeoPoint,
+    LocalizedAttributesRule, ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search,
+    U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -45,6 +49,7 @@ pub mod main_key {
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
@@ -63,13 +68,19 @@ pub mod main_key {
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const INFIX_SEARCHABLE_ATTRIBUTES: &str = "infix-searchable-attributes";
+    pub const DATE_ATTRIBUTES: &str = "date-attributes";
+    pub const NESTED_ATTRIBUTES: &str = "nested-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const HIERARCHICAL_FACETS: &str = "hierarchical-facets";
+    pub const EXACT_FACETS: &str = "exact-facets";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const CURATION_RULES: &str = "curation_rules";
     pub const FACET_SEARCH: &str = "facet_search";
     pub const PREFIX_SEARCH: &str = "prefix_search";
 }
@@ -92,11 +103,14 @@ pub mod db_name {
     pub const FACET_ID_EXISTS_DOCIDS: &str = "facet-id-exists-docids";
     pub const FACET_ID_IS_NULL_DOCIDS: &str = "facet-id-is-null-docids";
     pub const FACET_ID_IS_EMPTY_DOCIDS: &str = "facet-id-is-empty-docids";
+    pub const FACET_ID_SIZE_DOCIDS: &str = "facet-id-size-docids";
+    pub const FACET_ID_ELEMENT_DOCIDS: &str = "facet-id-element-docids";
     pub const FACET_ID_STRING_DOCIDS: &str = "facet-id-string-docids";
     pub const FACET_ID_NORMALIZED_STRING_STRINGS: &str = "facet-id-normalized-string-strings";
     pub const FACET_ID_STRING_FST: &str = "facet-id-string-fst";
     pub const FIELD_ID_DOCID_FACET_F64S: &str = "field-id-docid-facet-f64s";
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
+    pub const GEOJSON_GEOMETRIES: &str = "geojson-geometries";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
     pub const DOCUMENTS: &str = "documents";
@@ -146,6 +160,10 @@ pub struct Index {
     pub facet_id_is_null_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
     /// Maps the facet field id and the docids for which this field is considered empty
     pub facet_id_is_empty_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id and the number of values it contains with the docids that corresponds to them.
+    pub facet_id_size_docids: Database<FieldIdSizeCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id, the position of an element of a nested field and a value with the docids that corresponds to them.
+    pub facet_id_element_docids: Database<FieldIdElementFacetCodec, CboRoaringBitmapCodec>,
 
     /// Maps the facet field id and ranges of numbers with the docids that corresponds to them.
     pub facet_id_f64_docids: Database<FacetGroupKeyCodec<OrderedF64Codec>, FacetGroupValueCodec>,
@@ -161,6 +179,9 @@ pub struct Index {
     /// Maps the document id, the facet field id and the strings.
     pub field_id_docid_facet_strings: Database<FieldDocIdFacetStringCodec, Str>,
 
+    /// Maps the document id to the GeoJSON geometry of its `_geojson` field.
+    pub geojson_geometries: Database<BEU32, SerdeBincode<GeoJsonGeometry>>,
+
     /// Maps an embedder name to its id in the arroy store.
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
@@ -179,7 +200,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(27);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -213,10 +234,14 @@ impl Index {
             env.create_database(&mut wtxn, Some(FACET_ID_IS_NULL_DOCIDS))?;
         let facet_id_is_empty_docids =
             env.create_database(&mut wtxn, Some(FACET_ID_IS_EMPTY_DOCIDS))?;
+        let facet_id_size_docids = env.create_database(&mut wtxn, Some(FACET_ID_SIZE_DOCIDS))?;
+        let facet_id_element_docids =
+            env.create_database(&mut wtxn, Some(FACET_ID_ELEMENT_DOCIDS))?;
         let field_id_docid_facet_f64s =
             env.create_database(&mut wtxn, Some(FIELD_ID_DOCID_FACET_F64S))?;
         let field_id_docid_facet_strings =
             env.create_database(&mut wtxn, Some(FIELD_ID_DOCID_FACET_STRINGS))?;
+        let geojson_geometries = env.create_database(&mut wtxn, Some(GEOJSON_GEOMETRIES))?;
         // vector stuff
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
@@ -248,8 +273,11 @@ impl Index {
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_size_docids,
+            facet_id_element_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
+            geojson_geometries,
             vector_arroy,
             embedder_category_id,
             documents,
@@ -511,6 +539,35 @@ impl Index {
         }
     }
 
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the bounding box of GeoJSON geometries
+    /// to documents ids, the geometries themselves are stored in the `geojson_geometries` database.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rtree: &RTree<GeoJsonShape>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>().put(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the bounding box of GeoJSON geometries to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the bounding box of GeoJSON geometries to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn<'_>) -> Result<Option<RTree<GeoJsonShape>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>()
+            .get(rtxn, main_key::GEOJSON_RTREE_KEY)?)
+    }
+
     /* geo faceted */
 
     /// Writes the documents ids that are faceted with a _geo field.
@@ -904,7 +961,7 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct, Asc/Desc and decay fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
@@ -912,6 +969,7 @@ impl Index {
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
                 Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Decay(decay) => Some(decay.field),
                 _otherwise => None,
             });
 
@@ -1490,6 +1548,119 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_ATTRIBUTES)
     }
 
+    /// Returns the infix searchable attributes: attributes in which the words
+    /// can also be matched by their middle or their end, e.g. `*4471*`.
+    pub fn infix_searchable_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::INFIX_SEARCHABLE_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of infix searchable attributes field ids.
+    pub fn infix_searchable_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.infix_searchable_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(fid_map
+            .iter()
+            .filter(|(_, name)| attrs.iter().any(|attr| crate::is_faceted_by(name, attr)))
+            .map(|(fid, _)| fid)
+            .collect())
+    }
+
+    /// Writes the infix searchable attributes to the database.
+    pub(crate) fn put_infix_searchable_attributes(
+        &self,
+        txn: &mut RwTxn<'_>,
+        attrs: &[&str],
+    ) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::INFIX_SEARCHABLE_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the infix searchable attributes from the store.
+    pub(crate) fn delete_infix_searchable_attributes(
+        &self,
+        txn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::INFIX_SEARCHABLE_ATTRIBUTES)
+    }
+
+    /* date attributes */
+
+    /// Returns the attributes whose RFC 3339 string values are indexed as dates.
+    pub fn date_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::DATE_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of the date attributes field ids.
+    pub fn date_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.date_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(fid_map
+            .iter()
+            .filter(|(_, name)| attrs.iter().any(|attr| crate::is_faceted_by(name, attr)))
+            .map(|(fid, _)| fid)
+            .collect())
+    }
+
+    /// Writes the date attributes to the database.
+    pub(crate) fn put_date_attributes(&self, txn: &mut RwTxn<'_>, attrs: &[&str]) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::DATE_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the date attributes from the store.
+    pub(crate) fn delete_date_attributes(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::DATE_ATTRIBUTES)
+    }
+
+    /* nested attributes */
+
+    /// Returns the attributes whose array elements are filtered one by one.
+    pub fn nested_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::NESTED_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of the nested attributes field ids.
+    pub fn nested_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.nested_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(fid_map.iter().filter(|(_, name)| attrs.contains(name)).map(|(fid, _)| fid).collect())
+    }
+
+    /// Writes the nested attributes to the database.
+    pub(crate) fn put_nested_attributes(&self, txn: &mut RwTxn<'_>, attrs: &[&str]) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::NESTED_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the nested attributes from the store.
+    pub(crate) fn delete_nested_attributes(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::NESTED_ATTRIBUTES)
+    }
+
     pub fn max_values_per_facet(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::MAX_VALUES_PER_FACET)
     }
@@ -1527,6 +1698,65 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::SORT_FACET_VALUES_BY)
     }
 
+    /// Returns the separators of the hierarchical facets, by attribute.
+    pub fn hierarchical_facets(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, String>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, String>>>()
+            .get(txn, main_key::HIERARCHICAL_FACETS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_hierarchical_facets(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::HIERARCHICAL_FACETS, &val)
+    }
+
+    pub(crate) fn delete_hierarchical_facets(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::HIERARCHICAL_FACETS)
+    }
+
+    /// Returns the attributes whose string facet values are kept exact, without being lowercased or normalized.
+    pub fn exact_facets<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::EXACT_FACETS)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of the exact facets field ids.
+    pub fn exact_facets_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.exact_facets(txn)?;
+        if attrs.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(fid_map
+            .iter()
+            .filter(|(_, name)| attrs.iter().any(|attr| crate::is_faceted_by(name, attr)))
+            .map(|(fid, _)| fid)
+            .collect())
+    }
+
+    /// Writes the exact facets to the database.
+    pub(crate) fn put_exact_facets(&self, txn: &mut RwTxn<'_>, attrs: &[&str]) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::EXACT_FACETS,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the exact facets from the store.
+    pub(crate) fn delete_exact_facets(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_FACETS)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1631,6 +1861,31 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
     }
 
+    /// Returns the rules pinning or hiding documents for some queries.
+    pub fn curation_rules(&self, rtxn: &RoTxn<'_>) -> heed::Result<Vec<CurationRule>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<Vec<CurationRule>>>()
+            .get(rtxn, main_key::CURATION_RULES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_curation_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &[CurationRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<&[CurationRule]>>().put(
+            txn,
+            main_key::CURATION_RULES,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_curation_rules(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::CURATION_RULES)
+    }
+
     /// Put the embedding configs:
     /// 1. The name of the embedder
     /// 2. The configuration option for this embedder
@@ -1732,7 +1987,7 @@ pub(crate) mod tests {
     use memmap2::Mmap;
     use tempfile::TempDir;
 
-    use crate::constants::RESERVED_GEO_FIELD_NAME;
+    use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
     use crate::error::{Error, InternalError};
     use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
     use crate::progress::Progress;
@@ -2276,6 +2531,232 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn test_basic_geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S(RESERVED_GEO_FIELD_NAME) });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": "0", "lng": "0" } },
+                { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": "-175" } },
+                { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": "0", "lng": 175 } },
+                { "id": 3, RESERVED_GEO_FIELD_NAME: { "lat": 85, "lng": 0 } },
+                { "id": 4, RESERVED_GEO_FIELD_NAME: { "lat": "-85", "lng": "0" } },
+                { "id": 5, RESERVED_GEO_FIELD_NAME: { "lat": 8, "lng": -8 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut search = index.search(&rtxn);
+
+        // match a document in the middle of the polygon
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([5, -5], [5, 5], [-5, 5], [-5, -5])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+        // the polygon is concave and does not contain its center
+        let search_result = search
+            .filter(
+                Filter::from_str(
+                    "_geoPolygon([-10, -10], [-10, 10], [-5, 10], [-5, -5], [10, -5], [10, -10])",
+                )
+                .unwrap()
+                .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[5]>");
+
+        // cross the antimeridian
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([5, 170], [5, -170], [-5, -170], [-5, 170])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1, 2]>");
+
+        // go near the pole
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([80, -10], [89, -10], [89, 10], [80, 10])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[3]>");
+    }
+
+    #[test]
+    fn test_geo_filters_with_multiple_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S(RESERVED_GEO_FIELD_NAME) });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0 }, { "lat": 50, "lng": 50 }] },
+                { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 10, "lng": 10 } },
+                { "id": 2, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 50 }, { "lat": "50", "lng": "0" }] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            index.search(&rtxn).filter(filter).execute().unwrap().candidates
+        };
+
+        // any point of a document can match
+        insta::assert_debug_snapshot!(filter("_geoRadius(0, 0, 1000)"), @"RoaringBitmap<[0]>");
+        insta::assert_debug_snapshot!(filter("_geoRadius(50, 50, 1000)"), @"RoaringBitmap<[0]>");
+        insta::assert_debug_snapshot!(filter("_geoPolygon([51, -1], [51, 1], [49, 1], [49, -1])"), @"RoaringBitmap<[2]>");
+        insta::assert_debug_snapshot!(filter("_geoBoundingBox([51, 51], [49, 49])"), @"RoaringBitmap<[0]>");
+        // the document 2 has a latitude and a longitude in the bounding box but they belong to different points
+        insta::assert_debug_snapshot!(filter("_geoBoundingBox([1, 1], [-1, -1])"), @"RoaringBitmap<[0]>");
+        insta::assert_debug_snapshot!(filter("_geoBoundingBox([51, 1], [-1, -1])"), @"RoaringBitmap<[0, 2]>");
+        insta::assert_debug_snapshot!(filter("_geoRadius(10, 10, 1000)"), @"RoaringBitmap<[1]>");
+        drop(rtxn);
+
+        // all the points of a document are removed when it is updated or deleted
+        index
+            .add_documents(documents!([
+                { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 10, "lng": 10 } },
+            ]))
+            .unwrap();
+        index.delete_document("2");
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            index.search(&rtxn).filter(filter).execute().unwrap().candidates
+        };
+        insta::assert_debug_snapshot!(filter("_geoRadius(0, 0, 1000)"), @"RoaringBitmap<[]>");
+        insta::assert_debug_snapshot!(filter("_geoRadius(50, 50, 1000)"), @"RoaringBitmap<[]>");
+        insta::assert_debug_snapshot!(filter("_geoPolygon([51, -1], [51, 1], [49, 1], [49, -1])"), @"RoaringBitmap<[]>");
+        insta::assert_debug_snapshot!(filter("_geoRadius(10, 10, 1000)"), @"RoaringBitmap<[0, 1]>");
+        assert_eq!(index.geo_rtree(&rtxn).unwrap().unwrap().size(), 2);
+    }
+
+    #[test]
+    fn test_geojson_contains() {
+        let mut index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [
+                    [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                    [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]],
+                ] } },
+                { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[0, 0], [20, 20]] } },
+                { "id": 2 },
+                { "id": 3, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [
+                    [[20, 0], [30, 0], [20, 10], [20, 0]],
+                ] } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let error = index
+            .search(&rtxn)
+            .filter(Filter::from_str("_geoContains(2, 2)").unwrap().unwrap())
+            .execute()
+            .unwrap_err();
+        insta::assert_snapshot!(error, @r###"
+        Attribute `_geojson` is not filterable. This index does not have configured filterable attributes.
+        14:15 _geoContains(2, 2)
+        "###);
+        drop(rtxn);
+
+        // the geometries of the existing documents are indexed by the settings update
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S(RESERVED_GEOJSON_FIELD_NAME) });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            index.search(&rtxn).filter(filter).execute().unwrap().candidates
+        };
+        insta::assert_debug_snapshot!(filter("_geoContains(2, 2)"), @"RoaringBitmap<[0]>");
+        // the point is in the hole of the polygon but on the line
+        insta::assert_debug_snapshot!(filter("_geoContains(5, 5)"), @"RoaringBitmap<[1]>");
+        insta::assert_debug_snapshot!(filter("_geoContains(15, 15)"), @"RoaringBitmap<[1]>");
+        insta::assert_debug_snapshot!(filter("_geoContains(15, 14)"), @"RoaringBitmap<[]>");
+        insta::assert_debug_snapshot!(filter("_geoContains(2, 22)"), @"RoaringBitmap<[3]>");
+        // the point is in the bounding box of the triangle but not in the triangle
+        insta::assert_debug_snapshot!(filter("_geoContains(8, 28)"), @"RoaringBitmap<[]>");
+        insta::assert_debug_snapshot!(filter("NOT _geoContains(2, 2)"), @"RoaringBitmap<[1, 2, 3]>");
+        drop(rtxn);
+
+        // the geometries are replaced or removed when the documents are updated or deleted
+        index
+            .add_documents(documents!([
+                { "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[0, 2], [10, 2]] } },
+            ]))
+            .unwrap();
+        index.delete_document("3");
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            index.search(&rtxn).filter(filter).execute().unwrap().candidates
+        };
+        insta::assert_debug_snapshot!(filter("_geoContains(2, 2)"), @"RoaringBitmap<[0]>");
+        insta::assert_debug_snapshot!(filter("_geoContains(3, 3)"), @"RoaringBitmap<[1]>");
+        insta::assert_debug_snapshot!(filter("_geoContains(2, 22)"), @"RoaringBitmap<[]>");
+        assert_eq!(index.geojson_rtree(&rtxn).unwrap().unwrap().size(), 2);
+        assert_eq!(index.geojson_geometries.len(&rtxn).unwrap(), 2);
+        drop(rtxn);
+
+        // a partial update that doesn't contain the geometry keeps it
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+        index.add_documents(documents!([{ "id": 0, "name": "a road" }])).unwrap();
+        index.index_documents_config.update_method = IndexDocumentsMethod::ReplaceDocuments;
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            index.search(&rtxn).filter(filter).execute().unwrap().candidates
+        };
+        insta::assert_debug_snapshot!(filter("_geoContains(2, 2)"), @"RoaringBitmap<[0]>");
+        assert_eq!(index.geojson_geometries.len(&rtxn).unwrap(), 2);
+        drop(rtxn);
+
+        let error = index
+            .add_documents(documents!([
+                { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [1, 2] } },
+            ]))
+            .unwrap_err();
+        insta::assert_snapshot!(error, @r###"The `_geojson` field in the document with the id: `"4"` is not a valid GeoJSON geometry: the `type` must be `Polygon` or `LineString` but instead got `"Point"`."###);
+
+        let error = index
+            .add_documents(documents!([
+                { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[0, 0], [10, 0], [10, 10]]] } },
+            ]))
+            .unwrap_err();
+        insta::assert_snapshot!(error, @r###"The `_geojson` field in the document with the id: `"4"` is not a valid GeoJSON geometry: the rings of a `Polygon` must be closed and contain at least four positions."###);
+    }
+
     #[test]
     fn test_contains() {
         let index = TempIndex::new();
@@ -2836,6 +3317,7 @@ pub(crate) mod tests {
             candidates: _,
             document_scores: _,
             mut documents_ids,
+            cursor: _,
             degraded: _,
             used_negative_operator: _,
         } = search.execute().unwrap();
@@ -10,6 +10,7 @@ pub mod documents;
 
 mod asc_desc;
 mod criterion;
+mod curation_rules;
 mod error;
 mod external_documents_ids;
 pub mod facet;
@@ -44,14 +45,18 @@ use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
     execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    SearchCursor, SearchLogger, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 pub use {charabia as tokenizer, heed, rhai};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::criterion::{
+    default_criteria, Criterion, CriterionError, Decay, DecayFunction, DecayOrigin, DecayScale,
+    DurationUnit,
+};
+pub use self::curation_rules::{CurationRule, PinnedDocument, QueryMatchType};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -67,8 +72,12 @@ pub use self::heed_codec::{
 pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
 use self::localized_attributes_rules::LocalizedFieldIds;
-pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
+pub use self::search::did_you_mean::DidYouMean;
+pub use self::search::facet::{
+    FacetGroup, FacetNumberStats, FacetRange, FacetRangeCount, FacetValueHit, SearchForFacetValues,
+};
 pub use self::search::similar::Similar;
+pub use self::search::suggest::{Suggest, SuggestionHit};
 pub use self::search::{
     FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
     Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
@@ -100,6 +109,12 @@ pub type SmallVec8<T> = smallvec::SmallVec<[T; 8]>;
 /// expressed in term of latitude and longitude.
 pub type GeoPoint = rstar::primitives::GeomWithData<[f64; 3], (DocumentId, [f64; 2])>;
 
+/// A GeoJsonShape is the bounding box of a GeoJSON geometry expressed in terms of latitude
+/// and longitude. Its metadata is the DocumentId of the associated document, the exact
+/// geometry used to refine the matches of the bounding box is stored apart.
+pub type GeoJsonShape =
+    rstar::primitives::GeomWithData<rstar::primitives::Rectangle<[f64; 2]>, DocumentId>;
+
 /// The maximum length a LMDB key can be.
 ///
 /// Note that the actual allowed length is a little bit higher, but
@@ -328,6 +343,193 @@ pub fn lat_lng_to_xyz(coord: &[f64; 2]) -> [f64; 3] {
     [x, y, z]
 }
 
+/// A polygon on the earth, made of points expressed in terms of latitude and longitude,
+/// each point being linked to the next one and the last one to the first one.
+///
+/// The edges are the shortest way between their points in longitude, so an edge
+/// between the longitudes `170` and `-170` crosses the antimeridian.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoPolygon {
+    /// The points with their longitude shifted by 360 degrees where needed so that
+    /// no edge spans more than 180 degrees of longitude.
+    points: Vec<[f64; 2]>,
+}
+
+impl GeoPolygon {
+    pub fn new(mut points: Vec<[f64; 2]>) -> Self {
+        for i in 1..points.len() {
+            let previous = points[i - 1][1];
+            let lng = &mut points[i][1];
+            while *lng - previous > 180.0 {
+                *lng -= 360.0;
+            }
+            while *lng - previous < -180.0 {
+                *lng += 360.0;
+            }
+        }
+        GeoPolygon { points }
+    }
+
+    /// Returns an envelope, in the cartesian coordinates, containing all the points of the polygon.
+    ///
+    /// It is the slice of the sphere between the minimum and the maximum latitudes of the polygon.
+    pub fn envelope(&self) -> rstar::AABB<[f64; 3]> {
+        let (min_lat, max_lat) =
+            self.points.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), [lat, _]| {
+                (min.min(*lat), max.max(*lat))
+            });
+        let min_z = min_lat.to_radians().sin() - f64::EPSILON;
+        let max_z = max_lat.to_radians().sin() + f64::EPSILON;
+        rstar::AABB::from_corners([-1.0, -1.0, min_z], [1.0, 1.0, max_z])
+    }
+
+    /// Returns `true` if the point, expressed in terms of latitude and longitude, is inside the polygon.
+    pub fn contains(&self, &[lat, lng]: &[f64; 2]) -> bool {
+        // the polygon may go beyond the antimeridian so the point is checked on both sides of it
+        [lng - 360.0, lng, lng + 360.0].into_iter().any(|lng| {
+            let edges = self.points.iter().zip(self.points.iter().cycle().skip(1));
+            // cast a ray towards the increasing longitudes and count the crossed edges
+            let crossed = edges
+                .filter(|([lat_a, lng_a], [lat_b, lng_b])| {
+                    (*lat_a > lat) != (*lat_b > lat)
+                        && lng < (lng_b - lng_a) * (lat - lat_a) / (lat_b - lat_a) + lng_a
+                })
+                .count();
+            crossed % 2 == 1
+        })
+    }
+}
+
+/// A GeoJSON geometry stored in the `_geojson` field of a document.
+///
+/// The positions are expressed in terms of latitude and longitude, unlike the GeoJSON
+/// format which expresses them as `[longitude, latitude]`. The edges are straight lines
+/// in latitude and longitude, a geometry crossing the antimeridian must be split.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum GeoJsonGeometry {
+    /// The exterior ring followed by the holes, each ring being closed.
+    Polygon(Vec<Vec<[f64; 2]>>),
+    LineString(Vec<[f64; 2]>),
+}
+
+impl GeoJsonGeometry {
+    /// Parses and validates a GeoJSON `Polygon` or `LineString` geometry.
+    ///
+    /// Returns the reason why the geometry is invalid in the `Err` variant.
+    pub fn from_value(value: &Value) -> std::result::Result<Self, String> {
+        let Value::Object(object) = value else {
+            return Err(format!("expected an object but instead got `{value}`"));
+        };
+        let coordinates = object
+            .get("coordinates")
+            .ok_or_else(|| String::from("the `coordinates` field is missing"))?;
+
+        match object.get("type") {
+            Some(Value::String(kind)) if kind == "Polygon" => {
+                let Value::Array(rings) = coordinates else {
+                    return Err(format!(
+                        "expected an array of rings in `coordinates` but instead got `{coordinates}`"
+                    ));
+                };
+                if rings.is_empty() {
+                    return Err(String::from("a `Polygon` must contain at least one ring"));
+                }
+                let rings = rings
+                    .iter()
+                    .map(geojson_positions)
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                if rings.iter().any(|ring| ring.len() < 4 || ring.first() != ring.last()) {
+                    return Err(String::from(
+                        "the rings of a `Polygon` must be closed and contain at least four positions",
+                    ));
+                }
+                Ok(GeoJsonGeometry::Polygon(rings))
+            }
+            Some(Value::String(kind)) if kind == "LineString" => {
+                let line = geojson_positions(coordinates)?;
+                if line.len() < 2 {
+                    return Err(String::from("a `LineString` must contain at least two positions"));
+                }
+                Ok(GeoJsonGeometry::LineString(line))
+            }
+            Some(kind) => Err(format!(
+                "the `type` must be `Polygon` or `LineString` but instead got `{kind}`"
+            )),
+            None => Err(String::from("the `type` field is missing")),
+        }
+    }
+
+    /// Returns the bounding box of the geometry, in terms of latitude and longitude.
+    pub fn bounding_box(&self) -> rstar::primitives::Rectangle<[f64; 2]> {
+        let positions = match self {
+            GeoJsonGeometry::Polygon(rings) => &rings[0],
+            GeoJsonGeometry::LineString(line) => line,
+        };
+        let envelope = rstar::AABB::from_points(positions.iter());
+        rstar::primitives::Rectangle::from_aabb(envelope)
+    }
+
+    /// Returns `true` if the point, expressed in terms of latitude and longitude, is inside
+    /// the polygon but not in one of its holes, or lies on the line.
+    pub fn contains(&self, point: &[f64; 2]) -> bool {
+        match self {
+            GeoJsonGeometry::Polygon(rings) => {
+                let (exterior, holes) = rings.split_first().unwrap();
+                ring_contains(exterior, point) && !holes.iter().any(|h| ring_contains(h, point))
+            }
+            GeoJsonGeometry::LineString(line) => line.windows(2).any(|segment| {
+                let ([lat_a, lng_a], [lat_b, lng_b], [lat, lng]) = (segment[0], segment[1], point);
+                let cross = (lat_b - lat_a) * (lng - lng_a) - (lng_b - lng_a) * (lat - lat_a);
+                cross.abs() <= 1e-9
+                    && lat_a.min(lat_b) - 1e-9 <= *lat
+                    && *lat <= lat_a.max(lat_b) + 1e-9
+                    && lng_a.min(lng_b) - 1e-9 <= *lng
+                    && *lng <= lng_a.max(lng_b) + 1e-9
+            }),
+        }
+    }
+}
+
+/// Parses a list of GeoJSON positions, `[longitude, latitude]`, into `[latitude, longitude]`.
+fn geojson_positions(value: &Value) -> std::result::Result<Vec<[f64; 2]>, String> {
+    let Value::Array(positions) = value else {
+        return Err(format!("expected an array of positions but instead got `{value}`"));
+    };
+    positions
+        .iter()
+        .map(|position| match position.as_array().map(Vec::as_slice) {
+            // an eventual altitude is ignored
+            Some([lng, lat, ..]) => match (lat.as_f64(), lng.as_f64()) {
+                (Some(lat), Some(lng))
+                    if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) =>
+                {
+                    Ok([lat, lng])
+                }
+                _ => Err(format!(
+                    "expected a `[longitude, latitude]` position with a longitude between -180 and 180 and a latitude between -90 and 90 but instead got `{position}`"
+                )),
+            },
+            _ => Err(format!(
+                "expected a `[longitude, latitude]` position but instead got `{position}`"
+            )),
+        })
+        .collect()
+}
+
+/// Returns `true` if the point is inside the closed ring, by casting a ray towards
+/// the increasing longitudes and counting the crossed edges.
+fn ring_contains(ring: &[[f64; 2]], &[lat, lng]: &[f64; 2]) -> bool {
+    let crossed = ring
+        .windows(2)
+        .filter(|edge| {
+            let ([lat_a, lng_a], [lat_b, lng_b]) = (edge[0], edge[1]);
+            (lat_a > lat) != (lat_b > lat)
+                && lng < (lng_b - lng_a) * (lat - lat_a) / (lat_b - lat_a) + lng_a
+        })
+        .count();
+    crossed % 2 == 1
+}
+
 /// Returns `true` if the field match one of the faceted fields.
 /// See the function [`is_faceted_by`] below to see what “matching” means.
 pub fn is_faceted(field: &str, faceted_fields: impl IntoIterator<Item = impl AsRef<str>>) -> bool {
@@ -368,6 +570,34 @@ pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Normalizes a facet value, unless it is the value of an exact facet which is kept as is
+/// to be filtered and distributed in a case-sensitive way.
+pub fn normalize_facet_unless_exact(original: &str, exact: bool) -> String {
+    if exact {
+        original.to_string()
+    } else {
+        normalize_facet(original)
+    }
+}
+
+/// Truncates a facet value to the biggest valid LMDB key size.
+pub(crate) fn truncate_facet_value(s: &str) -> &str {
+    let index = s
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(s.len()))
+        .take_while(|idx| idx <= &MAX_FACET_VALUE_LENGTH)
+        .last();
+
+    &s[..index.unwrap_or(0)]
+}
+
+/// Normalizes the separator of a hierarchical facet the same way as the facet values,
+/// without trimming it as its whitespaces are part of the separator.
+pub fn normalize_facet_separator(separator: &str) -> String {
+    CompatibilityDecompositionNormalizer.normalize_str(separator).to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -17,6 +17,10 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Decay(Decay),
+    Boost(Boost),
+    /// Returned for the documents pinned by a curation rule, instead of the ranking rules scores
+    Pinned(Pinned),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -53,6 +57,9 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Decay(details) => Some(details.rank),
+            ScoreDetails::Boost(details) => Some(details.rank()),
+            ScoreDetails::Pinned(_) => Some(Rank { rank: 1, max_rank: 1 }),
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -98,6 +105,9 @@ impl ScoreDetails {
             ScoreDetails::ExactWords(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::Sort(sort) => RankOrValue::Sort(sort),
             ScoreDetails::GeoSort(geosort) => RankOrValue::GeoSort(geosort),
+            ScoreDetails::Decay(decay) => RankOrValue::Rank(decay.rank),
+            ScoreDetails::Boost(boost) => RankOrValue::Rank(boost.rank()),
+            ScoreDetails::Pinned(_) => RankOrValue::Rank(Rank { rank: 1, max_rank: 1 }),
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
@@ -248,6 +258,36 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::Decay(details) => {
+                    let decay = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        details.criterion.clone()
+                    };
+                    let decay_details = serde_json::json!({
+                        "order": order,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(decay, decay_details);
+                    order += 1;
+                }
+                ScoreDetails::Boost(details) => {
+                    let boost_details = serde_json::json!({
+                        "order": order,
+                        "matched": details.matched,
+                        "score": details.rank().local_score(),
+                    });
+                    details_map.insert(format!("boost({})", details.filter), boost_details);
+                    order += 1;
+                }
+                ScoreDetails::Pinned(details) => {
+                    let pinned_details = serde_json::json!({
+                        "order": order,
+                        "position": details.position,
+                    });
+                    details_map.insert("pinned".into(), pinned_details);
+                    order += 1;
+                }
                 ScoreDetails::Vector(s) => {
                     let similarity = s.similarity.as_ref();
 
@@ -488,6 +528,48 @@ pub struct Vector {
     pub similarity: Option<f32>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decay {
+    /// The decay ranking rule as written in the settings, e.g. `closeTo(price, 100)`
+    pub criterion: String,
+    pub redacted: bool,
+    /// The decay score of the documents, quantized in [`Decay::MAX_RANK`] ranks.
+    ///
+    /// Documents without a value in the field have a rank of 0.
+    pub rank: Rank,
+}
+
+impl Decay {
+    pub const MAX_RANK: u32 = 100;
+
+    pub fn from_score(criterion: String, redacted: bool, score: Option<f64>) -> Self {
+        let rank = match score {
+            Some(score) => 1 + (score.clamp(0.0, 1.0) * (Self::MAX_RANK - 1) as f64).round() as u32,
+            None => 0,
+        };
+        Self { criterion, redacted, rank: Rank { rank, max_rank: Self::MAX_RANK } }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boost {
+    pub filter: String,
+    /// Whether the documents match the filter.
+    pub matched: bool,
+}
+
+impl Boost {
+    pub fn rank(&self) -> Rank {
+        Rank { rank: if self.matched { 2 } else { 1 }, max_rank: 2 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pinned {
+    /// The position at which the document is pinned.
+    pub position: usize,
+}
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
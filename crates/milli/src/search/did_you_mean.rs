@@ -0,0 +1,139 @@
+use std::cmp::Reverse;
+
+use charabia::TokenKind;
+use fst::automaton::{Automaton, Complement, Intersection, StartsWith, Str, Union};
+use fst::{IntoStreamer, Streamer};
+
+use super::{build_dfa, get_first, tokenize_query};
+use crate::{Index, Result};
+
+/// The words shorter than this number of characters are never corrected,
+/// as almost any word is at most two typos away from them.
+const MIN_WORD_LEN_TO_CORRECT: usize = 3;
+
+/// Computes a corrected version of a search query, to be suggested to the user
+/// as a "did you mean" when the query returns few or no results.
+///
+/// Each word of the query that doesn't exist in the index is replaced by the word of the index
+/// that is the closest to it, up to two typos away, using the same automata as the typo tolerance.
+/// When several words are equally close, the one contained in the most documents is preferred.
+pub struct DidYouMean<'a> {
+    query: &'a str,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> DidYouMean<'a> {
+    pub fn new(query: &'a str, rtxn: &'a heed::RoTxn<'a>, index: &'a Index) -> Self {
+        Self { query, rtxn, index }
+    }
+
+    /// Returns the corrected query, or `None` if all the words of the query are known
+    /// or none of them could be corrected.
+    pub fn execute(&self) -> Result<Option<String>> {
+        let words_fst = self.index.words_fst(self.rtxn)?;
+
+        let words: Vec<_> = tokenize_query(self.index, self.rtxn, self.query)?
+            .into_iter()
+            .filter(|token| matches!(token.kind, TokenKind::Word))
+            // ignore the attribute names of the field-scoped terms
+            .filter(|token| !self.query[token.byte_end..].starts_with(':'))
+            .collect();
+
+        let mut corrected = String::with_capacity(self.query.len());
+        let mut last_byte_end = 0;
+        let mut any_correction = false;
+        for (i, token) in words.iter().enumerate() {
+            let word = token.lemma();
+            // the last word of the query is a prefix if it is not followed by a separator
+            let is_prefix = i == words.len() - 1 && token.byte_end == self.query.len();
+            let is_known = if is_prefix {
+                let mut stream = words_fst.search(Str::new(word).starts_with()).into_stream();
+                stream.next().is_some()
+            } else {
+                words_fst.contains(word)
+            };
+            if is_known || word.chars().count() < MIN_WORD_LEN_TO_CORRECT {
+                continue;
+            }
+
+            if let Some(correction) = self.best_correction(&words_fst, word)? {
+                corrected.push_str(&self.query[last_byte_end..token.byte_start]);
+                corrected.push_str(&correction);
+                last_byte_end = token.byte_end;
+                any_correction = true;
+            }
+        }
+        corrected.push_str(&self.query[last_byte_end..]);
+
+        Ok(any_correction.then_some(corrected))
+    }
+
+    /// Returns the word of the index with the fewest typos compared to the given word,
+    /// and then with the highest number of documents.
+    ///
+    /// As with the typo tolerance, a typo on the first letter counts as two typos.
+    fn best_correction(
+        &self,
+        words_fst: &fst::Set<impl AsRef<[u8]>>,
+        word: &str,
+    ) -> Result<Option<String>> {
+        let starts = StartsWith(Str::new(get_first(word)));
+        let first = Intersection(build_dfa(word, 1, false), Complement(&starts));
+        let second_dfa = build_dfa(word, 2, false);
+        let second = Intersection(&second_dfa, &starts);
+        let automaton = Union(first, &second);
+
+        let mut best: Option<((u8, Reverse<u64>), String)> = None;
+        let mut stream = words_fst.search_with_state(automaton).into_stream();
+        while let Some((derived_word, state)) = stream.next() {
+            let derived_word = std::str::from_utf8(derived_word)?;
+            let typos = if get_first(derived_word) != get_first(word) {
+                2
+            } else {
+                second_dfa.distance((state.1).0).to_u8()
+            };
+            let count = self.index.word_documents_count(self.rtxn, derived_word)?.unwrap_or(0);
+            let key = (typos, Reverse(count));
+            if best.as_ref().map_or(true, |(best_key, _)| key < *best_key) {
+                best = Some((key, derived_word.to_string()));
+            }
+        }
+
+        Ok(best.map(|(_, word)| word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn did_you_mean() {
+        let index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 0, "text": "red shoes" },
+                { "id": 1, "text": "blue shoes" },
+                { "id": 2, "text": "running shoes" },
+                { "id": 3, "text": "tv shows" },
+                { "id": 4, "text": "blue boots" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let did_you_mean = |query| DidYouMean::new(query, &rtxn, &index).execute().unwrap();
+
+        // the most frequent of the closest words is suggested
+        assert_eq!(did_you_mean("Red Shoos"), Some("Red shoes".to_string()));
+        assert_eq!(did_you_mean("tv shwos"), Some("tv shows".to_string()));
+        assert_eq!(did_you_mean("bleu botos!"), Some("blue boots!".to_string()));
+        // the last word is a prefix
+        assert_eq!(did_you_mean("red sho"), None);
+        assert_eq!(did_you_mean("red shoes"), None);
+        // too short or too far from any word
+        assert_eq!(did_you_mean("xy shoes"), None);
+        assert_eq!(did_you_mean("sandals"), None);
+    }
+}
@@ -1,24 +1,28 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Display;
-use std::ops::ControlFlow;
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
+use either::Either;
 use heed::types::Bytes;
 use heed::BytesDecode;
 use indexmap::IndexMap;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::error::UserError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
-use crate::{FieldId, Index, Result};
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::{DocumentId, FieldId, Index, Result};
 
 /// The default number of values by facets that will
 /// be fetched from the key-value store.
@@ -47,6 +51,53 @@ impl Display for OrderBy {
     }
 }
 
+/// A range of numeric facet values, the lower bound being included and the upper one excluded.
+///
+/// A missing bound means that the range is unbounded on this side.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+/// The number of documents having a facet value within a range.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+pub struct FacetRangeCount {
+    /// The lower bound of the range, included
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    /// The upper bound of the range, excluded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    /// The number of documents having a value within the range
+    pub count: u64,
+}
+
+/// Statistics on the numeric values of a facet over the candidates.
+///
+/// A document with several values is accounted once for each of its values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetNumberStats {
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    /// The number of values, to compute the average
+    pub count: u64,
+    /// The number of distinct values
+    pub distinct_count: u64,
+    /// The requested percentiles, along with the value at each of them
+    pub percentiles: Vec<(f64, f64)>,
+}
+
+/// The candidates sharing a value of a facet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetGroup {
+    /// The number of candidates having the value
+    pub count: u64,
+    /// Statistics on the numeric values of the metric facets over these candidates
+    pub stats: BTreeMap<String, FacetNumberStats>,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
@@ -279,15 +330,9 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
-    pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
-        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+    /// Returns the requested facets, or all the filterable ones if none were requested.
+    fn stats_fields(&self) -> Result<HashSet<String>> {
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
-        let candidates = if let Some(candidates) = self.candidates.clone() {
-            candidates
-        } else {
-            return Ok(Default::default());
-        };
-
         let fields = match &self.facets {
             Some(facets) => {
                 let invalid_fields: HashSet<_> = facets
@@ -308,6 +353,18 @@ impl<'a> FacetDistribution<'a> {
             None => filterable_fields,
         };
 
+        Ok(fields)
+    }
+
+    pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = if let Some(candidates) = self.candidates.clone() {
+            candidates
+        } else {
+            return Ok(Default::default());
+        };
+        let fields = self.stats_fields()?;
+
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
@@ -339,6 +396,510 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Returns the number of candidates having each numeric value of the field, by ascending value.
+    fn facet_number_counts(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+    ) -> heed::Result<Vec<(f64, u64)>> {
+        let mut counts: Vec<(f64, u64)> = Vec::new();
+        if candidates.len() <= CANDIDATES_THRESHOLD {
+            let mut values = Vec::new();
+            let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
+            for docid in candidates {
+                key_buffer.truncate(mem::size_of::<FieldId>());
+                key_buffer.extend_from_slice(&docid.to_be_bytes());
+                let iter = self
+                    .index
+                    .field_id_docid_facet_f64s
+                    .remap_key_type::<Bytes>()
+                    .prefix_iter(self.rtxn, &key_buffer)?
+                    .remap_key_type::<FieldDocIdFacetF64Codec>();
+                for result in iter {
+                    let ((_, _, value), ()) = result?;
+                    values.push(value);
+                }
+            }
+
+            values.sort_unstable_by(f64::total_cmp);
+            for value in values {
+                match counts.last_mut() {
+                    Some((last, count)) if *last == value => *count += 1,
+                    _ => counts.push((value, 1)),
+                }
+            }
+        } else {
+            let mut level0_prefix = field_id.to_be_bytes().to_vec();
+            level0_prefix.push(0);
+            let iter = self
+                .index
+                .facet_id_f64_docids
+                .remap_key_type::<Bytes>()
+                .prefix_iter(self.rtxn, &level0_prefix)?
+                .remap_key_type::<FacetGroupKeyCodec<OrderedF64Codec>>();
+            for result in iter {
+                let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+                let count = bitmap.intersection_len(candidates);
+                if count != 0 {
+                    counts.push((left_bound, count));
+                }
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Computes statistics on the numeric values of the candidates for each requested facet,
+    /// including the values at the given percentiles, between 0 and 100.
+    ///
+    /// The percentiles are computed with the nearest-rank method. The facets without numeric
+    /// values are ignored.
+    pub fn compute_number_stats(
+        &self,
+        percentiles: &[f64],
+    ) -> Result<BTreeMap<String, FacetNumberStats>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = if let Some(candidates) = self.candidates.clone() {
+            candidates
+        } else {
+            return Ok(Default::default());
+        };
+        let fields = self.stats_fields()?;
+
+        let mut distribution = BTreeMap::new();
+        for (fid, name) in fields_ids_map.iter() {
+            if !crate::is_faceted(name, &fields) {
+                continue;
+            }
+            let counts = self.facet_number_counts(fid, &candidates)?;
+            let (Some(&(min, _)), Some(&(max, _))) = (counts.first(), counts.last()) else {
+                continue;
+            };
+
+            let count: u64 = counts.iter().map(|(_, count)| count).sum();
+            let sum: f64 = counts.iter().map(|(value, count)| value * *count as f64).sum();
+            let percentiles = percentiles
+                .iter()
+                .map(|&percentile| {
+                    let rank = ((percentile / 100.0 * count as f64).ceil() as u64).clamp(1, count);
+                    let mut seen = 0;
+                    let value = counts
+                        .iter()
+                        .find(|(_, count)| {
+                            seen += count;
+                            seen >= rank
+                        })
+                        .map_or(max, |(value, _)| *value);
+                    (percentile, value)
+                })
+                .collect();
+
+            let stats = FacetNumberStats {
+                min,
+                max,
+                sum,
+                count,
+                distinct_count: counts.len() as u64,
+                percentiles,
+            };
+            distribution.insert(name.to_string(), stats);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns an error built from the requested facets that are not filterable, if any.
+    fn check_filterable<'n>(
+        &self,
+        facets: impl IntoIterator<Item = &'n String>,
+        error: impl FnOnce(BTreeSet<String>, BTreeSet<String>) -> UserError,
+    ) -> Result<()> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let invalid_fields: BTreeSet<_> = facets
+            .into_iter()
+            .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+            .cloned()
+            .collect();
+        if invalid_fields.is_empty() {
+            Ok(())
+        } else {
+            Err(error(invalid_fields, filterable_fields.into_iter().collect()).into())
+        }
+    }
+
+    /// Returns the number of candidates having a numeric value of the field within the range.
+    fn facet_range_count(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+        FacetRange { from, to }: FacetRange,
+    ) -> Result<u64> {
+        let left = from.map_or(Bound::Unbounded, Bound::Included);
+        let right = to.map_or(Bound::Unbounded, Bound::Excluded);
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Ok(0);
+            }
+        }
+
+        let mut docids = RoaringBitmap::new();
+        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            self.rtxn,
+            self.index.facet_id_f64_docids,
+            field_id,
+            &left,
+            &right,
+            Some(candidates),
+            &mut docids,
+        )?;
+        Ok(docids.intersection_len(candidates))
+    }
+
+    /// Counts, for each of the given numeric facets, the candidates having a value within each range.
+    ///
+    /// A document with several values within a range is only counted once for this range.
+    pub fn compute_ranges(
+        &self,
+        ranges: &BTreeMap<String, Vec<FacetRange>>,
+    ) -> Result<BTreeMap<String, Vec<FacetRangeCount>>> {
+        self.check_filterable(ranges.keys(), |invalid_facets_name, valid_facets_name| {
+            UserError::InvalidFacetRanges { invalid_facets_name, valid_facets_name }
+        })?;
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, ranges) in ranges {
+            let field_id = fields_ids_map.id(name);
+            let counts = ranges
+                .iter()
+                .map(|&range| {
+                    let count = match field_id {
+                        Some(field_id) => self.facet_range_count(field_id, candidates, range)?,
+                        None => 0,
+                    };
+                    Ok(FacetRangeCount { from: range.from, to: range.to, count })
+                })
+                .collect::<Result<_>>()?;
+            distribution.insert(name.clone(), counts);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Splits the numeric values of each of the given facets into buckets of a fixed interval,
+    /// aligned on multiples of the interval, and counts the candidates within each bucket.
+    ///
+    /// The buckets go from the one of the smallest value of the candidates to the one of the largest,
+    /// empty buckets included, and are truncated to the maximum number of values per facet.
+    /// The facets with an interval that is not a strictly positive number are ignored.
+    pub fn compute_histograms(
+        &self,
+        intervals: &BTreeMap<String, f64>,
+    ) -> Result<BTreeMap<String, Vec<FacetRangeCount>>> {
+        self.check_filterable(intervals.keys(), |invalid_facets_name, valid_facets_name| {
+            UserError::InvalidFacetHistograms { invalid_facets_name, valid_facets_name }
+        })?;
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd.clone(),
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, &interval) in intervals {
+            if !(interval.is_finite() && interval > 0.0) {
+                continue;
+            }
+            let mut buckets = Vec::new();
+            if let Some(field_id) = fields_ids_map.id(name) {
+                let index = self.index;
+                let min = super::facet_min_value(index, self.rtxn, field_id, candidates.clone())?;
+                let max = super::facet_max_value(index, self.rtxn, field_id, candidates.clone())?;
+                if let (Some(min), Some(max)) = (min, max) {
+                    // the rounding errors must not leave the extreme values out of the buckets
+                    let mut first = (min / interval).floor();
+                    if histogram_bound(first, interval) > min {
+                        first -= 1.0;
+                    }
+                    let mut last = (max / interval).floor();
+                    if histogram_bound(last + 1.0, interval) <= max {
+                        last += 1.0;
+                    }
+                    let len = (last - first + 1.0).min(self.max_values_per_facet as f64) as usize;
+                    // each bucket starts where the previous one ends, without gaps or overlaps
+                    let mut from = histogram_bound(first, interval);
+                    for i in 0..len {
+                        let to = histogram_bound(first + i as f64 + 1.0, interval);
+                        let range = FacetRange { from: Some(from), to: Some(to) };
+                        let count = self.facet_range_count(field_id, &candidates, range)?;
+                        buckets.push(FacetRangeCount { from: range.from, to: range.to, count });
+                        from = to;
+                    }
+                }
+            }
+            distribution.insert(name.clone(), buckets);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the candidates count under each direct child of the node of a hierarchical facet,
+    /// the children being identified by their full path.
+    fn facet_children(
+        &self,
+        field_id: FieldId,
+        node: &str,
+        separator: &str,
+        candidates: &RoaringBitmap,
+        order_by: OrderBy,
+    ) -> Result<IndexMap<String, u64>> {
+        // the values of an exact facet are not normalized
+        let exact = self.index.exact_facets_ids(self.rtxn)?.contains(&field_id);
+        let normalized_separator = match exact {
+            true => separator.to_string(),
+            false => crate::normalize_facet_separator(separator),
+        };
+        let prefix = match crate::normalize_facet_unless_exact(node, exact) {
+            root if root.is_empty() => root,
+            node => node + &normalized_separator,
+        };
+        // the number of path segments of the children
+        let depth = prefix.matches(normalized_separator.as_str()).count() + 1;
+
+        // the docids of each child, along with any of its values to retrieve its original path
+        let mut children: BTreeMap<String, (RoaringBitmap, String, DocumentId)> = BTreeMap::new();
+        let base = FacetGroupKey { field_id, level: 0, left_bound: prefix.as_str() };
+        for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &base)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let bitmap = bitmap & candidates;
+            let Some(docid) = bitmap.min() else { continue };
+            let rest = &left_bound[prefix.len()..];
+            let segment = rest.split(normalized_separator.as_str()).next().unwrap_or(rest);
+            let (docids, _, _) = children
+                .entry(format!("{prefix}{segment}"))
+                .or_insert_with(|| (RoaringBitmap::new(), left_bound.to_string(), docid));
+            *docids |= bitmap;
+        }
+
+        let mut distribution = Vec::with_capacity(children.len());
+        for (docids, value, docid) in children.into_values() {
+            let key: (FieldId, _, &str) = (field_id, docid, value.as_str());
+            let original = self.index.field_id_docid_facet_strings.get(self.rtxn, &key)?;
+            let original = original.unwrap_or(value.as_str());
+            let path: Vec<_> = original.split(separator).take(depth).collect();
+            distribution.push((path.join(separator), docids.len()));
+        }
+        if order_by == OrderBy::Count {
+            distribution.sort_by(|(_, left), (_, right)| right.cmp(left));
+        }
+
+        Ok(distribution.into_iter().take(self.max_values_per_facet).collect())
+    }
+
+    /// Counts, for each of the given hierarchical facets, the candidates under each direct child
+    /// of the selected node. An empty node selects the roots of the hierarchy.
+    ///
+    /// A document is counted under a child when one of its values is this child or a descendant of it.
+    pub fn compute_children(
+        &self,
+        nodes: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
+        self.check_filterable(nodes.keys(), |invalid_facets_name, valid_facets_name| {
+            UserError::InvalidFacetChildren { invalid_facets_name, valid_facets_name }
+        })?;
+
+        let hierarchical_facets = self.index.hierarchical_facets(self.rtxn)?;
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd.clone(),
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, node) in nodes {
+            let Some(separator) = hierarchical_facets.get(name) else {
+                return Err(UserError::NotHierarchicalFacet { attribute: name.clone() }.into());
+            };
+            let children = match fields_ids_map.id(name) {
+                Some(field_id) => {
+                    let order_by = self
+                        .facets
+                        .as_ref()
+                        .and_then(|facets| facets.get(name).copied())
+                        .unwrap_or(self.default_order_by);
+                    self.facet_children(field_id, node, separator, &candidates, order_by)?
+                }
+                None => IndexMap::new(),
+            };
+            distribution.insert(name.clone(), children);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the candidates having each value of the field, numbers first then strings,
+    /// each value being identified like in the facet distribution.
+    ///
+    /// Only the first `max_values_per_facet` values are returned, in lexicographic order or by
+    /// decreasing count, and the candidates of the other values are never kept.
+    fn facet_value_docids(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+        order_by: OrderBy,
+    ) -> Result<Vec<(String, RoaringBitmap)>> {
+        let mut level0_prefix = field_id.to_be_bytes().to_vec();
+        level0_prefix.push(0);
+        let numbers = self
+            .index
+            .facet_id_f64_docids
+            .remap_key_type::<Bytes>()
+            .prefix_iter(self.rtxn, &level0_prefix)?
+            .remap_key_type::<FacetGroupKeyCodec<OrderedF64Codec>>();
+        let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
+        let strings = self.index.facet_id_string_docids.prefix_iter(self.rtxn, &base)?;
+
+        // The value of a string is the original string of the first candidate having it.
+        let original_string = |normalized: &str, bitmap: &RoaringBitmap| -> Result<String> {
+            let docid = bitmap.min().unwrap_or_default();
+            let key: (FieldId, _, &str) = (field_id, docid, normalized);
+            let original = self.index.field_id_docid_facet_strings.get(self.rtxn, &key)?;
+            Ok(original.unwrap_or(normalized).to_string())
+        };
+
+        let mut values = Vec::new();
+        match order_by {
+            OrderBy::Lexicographic => {
+                for result in numbers {
+                    if values.len() == self.max_values_per_facet {
+                        return Ok(values);
+                    }
+                    let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
+                        result?;
+                    let bitmap = bitmap & candidates;
+                    if !bitmap.is_empty() {
+                        values.push((left_bound.to_string(), bitmap));
+                    }
+                }
+                for result in strings {
+                    if values.len() == self.max_values_per_facet {
+                        return Ok(values);
+                    }
+                    let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
+                        result?;
+                    let bitmap = bitmap & candidates;
+                    if !bitmap.is_empty() {
+                        values.push((original_string(left_bound, &bitmap)?, bitmap));
+                    }
+                }
+            }
+            OrderBy::Count => {
+                // only the counts are kept to find the most frequent values
+                let mut counts = Vec::new();
+                for result in numbers {
+                    let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
+                        result?;
+                    let count = bitmap.intersection_len(candidates);
+                    if count != 0 {
+                        counts.push((count, Either::Left(left_bound)));
+                    }
+                }
+                for result in strings {
+                    let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
+                        result?;
+                    let count = bitmap.intersection_len(candidates);
+                    if count != 0 {
+                        counts.push((count, Either::Right(left_bound.to_string())));
+                    }
+                }
+                counts.sort_by(|(left, _), (right, _)| right.cmp(left));
+                counts.truncate(self.max_values_per_facet);
+
+                for (_, value) in counts {
+                    match value {
+                        Either::Left(number) => {
+                            let key = FacetGroupKey { field_id, level: 0, left_bound: number };
+                            let group = self.index.facet_id_f64_docids.get(self.rtxn, &key)?;
+                            let bitmap = group.map_or_else(RoaringBitmap::new, |g| g.bitmap);
+                            values.push((number.to_string(), bitmap & candidates));
+                        }
+                        Either::Right(string) => {
+                            let key =
+                                FacetGroupKey { field_id, level: 0, left_bound: string.as_str() };
+                            let group = self.index.facet_id_string_docids.get(self.rtxn, &key)?;
+                            let bitmap = group.map_or_else(RoaringBitmap::new, |g| g.bitmap);
+                            let bitmap = bitmap & candidates;
+                            values.push((original_string(&string, &bitmap)?, bitmap));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Groups the candidates by the values of each of the given facets and computes, for each
+    /// group, statistics on the numeric values of the associated metric facets.
+    ///
+    /// The groups are ordered like the facet distribution and truncated to the maximum number
+    /// of values per facet.
+    pub fn compute_groups(
+        &self,
+        groups: &BTreeMap<String, Vec<String>>,
+    ) -> Result<BTreeMap<String, IndexMap<String, FacetGroup>>> {
+        let facets = groups.keys().chain(groups.values().flatten());
+        self.check_filterable(facets, |invalid_facets_name, valid_facets_name| {
+            UserError::InvalidFacetGroups { invalid_facets_name, valid_facets_name }
+        })?;
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd.clone(),
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, metrics) in groups {
+            let order_by = self
+                .facets
+                .as_ref()
+                .and_then(|facets| facets.get(name).copied())
+                .unwrap_or(self.default_order_by);
+            let values = match fields_ids_map.id(name) {
+                Some(field_id) => self.facet_value_docids(field_id, &candidates, order_by)?,
+                None => Vec::new(),
+            };
+
+            let mut facet_groups = IndexMap::new();
+            for (value, docids) in values {
+                let count = docids.len();
+                let stats = if metrics.is_empty() {
+                    BTreeMap::new()
+                } else {
+                    FacetDistribution::new(self.rtxn, self.index)
+                        .facets(metrics.iter().map(|metric| (metric, OrderBy::default())))
+                        .candidates(docids)
+                        .compute_number_stats(&[])?
+                };
+                facet_groups.insert(value, FacetGroup { count, stats });
+            }
+            distribution.insert(name.clone(), facet_groups);
+        }
+
+        Ok(distribution)
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
@@ -380,6 +941,19 @@ impl<'a> FacetDistribution<'a> {
     }
 }
 
+/// Returns the bound of the histogram buckets at the given number of intervals from zero.
+///
+/// A fractional interval is usually the inverse of an integer, e.g. `0.1`, dividing by this
+/// integer gives the closest float to the decimal bound, e.g. `0.3` and not `0.30000000000000004`.
+fn histogram_bound(intervals: f64, interval: f64) -> f64 {
+    let inverse = (1.0 / interval).round();
+    if interval < 1.0 && 1.0 / inverse == interval {
+        intervals / inverse
+    } else {
+        intervals * interval
+    }
+}
+
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FacetDistribution {
@@ -402,14 +976,16 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::iter;
 
     use big_s::S;
-    use maplit::hashset;
+    use indexmap::IndexMap;
+    use maplit::{btreemap, hashset};
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::{milli_snap, FacetDistribution, FacetGroup, FacetRange, FacetRangeCount, OrderBy};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -680,6 +1256,76 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_number_stats() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("price") }))
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..4000 {
+            let document = serde_json::json!({
+                "id": i,
+                "price": i % 100,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .compute_number_stats(&[50.0])
+            .unwrap();
+        assert!(map.is_empty());
+
+        // more candidates than the threshold, the level 0 of the facet database is used
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .candidates((0..4000).collect())
+            .compute_number_stats(&[1.0, 50.0, 99.0])
+            .unwrap();
+        let stats = &map["price"];
+        assert_eq!((stats.min, stats.max), (0.0, 99.0));
+        assert_eq!(stats.sum, 198000.0);
+        assert_eq!(stats.count, 4000);
+        assert_eq!(stats.distinct_count, 100);
+        assert_eq!(stats.percentiles, vec![(1.0, 0.0), (50.0, 49.0), (99.0, 98.0)]);
+
+        // fewer candidates than the threshold, the values of each document are used
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .candidates((0..400).collect())
+            .compute_number_stats(&[1.0, 50.0, 99.0])
+            .unwrap();
+        let stats = &map["price"];
+        assert_eq!((stats.min, stats.max), (0.0, 99.0));
+        assert_eq!(stats.sum, 19800.0);
+        assert_eq!(stats.count, 400);
+        assert_eq!(stats.distinct_count, 100);
+        assert_eq!(stats.percentiles, vec![(1.0, 0.0), (50.0, 49.0), (99.0, 98.0)]);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .candidates((0..10).collect())
+            .compute_number_stats(&[0.0, 50.0, 99.0])
+            .unwrap();
+        let stats = &map["price"];
+        assert_eq!((stats.min, stats.max), (0.0, 9.0));
+        assert_eq!(stats.sum, 45.0);
+        assert_eq!(stats.count, 10);
+        assert_eq!(stats.distinct_count, 10);
+        assert_eq!(stats.percentiles, vec![(0.0, 0.0), (50.0, 4.0), (99.0, 9.0)]);
+    }
+
     #[test]
     fn facet_stats_array() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -837,4 +1483,258 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn facet_ranges_and_histograms() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("price"), S("colour") })
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({
+                "id": i,
+                "price": i,
+                "colour": if i % 2 == 0 { "red" } else { "blue" },
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let format = |map: BTreeMap<String, Vec<FacetRangeCount>>| {
+            map.into_iter()
+                .map(|(name, counts)| {
+                    let counts: Vec<_> = counts
+                        .into_iter()
+                        .map(|FacetRangeCount { from, to, count }| {
+                            format!("{from:?}..{to:?}: {count}")
+                        })
+                        .collect();
+                    format!("{name}: {}", counts.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let ranges: BTreeMap<_, _> = [
+            (
+                S("price"),
+                vec![
+                    FacetRange { from: None, to: Some(100.) },
+                    FacetRange { from: Some(100.), to: Some(250.) },
+                    FacetRange { from: Some(900.), to: None },
+                ],
+            ),
+            // the string values are ignored
+            (S("colour"), vec![FacetRange::default()]),
+        ]
+        .into();
+
+        let map = FacetDistribution::new(&txn, &index).compute_ranges(&ranges).unwrap();
+        milli_snap!(format(map), "no_candidates", @r###"
+        colour: None..None: 0
+        price: None..Some(100.0): 100, Some(100.0)..Some(250.0): 150, Some(900.0)..None: 100
+        "###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((50..150).collect())
+            .compute_ranges(&ranges)
+            .unwrap();
+        milli_snap!(format(map), "candidates_50_150", @r###"
+        colour: None..None: 0
+        price: None..Some(100.0): 50, Some(100.0)..Some(250.0): 50, Some(900.0)..None: 0
+        "###);
+
+        let histograms: BTreeMap<_, _> = [(S("price"), 250.)].into();
+
+        let map = FacetDistribution::new(&txn, &index).compute_histograms(&histograms).unwrap();
+        milli_snap!(format(map), "histogram_no_candidates", @"price: Some(0.0)..Some(250.0): 250, Some(250.0)..Some(500.0): 250, Some(500.0)..Some(750.0): 250, Some(750.0)..Some(1000.0): 250");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .compute_histograms(&histograms)
+            .unwrap();
+        milli_snap!(format(map), "histogram_candidates_217_777", @"price: Some(0.0)..Some(250.0): 33, Some(250.0)..Some(500.0): 250, Some(500.0)..Some(750.0): 250, Some(750.0)..Some(1000.0): 27");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .max_values_per_facet(2)
+            .compute_histograms(&histograms)
+            .unwrap();
+        milli_snap!(format(map), "histogram_max_values", @"price: Some(0.0)..Some(250.0): 33, Some(250.0)..Some(500.0): 250");
+
+        let histograms: BTreeMap<_, _> = [(S("id"), 10.)].into();
+        let error =
+            FacetDistribution::new(&txn, &index).compute_histograms(&histograms).unwrap_err();
+        milli_snap!(error.to_string(), @"Invalid facet histograms, attribute `id` is not filterable. The available filterable attributes are `colour, price`.");
+    }
+
+    #[test]
+    fn facet_histograms_fractional_interval() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("rating") }))
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "rating": 0.1 },
+                { "id": 1, "rating": 0.2 },
+                { "id": 2, "rating": 0.3 },
+                { "id": 3, "rating": 0.3 },
+                { "id": 4, "rating": 0.7 },
+                { "id": 5, "rating": 1.0 },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let histograms: BTreeMap<_, _> = [(S("rating"), 0.1)].into();
+        let map = FacetDistribution::new(&txn, &index).compute_histograms(&histograms).unwrap();
+        let buckets = &map["rating"];
+
+        // the buckets follow each other and every document is counted once
+        assert!(buckets.windows(2).all(|pair| pair[0].to == pair[1].from));
+        assert_eq!(buckets.iter().map(|bucket| bucket.count).sum::<u64>(), 6);
+
+        let buckets: Vec<_> = buckets
+            .iter()
+            .map(|FacetRangeCount { from, to, count }| format!("{from:?}..{to:?}: {count}"))
+            .collect();
+        milli_snap!(buckets.join(", "), @"Some(0.1)..Some(0.2): 1, Some(0.2)..Some(0.3): 1, Some(0.3)..Some(0.4): 2, Some(0.4)..Some(0.5): 0, Some(0.5)..Some(0.6): 0, Some(0.6)..Some(0.7): 0, Some(0.7)..Some(0.8): 1, Some(0.8)..Some(0.9): 0, Some(0.9)..Some(1.0): 0, Some(1.0)..Some(1.1): 1");
+    }
+
+    #[test]
+    fn facet_children() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("category"), S("brand") });
+                settings.set_hierarchical_facets(btreemap! { S("category") => S(" > ") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "category": "Electronics > Audio > Headphones" },
+                { "id": 1, "category": "Electronics > Audio > Speakers" },
+                { "id": 2, "category": ["Electronics > Audio", "Home > Kitchen"] },
+                { "id": 3, "category": "Electronics > TV" },
+                { "id": 4, "category": "Home", "brand": "Acme" },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let nodes = btreemap! { S("category") => S("") };
+        let map = FacetDistribution::new(&txn, &index).compute_children(&nodes).unwrap();
+        milli_snap!(format!("{map:?}"), "roots", @r###"{"category": {"Electronics": 4, "Home": 2}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates([0, 3, 4].into_iter().collect())
+            .compute_children(&nodes)
+            .unwrap();
+        milli_snap!(format!("{map:?}"), "roots_candidates", @r###"{"category": {"Electronics": 2, "Home": 1}}"###);
+
+        let nodes = btreemap! { S("category") => S("Electronics") };
+        let map = FacetDistribution::new(&txn, &index).compute_children(&nodes).unwrap();
+        milli_snap!(format!("{map:?}"), "electronics", @r###"{"category": {"Electronics > Audio": 3, "Electronics > TV": 1}}"###);
+
+        let nodes = btreemap! { S("category") => S("electronics > audio") };
+        let map = FacetDistribution::new(&txn, &index).compute_children(&nodes).unwrap();
+        milli_snap!(format!("{map:?}"), "audio", @r###"{"category": {"Electronics > Audio > Headphones": 1, "Electronics > Audio > Speakers": 1}}"###);
+
+        let nodes = btreemap! { S("brand") => S("") };
+        let error = FacetDistribution::new(&txn, &index).compute_children(&nodes).unwrap_err();
+        milli_snap!(error.to_string(), @"Attribute `brand` is not a hierarchical facet. Declare it with its separator in the `faceting.hierarchicalFacets` index setting.");
+    }
+
+    #[test]
+    fn facet_groups() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("brand"), S("rating"), S("price") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "Acme", "rating": 4, "price": 10 },
+                { "id": 1, "brand": "acme", "rating": 2, "price": 30 },
+                { "id": 2, "brand": "Zorg", "rating": 5 },
+                { "id": 3, "brand": ["Zorg", "Globex"], "rating": 3, "price": 20 },
+                { "id": 4, "brand": "Zorg", "price": 50 },
+                { "id": 5, "brand": 7, "rating": 1 },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let format = |map: BTreeMap<String, IndexMap<String, FacetGroup>>| {
+            map.into_iter()
+                .flat_map(|(name, groups)| {
+                    groups.into_iter().map(move |(value, FacetGroup { count, stats })| {
+                        let stats: Vec<_> = stats
+                            .into_iter()
+                            .map(|(metric, stats)| {
+                                format!(
+                                    "{metric}: {}..{} sum {} count {}",
+                                    stats.min, stats.max, stats.sum, stats.count
+                                )
+                            })
+                            .collect();
+                        format!("{name} {value} ({count}): {}", stats.join(", "))
+                    })
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let groups = btreemap! { S("brand") => vec![S("rating"), S("price")] };
+        let map = FacetDistribution::new(&txn, &index).compute_groups(&groups).unwrap();
+        milli_snap!(format(map), "all", @r###"
+        brand 7 (1): rating: 1..1 sum 1 count 1
+        brand Acme (2): price: 10..30 sum 40 count 2, rating: 2..4 sum 6 count 2
+        brand Globex (1): price: 20..20 sum 20 count 1, rating: 3..3 sum 3 count 1
+        brand Zorg (3): price: 20..50 sum 70 count 2, rating: 3..5 sum 8 count 2
+        "###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("brand", OrderBy::Count)))
+            .candidates([1, 2, 3, 4].into_iter().collect())
+            .max_values_per_facet(2)
+            .compute_groups(&groups)
+            .unwrap();
+        milli_snap!(format(map), "count_candidates", @r###"
+        brand Zorg (3): price: 20..50 sum 70 count 2, rating: 3..5 sum 8 count 2
+        brand acme (1): price: 30..30 sum 30 count 1, rating: 2..2 sum 2 count 1
+        "###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .max_values_per_facet(2)
+            .compute_groups(&groups)
+            .unwrap();
+        milli_snap!(format(map), "lexicographic_truncated", @r###"
+        brand 7 (1): rating: 1..1 sum 1 count 1
+        brand Acme (2): price: 10..30 sum 40 count 2, rating: 2..4 sum 6 count 2
+        "###);
+
+        let groups = btreemap! { S("brand") => vec![S("id")] };
+        let error = FacetDistribution::new(&txn, &index).compute_groups(&groups).unwrap_err();
+        milli_snap!(error.to_string(), @"Invalid facet groups, attribute `id` is not filterable. The available filterable attributes are `brand, price, rating`.");
+    }
 }
@@ -1,28 +1,34 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Display};
-use std::ops::Bound::{self, Excluded, Included};
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+use std::ops::RangeBounds;
 
 use either::Either;
-pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
-use heed::types::LazyDecode;
+use filter_parser::ErrorKind;
+pub use filter_parser::{Comparison, Condition, Error as FPError, FilterCondition, Token};
+use heed::types::{Bytes, LazyDecode};
 use memchr::memmem::Finder;
 use roaring::{MultiOps, RoaringBitmap};
 use serde_json::Value;
 
-use super::facet_range_search;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use super::{facet_range_search, filter_object};
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::{Error, UserError};
 use crate::heed_codec::facet::{
-    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec,
+    FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
+use crate::heed_codec::{ElementFacet, FieldIdElementFacetCodec, FieldIdSizeCodec, StrRefCodec};
 use crate::index::db_name::FACET_ID_STRING_DOCIDS;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, FieldId, Index, InternalError, Result,
-    SerializationError,
+    distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap, GeoPolygon, Index,
+    InternalError, Result, SerializationError,
 };
 
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
+/// The maximum number of documents whose fields are read to compare a field with another one.
+const MAX_COMPARED_DOCUMENTS: u64 = 100_000;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Filter<'a> {
@@ -61,8 +67,13 @@ impl Display for BadGeoError {
 #[derive(Debug)]
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
+    AttributeNotHierarchical { attribute: String },
+    AttributeNotNested { attribute: String },
+    NestedFilterNotSupported,
     ParseGeoError(BadGeoError),
+    SizeNotComparable,
     TooDeep,
+    TooManyComparedDocuments,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
 
@@ -93,11 +104,32 @@ impl<'a> Display for FilterError<'a> {
                     write!(f, ".")
                 }
             }
+            Self::AttributeNotHierarchical { attribute } => write!(
+                f,
+                "Attribute `{attribute}` is not a hierarchical facet and thus, cannot be used with the `UNDER` operator. Declare it with its separator in the `faceting.hierarchicalFacets` index setting."
+            ),
+            Self::AttributeNotNested { attribute } => write!(
+                f,
+                "Attribute `{attribute}` is not a nested attribute and thus, cannot be filtered element by element. Declare it in the `nestedAttributes` index setting."
+            ),
+            Self::NestedFilterNotSupported => write!(
+                f,
+                "A nested filter only supports the `=`, `!=`, `>`, `>=`, `<`, `<=`, `TO`, `EXISTS`, `IN`, `ALL`, `NOT`, `AND` and `OR` operators."
+            ),
+            Self::SizeNotComparable => write!(
+                f,
+                "The `SIZE` filter only supports the `=`, `!=`, `>`, `>=`, `<`, `<=` and `TO` operators."
+            ),
             Self::TooDeep => write!(
                 f,
                 "Too many filter conditions, can't process more than {} filters.",
                 MAX_FILTER_DEPTH
             ),
+            Self::TooManyComparedDocuments => write!(
+                f,
+                "Comparing two fields is limited to {} documents. Combine the comparison with other filters using `AND` to reduce the number of documents compared.",
+                MAX_COMPARED_DOCUMENTS
+            ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
         }
     }
@@ -123,8 +155,9 @@ impl<'a> Filter<'a> {
                 Ok(condition)
             }
             Value::Array(arr) => Self::parse_filter_array(arr),
+            Value::Object(_) => Self::from_object(facets),
             v => Err(Error::UserError(UserError::InvalidFilterExpression(
-                &["String", "Array"],
+                &["String", "Array", "Object"],
                 v.clone(),
             ))),
         }
@@ -134,15 +167,17 @@ impl<'a> Filter<'a> {
         let mut ands = Vec::new();
         for value in arr {
             match value {
-                Value::String(s) => ands.push(Either::Right(s.as_str())),
+                Value::String(s) => ands.push(Either::Right(Self::from_str(s)?)),
+                Value::Object(_) => ands.push(Either::Right(Self::from_object(value)?)),
                 Value::Array(arr) => {
                     let mut ors = Vec::new();
                     for value in arr {
                         match value {
-                            Value::String(s) => ors.push(s.as_str()),
+                            Value::String(s) => ors.push(Self::from_str(s)?),
+                            Value::Object(_) => ors.push(Self::from_object(value)?),
                             v => {
                                 return Err(Error::UserError(UserError::InvalidFilterExpression(
-                                    &["String"],
+                                    &["String", "Object"],
                                     v.clone(),
                                 )))
                             }
@@ -152,14 +187,14 @@ impl<'a> Filter<'a> {
                 }
                 v => {
                     return Err(Error::UserError(UserError::InvalidFilterExpression(
-                        &["String", "[String]"],
+                        &["String", "Object", "[String | Object]"],
                         v.clone(),
                     )))
                 }
             }
         }
 
-        Filter::from_array(ands)
+        Filter::from_filters(ands)
     }
 
     pub fn from_array<I, J>(array: I) -> Result<Option<Self>>
@@ -168,16 +203,34 @@ impl<'a> Filter<'a> {
         J: IntoIterator<Item = &'a str>,
     {
         let mut ands = vec![];
+        for either in array {
+            match either {
+                Either::Left(array) => {
+                    let ors = array.into_iter().map(Self::from_str).collect::<Result<Vec<_>>>()?;
+                    ands.push(Either::Left(ors));
+                }
+                Either::Right(rule) => ands.push(Either::Right(Self::from_str(rule)?)),
+            }
+        }
+
+        Self::from_filters(ands)
+    }
+
+    /// Combines the filters with an `AND`, the filters of a `Left` being combined with an `OR`.
+    ///
+    /// Empty filters are ignored.
+    pub fn from_filters<I, J>(array: I) -> Result<Option<Self>>
+    where
+        I: IntoIterator<Item = Either<J, Option<Self>>>,
+        J: IntoIterator<Item = Option<Self>>,
+    {
+        let mut ands = vec![];
 
         for either in array {
             match either {
                 Either::Left(array) => {
-                    let mut ors = vec![];
-                    for rule in array {
-                        if let Some(filter) = Self::from_str(rule)? {
-                            ors.push(filter.condition);
-                        }
-                    }
+                    let mut ors: Vec<_> =
+                        array.into_iter().flatten().map(|filter| filter.condition).collect();
 
                     match ors.len() {
                         0 => (),
@@ -185,8 +238,8 @@ impl<'a> Filter<'a> {
                         _ => ands.push(FilterCondition::Or(ors)),
                     }
                 }
-                Either::Right(rule) => {
-                    if let Some(filter) = Self::from_str(rule)? {
+                Either::Right(filter) => {
+                    if let Some(filter) = filter {
                         ands.push(filter.condition);
                     }
                 }
@@ -207,6 +260,20 @@ impl<'a> Filter<'a> {
         Ok(Some(Self { condition: and }))
     }
 
+    /// Builds a filter from its JSON object representation, e.g.
+    /// `{ "and": [{ "field": "price", "op": ">", "value": 10 }, "genres = horror"] }`.
+    pub fn from_object(object: &'a Value) -> Result<Option<Self>> {
+        let Some(condition) = filter_object::parse_filter_value(object)? else {
+            return Ok(None);
+        };
+
+        if let Some(token) = condition.token_at_depth(MAX_FILTER_DEPTH) {
+            return Err(token.as_external_error(FilterError::TooDeep).into());
+        }
+
+        Ok(Some(Self { condition }))
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(expression: &'a str) -> Result<Option<Self>> {
         let condition = match FilterCondition::parse(expression) {
@@ -225,22 +292,80 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Returns this filter without its top-level conditions that only apply to the given facet,
+    /// or `None` if no condition is left.
+    ///
+    /// It is used to compute the distribution of a facet as if this facet was not filtered,
+    /// the conditions on the other fields still applying.
+    pub fn without_facet(&self, facet: &str) -> Option<Self> {
+        fn only_on_facet(condition: &FilterCondition, facet: &str) -> bool {
+            match condition {
+                FilterCondition::Condition { fid, op: Condition::CompareField { field, .. } } => {
+                    crate::is_faceted_by(fid.value(), facet)
+                        && crate::is_faceted_by(field.value(), facet)
+                }
+                FilterCondition::Condition { fid, .. }
+                | FilterCondition::In { fid, .. }
+                | FilterCondition::All { fid, .. }
+                | FilterCondition::Size { fid, .. }
+                | FilterCondition::Nested { fid, .. } => crate::is_faceted_by(fid.value(), facet),
+                FilterCondition::Not(condition) => only_on_facet(condition, facet),
+                FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+                    conditions.iter().all(|condition| only_on_facet(condition, facet))
+                }
+                FilterCondition::GeoLowerThan { .. }
+                | FilterCondition::GeoBoundingBox { .. }
+                | FilterCondition::GeoPolygon { .. }
+                | FilterCondition::GeoContains { .. } => false,
+            }
+        }
+
+        fn retain<'a>(
+            condition: &FilterCondition<'a>,
+            facet: &str,
+            ands: &mut Vec<FilterCondition<'a>>,
+        ) {
+            match condition {
+                FilterCondition::And(conditions) => {
+                    conditions.iter().for_each(|condition| retain(condition, facet, ands))
+                }
+                condition if only_on_facet(condition, facet) => (),
+                condition => ands.push(condition.clone()),
+            }
+        }
+
+        let mut ands = Vec::new();
+        retain(&self.condition, facet, &mut ands);
+        let condition = match ands.len() {
+            0 => return None,
+            1 => ands.pop().unwrap(),
+            _ => FilterCondition::And(ands),
+        };
+        Some(Self { condition })
+    }
 }
 
 impl<'a> Filter<'a> {
     pub fn evaluate(&self, rtxn: &heed::RoTxn<'_>, index: &Index) -> Result<RoaringBitmap> {
         // to avoid doing this for each recursive call we're going to do it ONCE ahead of time
         let filterable_fields = index.filterable_fields(rtxn)?;
+        self.check_filterable_fields(&filterable_fields)?;
+        self.inner_evaluate(rtxn, index, &filterable_fields, None)
+    }
+
+    /// Returns an error if the filter uses an attribute that is not in the filterable fields.
+    pub fn check_filterable_fields(&self, filterable_fields: &HashSet<String>) -> Result<()> {
         for fid in self.condition.fids(MAX_FILTER_DEPTH) {
             let attribute = fid.value();
-            if !crate::is_faceted(attribute, &filterable_fields) {
+            if !crate::is_faceted(attribute, filterable_fields) {
                 return Err(fid.as_external_error(FilterError::AttributeNotFilterable {
                     attribute,
-                    filterable_fields,
+                    filterable_fields: filterable_fields.clone(),
                 }))?;
             }
         }
-        self.inner_evaluate(rtxn, index, &filterable_fields, None)
+        Ok(())
     }
 
     fn evaluate_operator(
@@ -252,24 +377,24 @@ impl<'a> Filter<'a> {
     ) -> Result<RoaringBitmap> {
         let numbers_db = index.facet_id_f64_docids;
         let strings_db = index.facet_id_string_docids;
+        // the strings of the exact facets are not normalized
+        // and the dates of the date attributes are indexed as numbers
+        let exact = index.exact_facets_ids(rtxn)?.contains(&field_id);
+        let date = index.date_attributes_ids(rtxn)?.contains(&field_id);
 
         // Make sure we always bound the ranges with the field id and the level,
         // as the facets values are all in the same database and prefixed by the
         // field id and the level.
 
+        let range_bound = |val: &Token<'a>| RangeBound::parse(val, exact, date);
+
         let (left, right) = match operator {
-            Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
-            }
-            Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
-            }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
-            Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
-            }
+            Condition::GreaterThan(val) => (Excluded(range_bound(val)?), Unbounded),
+            Condition::GreaterThanOrEqual(val) => (Included(range_bound(val)?), Unbounded),
+            Condition::LowerThan(val) => (Unbounded, Excluded(range_bound(val)?)),
+            Condition::LowerThanOrEqual(val) => (Unbounded, Included(range_bound(val)?)),
             Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+                (Included(range_bound(from)?), Included(range_bound(to)?))
             }
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
@@ -290,12 +415,16 @@ impl<'a> Filter<'a> {
                         &FacetGroupKey {
                             field_id,
                             level: 0,
-                            left_bound: &crate::normalize_facet(val.value()),
+                            left_bound: &crate::normalize_facet_unless_exact(val.value(), exact),
                         },
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                // only the date attributes index their dates as numbers
+                let number = match date {
+                    true => val.parse_finite_float_or_date().ok(),
+                    false => val.parse_finite_float().ok(),
+                };
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -312,7 +441,7 @@ impl<'a> Filter<'a> {
                 return Ok(all_ids - docids);
             }
             Condition::Contains { keyword: _, word } => {
-                let value = crate::normalize_facet(word.value());
+                let value = crate::normalize_facet_unless_exact(word.value(), exact);
                 let finder = Finder::new(&value);
                 let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
                 let docids = strings_db
@@ -347,7 +476,7 @@ impl<'a> Filter<'a> {
                 return Ok(docids);
             }
             Condition::StartsWith { keyword: _, word } => {
-                let value = crate::normalize_facet(word.value());
+                let value = crate::normalize_facet_unless_exact(word.value(), exact);
                 let base = FacetGroupKey { field_id, level: 0, left_bound: value.as_str() };
                 let docids = strings_db
                     .prefix_iter(rtxn, &base)?
@@ -364,21 +493,398 @@ impl<'a> Filter<'a> {
 
                 return Ok(docids);
             }
+            Condition::Under(node) => {
+                let fields_ids_map = index.fields_ids_map(rtxn)?;
+                let attribute = fields_ids_map.name(field_id).unwrap_or_default();
+                let hierarchical_facets = index.hierarchical_facets(rtxn)?;
+                let Some(separator) = hierarchical_facets.get(attribute) else {
+                    return Err(node.as_external_error(FilterError::AttributeNotHierarchical {
+                        attribute: attribute.to_string(),
+                    }))?;
+                };
+
+                // the node itself and all of its descendants
+                let node = crate::normalize_facet_unless_exact(node.value(), exact);
+                let node_docids = strings_db
+                    .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: node.as_str() })?
+                    .map(|v| v.bitmap)
+                    .unwrap_or_default();
+                let prefix = match exact {
+                    true => node + separator,
+                    false => node + &crate::normalize_facet_separator(separator),
+                };
+                let base = FacetGroupKey { field_id, level: 0, left_bound: prefix.as_str() };
+                let descendants_docids = strings_db
+                    .prefix_iter(rtxn, &base)?
+                    .map(|result| -> Result<RoaringBitmap> {
+                        match result {
+                            Ok((_facet_group_key, FacetGroupValue { bitmap, .. })) => Ok(bitmap),
+                            Err(_e) => Err(InternalError::from(SerializationError::Decoding {
+                                db_name: Some(FACET_ID_STRING_DOCIDS),
+                            })
+                            .into()),
+                        }
+                    })
+                    .union()?;
+
+                return Ok(node_docids | descendants_docids);
+            }
+            Condition::CompareField { comparison, field } => {
+                let fields_ids_map = index.fields_ids_map(rtxn)?;
+                // the field is filterable but no document contains it
+                let Some(other_field_id) = fields_ids_map.id(field.value()) else {
+                    return Ok(RoaringBitmap::new());
+                };
+                let mut candidates = index.exists_faceted_documents_ids(rtxn, field_id)?
+                    & index.exists_faceted_documents_ids(rtxn, other_field_id)?;
+                if let Some(universe) = universe {
+                    candidates &= universe;
+                }
+                // the fields are read document by document
+                if candidates.len() > MAX_COMPARED_DOCUMENTS {
+                    return Err(field.as_external_error(FilterError::TooManyComparedDocuments))?;
+                }
+
+                let mut docids = RoaringBitmap::new();
+                for docid in candidates {
+                    if Self::compare_fields(
+                        rtxn,
+                        index,
+                        docid,
+                        field_id,
+                        other_field_id,
+                        *comparison,
+                    )? {
+                        docids.insert(docid);
+                    }
+                }
+                return Ok(docids);
+            }
         };
 
         let mut output = RoaringBitmap::new();
-        Self::explore_facet_number_levels(
-            rtxn,
-            numbers_db,
-            field_id,
-            left,
-            right,
-            universe,
-            &mut output,
-        )?;
+        if let (Some(left), Some(right)) =
+            (facet_bound(&left, |bound| bound.number), facet_bound(&right, |bound| bound.number))
+        {
+            Self::explore_facet_number_levels(
+                rtxn,
+                numbers_db,
+                field_id,
+                left,
+                right,
+                universe,
+                &mut output,
+            )?;
+        }
+        if let (Some(left), Some(right)) = (
+            facet_bound(&left, |bound| bound.string.as_deref()),
+            facet_bound(&right, |bound| bound.string.as_deref()),
+        ) {
+            Self::explore_facet_string_levels(
+                rtxn,
+                strings_db,
+                field_id,
+                left,
+                right,
+                universe,
+                &mut output,
+            )?;
+        }
         Ok(output)
     }
 
+    /// Aggregates the documents ids that have a number of values in the field that matches the operator,
+    /// the documents where the field is missing are considered to have zero values.
+    fn evaluate_size(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        fid: &Token<'a>,
+        field_id: Option<FieldId>,
+        universe: Option<&RoaringBitmap>,
+        operator: &Condition<'a>,
+    ) -> Result<RoaringBitmap> {
+        let (left, right) = match operator {
+            Condition::GreaterThan(val) => {
+                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::GreaterThanOrEqual(val) => {
+                (Included(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThanOrEqual(val) => {
+                (Included(f64::MIN), Included(val.parse_finite_float()?))
+            }
+            Condition::Between { from, to } => {
+                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+            }
+            Condition::Equal(val) => {
+                let size = val.parse_finite_float()?;
+                (Included(size), Included(size))
+            }
+            Condition::NotEqual(val) => {
+                let operator = Condition::Equal(val.clone());
+                let docids = Self::evaluate_size(rtxn, index, fid, field_id, universe, &operator)?;
+                let all_ids = match universe {
+                    Some(universe) => universe.clone(),
+                    None => index.documents_ids(rtxn)?,
+                };
+                return Ok(all_ids - docids);
+            }
+            _ => return Err(fid.as_external_error(FilterError::SizeNotComparable))?,
+        };
+
+        let mut docids = RoaringBitmap::new();
+        if (left, right).contains(&0.0) {
+            let mut missing = index.documents_ids(rtxn)?;
+            if let Some(field_id) = field_id {
+                missing -= index.exists_faceted_documents_ids(rtxn, field_id)?;
+            }
+            docids |= missing;
+        }
+        if let Some(field_id) = field_id {
+            let iter = index
+                .facet_id_size_docids
+                .remap_key_type::<Bytes>()
+                .prefix_iter(rtxn, &field_id.to_be_bytes())?
+                .remap_key_type::<FieldIdSizeCodec>();
+            for result in iter {
+                let ((_, size), size_docids) = result?;
+                if (left, right).contains(&(size as f64)) {
+                    docids |= size_docids;
+                }
+            }
+        }
+        if let Some(universe) = universe {
+            docids &= universe;
+        }
+        Ok(docids)
+    }
+
+    /// Aggregates the documents ids that have an element in the nested field matching the whole filter.
+    fn evaluate_nested(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        fid: &Token<'a>,
+        filter: &FilterCondition<'a>,
+        universe: Option<&RoaringBitmap>,
+    ) -> Result<RoaringBitmap> {
+        let nested_attributes = index.nested_attributes(rtxn)?;
+        if !nested_attributes.contains(&fid.value()) {
+            return Err(fid.as_external_error(FilterError::AttributeNotNested {
+                attribute: fid.value().to_string(),
+            }))?;
+        }
+
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let Some(field_id) = fields_ids_map.id(fid.value()) else {
+            return Ok(RoaringBitmap::new());
+        };
+
+        // every element is registered as existing under the nested field id
+        let iter = index
+            .facet_id_element_docids
+            .remap_key_type::<Bytes>()
+            .prefix_iter(rtxn, &field_id.to_be_bytes())?
+            .remap_key_type::<FieldIdElementFacetCodec>();
+        let mut docids = RoaringBitmap::new();
+        for result in iter {
+            let ((_, position, facet), mut element_docids) = result?;
+            if facet != ElementFacet::Exists {
+                continue;
+            }
+            if let Some(universe) = universe {
+                element_docids &= universe;
+            }
+            if element_docids.is_empty() {
+                continue;
+            }
+            let element = Element { fid, position, docids: &element_docids };
+            docids |= Self::evaluate_element(rtxn, index, &fields_ids_map, &element, filter)?;
+        }
+        Ok(docids)
+    }
+
+    /// Aggregates the documents ids where the element at the given position matches the filter.
+    fn evaluate_element(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        fields_ids_map: &FieldsIdsMap,
+        element: &Element<'_, 'a>,
+        filter: &FilterCondition<'a>,
+    ) -> Result<RoaringBitmap> {
+        let evaluate = |filter: &FilterCondition<'a>| {
+            Self::evaluate_element(rtxn, index, fields_ids_map, element, filter)
+        };
+        match filter {
+            FilterCondition::Not(filter) => Ok(element.docids - evaluate(filter)?),
+            FilterCondition::Or(filters) => filters.iter().map(evaluate).union(),
+            FilterCondition::And(filters) => filters.iter().map(evaluate).intersection(),
+            FilterCondition::In { fid, els } => els
+                .iter()
+                .map(|el| Condition::Equal(el.clone()))
+                .map(|op| {
+                    Self::evaluate_element_operator(rtxn, index, fields_ids_map, element, fid, &op)
+                })
+                .union(),
+            FilterCondition::All { fid, els } => els
+                .iter()
+                .map(|el| Condition::Equal(el.clone()))
+                .map(|op| {
+                    Self::evaluate_element_operator(rtxn, index, fields_ids_map, element, fid, &op)
+                })
+                .intersection(),
+            FilterCondition::Condition { fid, op } => {
+                Self::evaluate_element_operator(rtxn, index, fields_ids_map, element, fid, op)
+            }
+            _ => Err(element.fid.as_external_error(FilterError::NestedFilterNotSupported))?,
+        }
+    }
+
+    /// Aggregates the documents ids where a field of the element at the given position matches the operator.
+    fn evaluate_element_operator(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        fields_ids_map: &FieldsIdsMap,
+        element: &Element<'_, 'a>,
+        fid: &Token<'a>,
+        operator: &Condition<'a>,
+    ) -> Result<RoaringBitmap> {
+        let db = index.facet_id_element_docids;
+        let position = element.position;
+        let Some(field_id) = fields_ids_map.id(&format!("{}.{}", element.fid.value(), fid.value()))
+        else {
+            return match operator {
+                Condition::NotEqual(_) => Ok(element.docids.clone()),
+                _ => Ok(RoaringBitmap::new()),
+            };
+        };
+
+        // the dates of the elements are not indexed as numbers
+        let (left, right) = match operator {
+            Condition::GreaterThan(val) => {
+                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::GreaterThanOrEqual(val) => {
+                (Included(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThanOrEqual(val) => {
+                (Included(f64::MIN), Included(val.parse_finite_float()?))
+            }
+            Condition::Between { from, to } => {
+                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+            }
+            Condition::Exists => {
+                let key = (field_id, position, ElementFacet::Exists);
+                return Ok(db.get(rtxn, &key)?.unwrap_or_default());
+            }
+            Condition::Equal(val) => {
+                let exact = index.exact_facets_ids(rtxn)?.contains(&field_id);
+                let normalized = crate::normalize_facet_unless_exact(val.value(), exact);
+                let key = (field_id, position, ElementFacet::String(&normalized));
+                let mut docids = db.get(rtxn, &key)?.unwrap_or_default();
+                if let Ok(number) = val.parse_finite_float() {
+                    let key = (field_id, position, ElementFacet::Number(number));
+                    docids |= db.get(rtxn, &key)?.unwrap_or_default();
+                }
+                return Ok(docids);
+            }
+            Condition::NotEqual(val) => {
+                let operator = Condition::Equal(val.clone());
+                let docids = Self::evaluate_element_operator(
+                    rtxn,
+                    index,
+                    fields_ids_map,
+                    element,
+                    fid,
+                    &operator,
+                )?;
+                return Ok(element.docids - docids);
+            }
+            _ => return Err(fid.as_external_error(FilterError::NestedFilterNotSupported))?,
+        };
+
+        let mut prefix = field_id.to_be_bytes().to_vec();
+        prefix.extend_from_slice(&position.to_be_bytes());
+        prefix.push(ElementFacet::Number(0.0).tag());
+        let iter = db
+            .remap_key_type::<Bytes>()
+            .prefix_iter(rtxn, &prefix)?
+            .remap_key_type::<FieldIdElementFacetCodec>();
+        let mut docids = RoaringBitmap::new();
+        for result in iter {
+            let ((_, _, facet), number_docids) = result?;
+            if let ElementFacet::Number(number) = facet {
+                if (left, right).contains(&number) {
+                    docids |= number_docids;
+                }
+            }
+        }
+        Ok(docids)
+    }
+
+    /// Returns whether the document has a value in the left field that compares to a value in
+    /// the right field, numbers being compared with numbers and strings with strings.
+    fn compare_fields(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        docid: u32,
+        left: FieldId,
+        right: FieldId,
+        comparison: Comparison,
+    ) -> Result<bool> {
+        let number_bounds = |field_id: FieldId| -> Result<Option<(f64, f64)>> {
+            let mut prefix = field_id.to_be_bytes().to_vec();
+            prefix.extend_from_slice(&docid.to_be_bytes());
+            let mut bounds = None;
+            let iter = index
+                .field_id_docid_facet_f64s
+                .remap_key_type::<Bytes>()
+                .prefix_iter(rtxn, &prefix)?
+                .remap_key_type::<FieldDocIdFacetF64Codec>();
+            for result in iter {
+                let ((_, _, value), ()) = result?;
+                bounds = Some(match bounds {
+                    Some((min, max)) => (f64::min(min, value), f64::max(max, value)),
+                    None => (value, value),
+                });
+            }
+            Ok(bounds)
+        };
+        let string_bounds = |field_id: FieldId| -> Result<Option<(String, String)>> {
+            let mut prefix = field_id.to_be_bytes().to_vec();
+            prefix.extend_from_slice(&docid.to_be_bytes());
+            let mut bounds: Option<(String, String)> = None;
+            let iter = index
+                .field_id_docid_facet_strings
+                .remap_key_type::<Bytes>()
+                .prefix_iter(rtxn, &prefix)?
+                .remap_key_type::<FieldDocIdFacetStringCodec>();
+            for result in iter {
+                let ((_, _, normalized), _original) = result?;
+                bounds = Some(match bounds {
+                    Some((min, max)) if normalized < min.as_str() => (normalized.to_owned(), max),
+                    Some((min, max)) if normalized > max.as_str() => (min, normalized.to_owned()),
+                    Some(bounds) => bounds,
+                    None => (normalized.to_owned(), normalized.to_owned()),
+                });
+            }
+            Ok(bounds)
+        };
+
+        if let (Some(left), Some(right)) = (number_bounds(left)?, number_bounds(right)?) {
+            if comparison.any_match(left, right) {
+                return Ok(true);
+            }
+        }
+        if let (Some(left), Some(right)) = (string_bounds(left)?, string_bounds(right)?) {
+            if comparison.any_match(left, right) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_number_levels(
@@ -405,6 +911,32 @@ impl<'a> Filter<'a> {
         Ok(())
     }
 
+    /// Aggregates the documents ids whose strings are part of the specified range automatically
+    /// going deeper through the levels, the strings are compared lexicographically.
+    fn explore_facet_string_levels(
+        rtxn: &heed::RoTxn<'_>,
+        db: heed::Database<FacetGroupKeyCodec<StrRefCodec>, FacetGroupValueCodec>,
+        field_id: FieldId,
+        left: Bound<&str>,
+        right: Bound<&str>,
+        universe: Option<&RoaringBitmap>,
+        output: &mut RoaringBitmap,
+    ) -> Result<()> {
+        match (left, right) {
+            // lower TO upper when lower > upper must return no result
+            (Included(l), Included(r)) if l > r => return Ok(()),
+            (Included(l), Excluded(r)) if l >= r => return Ok(()),
+            (Excluded(l), Excluded(r)) if l >= r => return Ok(()),
+            (Excluded(l), Included(r)) if l >= r => return Ok(()),
+            (_, _) => (),
+        }
+        facet_range_search::find_docids_of_facet_within_bounds::<StrRefCodec>(
+            rtxn, db, field_id, &left, &right, universe, output,
+        )?;
+
+        Ok(())
+    }
+
     fn inner_evaluate(
         &self,
         rtxn: &heed::RoTxn<'_>,
@@ -451,11 +983,14 @@ impl<'a> Filter<'a> {
                     }))?
                 }
             }
-            FilterCondition::Condition { fid, op } => {
+            FilterCondition::All { fid, els } => {
                 if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
                     if let Some(fid) = field_ids_map.id(fid.value()) {
-                        Self::evaluate_operator(rtxn, index, fid, universe, op)
+                        els.iter()
+                            .map(|el| Condition::Equal(el.clone()))
+                            .map(|op| Self::evaluate_operator(rtxn, index, fid, universe, &op))
+                            .intersection()
                     } else {
                         Ok(RoaringBitmap::new())
                     }
@@ -466,26 +1001,81 @@ impl<'a> Filter<'a> {
                     }))?
                 }
             }
-            FilterCondition::Or(subfilters) => subfilters
-                .iter()
-                .cloned()
-                .map(|f| Self::inner_evaluate(&f.into(), rtxn, index, filterable_fields, universe))
-                .union(),
-            FilterCondition::And(subfilters) => {
-                let mut subfilters_iter = subfilters.iter();
-                if let Some(first_subfilter) = subfilters_iter.next() {
-                    let mut bitmap = Self::inner_evaluate(
-                        &(first_subfilter.clone()).into(),
-                        rtxn,
-                        index,
-                        filterable_fields,
-                        universe,
-                    )?;
-                    for f in subfilters_iter {
-                        if bitmap.is_empty() {
-                            return Ok(bitmap);
-                        }
-                        // TODO We are doing the intersections two times,
+            FilterCondition::Size { fid, op } => {
+                if crate::is_faceted(fid.value(), filterable_fields) {
+                    let field_ids_map = index.fields_ids_map(rtxn)?;
+                    let field_id = field_ids_map.id(fid.value());
+                    Self::evaluate_size(rtxn, index, fid, field_id, universe, op)
+                } else {
+                    Err(fid.as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: fid.value(),
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+            FilterCondition::Nested { fid, filter } => {
+                if crate::is_faceted(fid.value(), filterable_fields) {
+                    Self::evaluate_nested(rtxn, index, fid, filter, universe)
+                } else {
+                    Err(fid.as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: fid.value(),
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+            FilterCondition::Condition { fid, op } => {
+                if let Condition::CompareField { field, .. } = op {
+                    if !crate::is_faceted(field.value(), filterable_fields) {
+                        return Err(field.as_external_error(
+                            FilterError::AttributeNotFilterable {
+                                attribute: field.value(),
+                                filterable_fields: filterable_fields.clone(),
+                            },
+                        ))?;
+                    }
+                }
+                if crate::is_faceted(fid.value(), filterable_fields) {
+                    let field_ids_map = index.fields_ids_map(rtxn)?;
+                    if let Some(fid) = field_ids_map.id(fid.value()) {
+                        Self::evaluate_operator(rtxn, index, fid, universe, op)
+                    } else {
+                        Ok(RoaringBitmap::new())
+                    }
+                } else {
+                    Err(fid.as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: fid.value(),
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+            FilterCondition::Or(subfilters) => subfilters
+                .iter()
+                .cloned()
+                .map(|f| Self::inner_evaluate(&f.into(), rtxn, index, filterable_fields, universe))
+                .union(),
+            FilterCondition::And(subfilters) => {
+                // the fields comparisons read the fields of every candidate document,
+                // they are evaluated last to only read the documents matching the other filters
+                let (comparisons, others): (Vec<_>, Vec<_>) = subfilters.iter().partition(|f| {
+                    matches!(
+                        f,
+                        FilterCondition::Condition { op: Condition::CompareField { .. }, .. }
+                    )
+                });
+                let mut subfilters_iter = others.into_iter().chain(comparisons);
+                if let Some(first_subfilter) = subfilters_iter.next() {
+                    let mut bitmap = Self::inner_evaluate(
+                        &(first_subfilter.clone()).into(),
+                        rtxn,
+                        index,
+                        filterable_fields,
+                        universe,
+                    )?;
+                    for f in subfilters_iter {
+                        if bitmap.is_empty() {
+                            return Ok(bitmap);
+                        }
+                        // TODO We are doing the intersections two times,
                         //      it could be more efficient
                         //      Can't I just replace this `&=` by an `=`?
                         bitmap &= Self::inner_evaluate(
@@ -660,7 +1250,45 @@ impl<'a> Filter<'a> {
                         )?
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    let selected = selected_lat & selected_lng;
+                    if selected.is_empty() {
+                        return Ok(selected);
+                    }
+
+                    // A document located at several places can have the latitude of one of its
+                    // points and the longitude of another one inside the bounding box, we make
+                    // sure that at least one of its points is really inside of it.
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+                    if rtree.size() as u64 == index.geo_faceted_documents_ids(rtxn)?.len() {
+                        // every document has a single point
+                        return Ok(selected);
+                    }
+
+                    let min_z = bottom_left[0].to_radians().sin() - f64::EPSILON;
+                    let max_z = top_right[0].to_radians().sin() + f64::EPSILON;
+                    let envelope =
+                        rstar::AABB::from_corners([-1.0, -1.0, min_z], [1.0, 1.0, max_z]);
+                    let contains_lng = |lng: f64| {
+                        if top_right[1] < bottom_left[1] {
+                            lng >= bottom_left[1] || lng <= top_right[1]
+                        } else {
+                            (bottom_left[1]..=top_right[1]).contains(&lng)
+                        }
+                    };
+                    let inside: RoaringBitmap = rtree
+                        .locate_in_envelope(&envelope)
+                        .filter(|point| selected.contains(point.data.0))
+                        .filter(|point| {
+                            let [lat, lng] = point.data.1;
+                            (bottom_left[0]..=top_right[0]).contains(&lat) && contains_lng(lng)
+                        })
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(inside)
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -670,16 +1298,131 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if filterable_fields.contains(RESERVED_GEO_FIELD_NAME) {
+                    let mut polygon = Vec::with_capacity(points.len());
+                    for [lat, lng] in points {
+                        let point = [lat.parse_finite_float()?, lng.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&point[0]) {
+                            return Err(lat.as_external_error(BadGeoError::Lat(point[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&point[1]) {
+                            return Err(lng.as_external_error(BadGeoError::Lng(point[1])))?;
+                        }
+                        polygon.push(point);
+                    }
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    let polygon = GeoPolygon::new(polygon);
+                    let result = rtree
+                        .locate_in_envelope(&polygon.envelope())
+                        .filter(|point| polygon.contains(&point.data.1))
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEO_FIELD_NAME,
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+            FilterCondition::GeoContains { point } => {
+                if filterable_fields.contains(RESERVED_GEOJSON_FIELD_NAME) {
+                    let lat_lng: [f64; 2] =
+                        [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
+                    if !(-90.0..=90.0).contains(&lat_lng[0]) {
+                        return Err(point[0].as_external_error(BadGeoError::Lat(lat_lng[0])))?;
+                    }
+                    if !(-180.0..=180.0).contains(&lat_lng[1]) {
+                        return Err(point[1].as_external_error(BadGeoError::Lng(lat_lng[1])))?;
+                    }
+                    let rtree = match index.geojson_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    // the bounding boxes only preselect the geometries, we only
+                    // load the geometries of these candidates to refine the matches
+                    let mut result = RoaringBitmap::new();
+                    for shape in rtree.locate_all_at_point(&lat_lng) {
+                        let docid = shape.data;
+                        let geometry = index.geojson_geometries.get(rtxn, &docid)?;
+                        if geometry.is_some_and(|geometry| geometry.contains(&lat_lng)) {
+                            result.insert(docid);
+                        }
+                    }
+
+                    Ok(result)
+                } else {
+                    Err(point[0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEOJSON_FIELD_NAME,
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
         }
     }
 }
 
+/// An element of a nested field, identified by its position, and the documents containing it.
+struct Element<'e, 'a> {
+    fid: &'e Token<'a>,
+    position: u16,
+    docids: &'e RoaringBitmap,
+}
+
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     fn from(fc: FilterCondition<'a>) -> Self {
         Self { condition: fc }
     }
 }
 
+/// A bound of a range condition. Numbers are compared with the number facets, other strings
+/// are compared lexicographically with the string facets and dates are compared with both,
+/// unless the field is a date attribute, whose dates are only indexed as numbers.
+struct RangeBound {
+    number: Option<f64>,
+    string: Option<String>,
+}
+
+impl RangeBound {
+    fn parse(token: &Token<'_>, exact: bool, date: bool) -> Result<Self> {
+        match token.parse_finite_float() {
+            Ok(number) => Ok(RangeBound { number: Some(number), string: None }),
+            Err(error) if matches!(error.kind(), ErrorKind::NonFiniteFloat) => Err(error.into()),
+            Err(_) => {
+                // only the date attributes index their dates, as numbers instead of strings
+                let timestamp = date.then(|| filter_parser::parse_rfc3339_date(token.value()));
+                match timestamp.flatten() {
+                    Some(timestamp) => Ok(RangeBound { number: Some(timestamp), string: None }),
+                    None => Ok(RangeBound {
+                        number: None,
+                        string: Some(crate::normalize_facet_unless_exact(token.value(), exact)),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Converts the bound of a range condition into a bound on one kind of facet values,
+/// returns `None` when the bound can't be compared with this kind of facet values.
+fn facet_bound<'b, T>(
+    bound: &'b Bound<RangeBound>,
+    value: impl Fn(&'b RangeBound) -> Option<T>,
+) -> Option<Bound<T>> {
+    match bound {
+        Included(bound) => value(bound).map(Included),
+        Excluded(bound) => value(bound).map(Excluded),
+        Unbounded => Some(Unbounded),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Write;
@@ -687,8 +1430,9 @@ mod tests {
 
     use big_s::S;
     use either::Either;
-    use maplit::hashset;
+    use maplit::{btreemap, hashset};
     use roaring::RoaringBitmap;
+    use serde_json::json;
 
     use crate::constants::RESERVED_GEO_FIELD_NAME;
     use crate::index::tests::TempIndex;
@@ -777,6 +1521,33 @@ mod tests {
         assert_eq!(condition, expected);
     }
 
+    #[test]
+    fn without_facet() {
+        let filter = Filter::from_array(vec![
+            Either::Right("brand = apple AND (price < 100 OR brand.origin = us)"),
+            Either::Left(vec!["brand = samsung", "NOT brand.origin = kr"]),
+            Either::Right("_geoRadius(12, 13, 14) AND brand IN [google, nokia]"),
+        ])
+        .unwrap()
+        .unwrap();
+
+        let expected =
+            Filter::from_str("(price < 100 OR brand.origin = us) AND _geoRadius(12, 13, 14)")
+                .unwrap()
+                .unwrap();
+        assert_eq!(filter.without_facet("brand"), Some(expected));
+
+        let expected = Filter::from_str(
+            "brand = apple AND (price < 100 OR brand.origin = us) AND (brand = samsung OR NOT brand.origin = kr) AND _geoRadius(12, 13, 14) AND brand IN [google, nokia]",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(filter.without_facet("bran"), Some(expected));
+
+        let filter = Filter::from_str("brand = apple OR brand = samsung").unwrap().unwrap();
+        assert_eq!(filter.without_facet("brand"), None);
+    }
+
     #[test]
     fn not_filterable() {
         let index = TempIndex::new();
@@ -794,6 +1565,13 @@ mod tests {
             "Attribute `_geo` is not filterable. This index does not have configured filterable attributes."
         ));
 
+        let filter =
+            Filter::from_str("_geoPolygon([42, 150], [30, 10], [20, 30])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `_geo` is not filterable. This index does not have configured filterable attributes."
+        ));
+
         let filter = Filter::from_str("dog = \"bernese mountain\"").unwrap().unwrap();
         let error = filter.evaluate(&rtxn, &index).unwrap_err();
         assert!(error.to_string().starts_with(
@@ -822,6 +1600,13 @@ mod tests {
             "Attribute `_geo` is not filterable. Available filterable attributes are: `title`."
         ));
 
+        let filter =
+            Filter::from_str("_geoPolygon([42, 150], [30, 10], [20, 30])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `_geo` is not filterable. Available filterable attributes are: `title`."
+        ));
+
         let filter = Filter::from_str("name = 12").unwrap().unwrap();
         let error = filter.evaluate(&rtxn, &index).unwrap_err();
         assert!(error.to_string().starts_with(
@@ -1077,6 +1862,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn geo_polygon_error() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S(RESERVED_GEO_FIELD_NAME) });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("_geoPolygon([10, 10], [90.0000001, 150], [30, 10])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad latitude `90.0000001`. Latitude must be contained between -90 and 90 degrees."
+        ));
+
+        let filter = Filter::from_str("_geoPolygon([10, 10], [30, 10], [-10, -180.000001])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad longitude `-180.000001`. Longitude must be contained between -180 and 180 degrees."
+        ));
+    }
+
     #[test]
     fn filter_depth() {
         // generates a big (2 MiB) filter with too much of ORs.
@@ -1237,4 +2051,653 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_date() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("createdAt"), S("price") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "createdAt": "2023-12-31T23:59:59Z" },
+                { "id": 1, "createdAt": "2024-01-01T00:00:00Z" },
+                { "id": 2, "createdAt": "2024-06-15T12:00:00+02:00" },
+                { "id": 3, "createdAt": "2024-12-31" },
+                { "id": 4, "createdAt": "not a date" },
+                { "id": 5, "price": 1704067200 },
+            ]))
+            .unwrap();
+
+        // the dates are compared as strings until they are declared as date attributes
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("createdAt >= 2024-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3, 4]));
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_date_attributes(hashset! { S("createdAt") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("createdAt >= 2024-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3]));
+
+        let filter = Filter::from_str("createdAt < 2024-01-01T00:00:00Z").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        let filter = Filter::from_str("createdAt 2024-06-15T10:00:00Z TO 2024-12-31T00:00:00Z")
+            .unwrap()
+            .unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 3]));
+
+        let filter = Filter::from_str("createdAt = '2024-01-01T01:00:00+01:00'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1]));
+
+        let filter = Filter::from_str("createdAt = 'not a date'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([4]));
+
+        // the dates are not converted to timestamps for the other attributes
+        let filter = Filter::from_str("price = 2024-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::new());
+
+        let filter = Filter::from_str("price >= 2024-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::new());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_date_attributes();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("createdAt >= 2024-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn filter_string_range() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("code") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "code": "A099" },
+                { "id": 1, "code": "A100" },
+                { "id": 2, "code": "a150" },
+                { "id": 3, "code": "B" },
+                { "id": 4, "code": "B001" },
+                { "id": 5, "code": "C" },
+                { "id": 6, "code": 150 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("code >= \"A100\" AND code < \"B\"").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("code > 'B'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([4, 5]));
+
+        let filter = Filter::from_str("code <= 'b'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2, 3]));
+
+        let filter = Filter::from_str("code 'a1' TO 'b0'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3]));
+
+        let filter = Filter::from_str("code 'B' TO 'A'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::new());
+
+        // the numbers are still only compared with the numbers
+        let filter = Filter::from_str("code < 1000").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([6]));
+    }
+
+    #[test]
+    fn filter_field_comparison() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! {
+                    S("salePrice"), S("listPrice"), S("from"), S("to"), S("discount")
+                });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "salePrice": 10, "listPrice": 12, "from": "b", "to": "a" },
+                { "id": 1, "salePrice": 12, "listPrice": 12, "from": "a", "to": "B" },
+                { "id": 2, "salePrice": 15, "listPrice": 12, "from": "c", "to": ["a", "d"] },
+                { "id": 3, "salePrice": [9, 20], "listPrice": 12 },
+                { "id": 4, "salePrice": 5, "from": "a" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("salePrice < listPrice").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 3]));
+
+        let filter = Filter::from_str("salePrice <= listPrice").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 3]));
+
+        let filter = Filter::from_str("salePrice > listPrice").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 3]));
+
+        let filter = Filter::from_str("NOT salePrice >= listPrice").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 4]));
+
+        // strings are compared with the strings, once normalized
+        let filter = Filter::from_str("from < to").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("salePrice < from").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::new());
+
+        // the comparisons are evaluated after the other filters of an `AND`
+        let filter = Filter::from_str("from < to AND salePrice > 11").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter =
+            Filter::from_str("salePrice < listPrice AND NOT salePrice = 9").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        // a filterable field that no document contains
+        let filter = Filter::from_str("salePrice < discount").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::new());
+
+        // both fields must be filterable
+        let filter = Filter::from_str("salePrice < stock").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `stock` is not filterable. Available filterable attributes are: `discount`, `from`, `listPrice`, `salePrice`, `to`."
+        ));
+
+        let filter = Filter::from_str("stock > salePrice OR salePrice = 5").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("Attribute `stock` is not filterable."));
+    }
+
+    #[test]
+    fn filter_under() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("category"), S("brand") });
+                settings.set_hierarchical_facets(btreemap! { S("category") => S(" > ") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "category": "Electronics > Audio > Headphones" },
+                { "id": 1, "category": "Electronics > Audio" },
+                { "id": 2, "category": "Electronics > Audiobooks" },
+                { "id": 3, "category": ["Electronics > TV", "Home > Kitchen"] },
+                { "id": 4, "category": "Home", "brand": "Acme" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let under = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+
+        assert_eq!(under("category UNDER Electronics"), RoaringBitmap::from_iter([0, 1, 2, 3]));
+        assert_eq!(under("category UNDER 'electronics > audio'"), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(under("category UNDER 'Home'"), RoaringBitmap::from_iter([3, 4]));
+        assert_eq!(under("category NOT UNDER 'Home'"), RoaringBitmap::from_iter([0, 1, 2]));
+        assert_eq!(under("category UNDER 'Electro'"), RoaringBitmap::new());
+
+        let filter = Filter::from_str("brand UNDER Acme").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `brand` is not a hierarchical facet and thus, cannot be used with the `UNDER` operator."
+        ));
+    }
+
+    #[test]
+    fn filter_all_and_size() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("tags") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "tags": ["red", "blue", "green"], "colors": ["red", "blue"] },
+                { "id": 1, "tags": ["red", "blue"], "colors": "red" },
+                { "id": 2, "tags": "red" },
+                { "id": 3, "tags": [] },
+                { "id": 4, "tags": null },
+                { "id": 5 },
+                { "id": 6, "tags": ["red", "", 3], "colors": ["red", " ", ""] },
+                { "id": 7, "tags": [" "], "colors": [" "] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+
+        assert_eq!(evaluate("tags ALL [red, blue]"), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(evaluate("tags ALL [red]"), RoaringBitmap::from_iter([0, 1, 2, 6]));
+        assert_eq!(evaluate("tags ALL [red, yellow]"), RoaringBitmap::new());
+        assert_eq!(
+            evaluate("tags NOT ALL [red, blue]"),
+            RoaringBitmap::from_iter([2, 3, 4, 5, 6, 7])
+        );
+        assert_eq!(evaluate("tags ALL [red, 3]"), RoaringBitmap::from_iter([6]));
+
+        // the missing, null and empty fields have a size of zero and every element is counted
+        assert_eq!(evaluate("SIZE(tags) = 0"), RoaringBitmap::from_iter([3, 4, 5]));
+        assert_eq!(evaluate("SIZE(tags) > 1"), RoaringBitmap::from_iter([0, 1, 6]));
+        assert_eq!(evaluate("SIZE(tags) >= 3"), RoaringBitmap::from_iter([0, 6]));
+        assert_eq!(evaluate("SIZE(tags) < 2"), RoaringBitmap::from_iter([2, 3, 4, 5, 7]));
+        assert_eq!(evaluate("SIZE(tags) 1 TO 2"), RoaringBitmap::from_iter([1, 2, 7]));
+        assert_eq!(evaluate("SIZE(tags) != 2"), RoaringBitmap::from_iter([0, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(evaluate("SIZE(tags) > 1 AND tags = blue"), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(evaluate("tags = red AND SIZE(tags) != 2"), RoaringBitmap::from_iter([0, 2, 6]));
+
+        let filter = Filter::from_str("SIZE(tags) > many").unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).is_err());
+
+        let filter = Filter::from_str("SIZE(colors) > 1").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `colors` is not filterable. Available filterable attributes are: `tags`."
+        ));
+        drop(rtxn);
+
+        // the sizes are updated with the documents
+        index.add_documents(documents!([{ "id": 1, "tags": ["blue"] }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(evaluate("SIZE(tags) = 1"), RoaringBitmap::from_iter([1, 2, 7]));
+        assert_eq!(evaluate("SIZE(tags) = 3"), RoaringBitmap::from_iter([0, 6]));
+        drop(rtxn);
+
+        // and computed the same way when a field becomes filterable
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("tags"), S("colors") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(evaluate("SIZE(colors) = 3"), RoaringBitmap::from_iter([6]));
+        assert_eq!(evaluate("SIZE(colors) = 2"), RoaringBitmap::from_iter([0]));
+        assert_eq!(evaluate("SIZE(colors) = 1"), RoaringBitmap::from_iter([7]));
+        assert_eq!(evaluate("SIZE(colors) = 0"), RoaringBitmap::from_iter([1, 2, 3, 4, 5]));
+        assert_eq!(evaluate("SIZE(tags) = 1"), RoaringBitmap::from_iter([1, 2, 7]));
+    }
+
+    #[test]
+    fn filter_nested() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("variants"), S("tags") });
+                settings.set_nested_attributes(hashset! { S("variants") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "variants": [
+                    { "color": "red", "size": "L", "price": 10 },
+                    { "color": "blue", "size": "M", "price": 20 },
+                ] },
+                { "id": 1, "variants": [
+                    { "color": "red", "size": "M", "price": 30 },
+                    { "color": "blue", "size": "L", "price": 5 },
+                ] },
+                { "id": 2, "variants": {
+                    "color": "Red", "size": "L", "price": 15, "stock": { "available": true }
+                } },
+                { "id": 3, "variants": [] },
+                { "id": 4, "tags": [{ "name": "new" }] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+
+        // all the conditions must match the same element
+        assert_eq!(
+            evaluate("variants[color = red AND size = L]"),
+            RoaringBitmap::from_iter([0, 2])
+        );
+        assert_eq!(
+            evaluate("variants.color = red AND variants.size = L"),
+            RoaringBitmap::from_iter([0, 1, 2])
+        );
+        assert_eq!(
+            evaluate("variants[color = blue AND price < 10]"),
+            RoaringBitmap::from_iter([1])
+        );
+        assert_eq!(evaluate("variants[price 10 TO 15]"), RoaringBitmap::from_iter([0, 2]));
+        assert_eq!(evaluate("variants[NOT size = L]"), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(evaluate("variants[size != L]"), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(
+            evaluate("variants[color IN [blue, green] AND price >= 20]"),
+            RoaringBitmap::from_iter([0])
+        );
+        assert_eq!(evaluate("variants[stock.available = true]"), RoaringBitmap::from_iter([2]));
+        assert_eq!(evaluate("variants[discount EXISTS]"), RoaringBitmap::new());
+        assert_eq!(evaluate("NOT variants[color = red]"), RoaringBitmap::from_iter([3, 4]));
+
+        let filter = Filter::from_str("tags[name = new]").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `tags` is not a nested attribute and thus, cannot be filtered element by element."
+        ));
+
+        let filter = Filter::from_str("variants[SIZE(color) > 1]").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("A nested filter only supports"));
+        drop(rtxn);
+
+        // the elements are updated with the documents
+        index
+            .add_documents(documents!([{ "id": 1, "variants": [{ "color": "red", "size": "L" }] }]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("variants[color = red AND size = L]").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+        let filter = Filter::from_str("variants[price EXISTS]").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2]));
+        drop(rtxn);
+
+        // and reindexed when the nested attributes change
+        index
+            .update_settings(|settings| {
+                settings.set_nested_attributes(hashset! { S("variants"), S("tags") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(evaluate("tags[name = new]"), RoaringBitmap::from_iter([4]));
+        assert_eq!(
+            evaluate("variants[color = red AND size = L]"),
+            RoaringBitmap::from_iter([0, 1, 2])
+        );
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_nested_attributes();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("variants[color = red]").unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).is_err());
+        assert!(index.facet_id_element_docids.is_empty(&rtxn).unwrap());
+    }
+
+    #[test]
+    fn filter_object() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(
+                    hashset! { S("title"), S("price"), S("genres"), S("_geo") },
+                );
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "Mister \"Mv\" O'Neil", "price": 10, "genres": ["horror"] },
+                { "id": 1, "title": "Hello", "price": 20, "genres": ["horror", "comedy"] },
+                { "id": 2, "title": "World", "price": 30.5, "genres": [] },
+                { "id": 3, "title": "AND", "genres": "comedy" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: serde_json::Value| {
+            let filter = Filter::from_json(&filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+
+        // the values don't need any quoting
+        assert_eq!(
+            evaluate(json!({ "field": "title", "op": "=", "value": "Mister \"Mv\" O'Neil" })),
+            RoaringBitmap::from_iter([0])
+        );
+        assert_eq!(
+            evaluate(json!({ "field": "title", "op": "=", "value": "AND" })),
+            RoaringBitmap::from_iter([3])
+        );
+        assert_eq!(
+            evaluate(json!({ "and": [
+                { "field": "price", "op": ">", "value": 10 },
+                { "field": "genres", "op": "IN", "value": ["horror", "comedy"] },
+            ] })),
+            RoaringBitmap::from_iter([1])
+        );
+        assert_eq!(
+            evaluate(json!({ "or": [
+                { "field": "price", "op": "TO", "value": [25, 40] },
+                { "not": { "field": "price", "op": "EXISTS" } },
+            ] })),
+            RoaringBitmap::from_iter([2, 3])
+        );
+        assert_eq!(
+            evaluate(json!({ "field": "genres", "op": "IS EMPTY" })),
+            RoaringBitmap::from_iter([2])
+        );
+        assert_eq!(
+            evaluate(json!({ "size": "genres", "op": ">=", "value": 2 })),
+            RoaringBitmap::from_iter([1])
+        );
+        // filter expressions and objects can be mixed
+        assert_eq!(
+            evaluate(json!(["price < 25", { "field": "genres", "op": "!=", "value": "comedy" }])),
+            RoaringBitmap::from_iter([0])
+        );
+        assert_eq!(
+            evaluate(json!([[{ "field": "price", "op": "=", "value": 10 }, "title = World"]])),
+            RoaringBitmap::from_iter([0, 2])
+        );
+        // empty filters are ignored
+        assert_eq!(
+            evaluate(
+                json!({ "and": ["", { "or": [] }, { "field": "price", "op": "<", "value": 15 }] })
+            ),
+            RoaringBitmap::from_iter([0])
+        );
+        assert!(Filter::from_json(&json!({ "not": { "and": [] } })).unwrap().is_none());
+
+        let error = |filter: serde_json::Value| Filter::from_json(&filter).unwrap_err().to_string();
+        assert!(error(json!({ "price": 10 })).starts_with(
+            "Invalid filter object `{\"price\":10}`: expected one of the `and`, `or`, `not`, `field`,"
+        ));
+        assert!(error(json!({ "field": "price", "op": "~", "value": 10 }))
+            .starts_with("Invalid operator `~` in the filter object"));
+        assert_eq!(
+            error(json!({ "field": "price", "op": "=" })),
+            "Missing `value` in the filter object `{\"field\":\"price\",\"op\":\"=\"}`."
+        );
+        assert!(error(json!({ "field": "price", "op": "TO", "value": [1] }))
+            .ends_with("expected an array of 2 elements."));
+        assert!(error(json!({ "field": "price", "op": "EXISTS", "boost": 2 }))
+            .starts_with("Unexpected key `boost` in the filter object"));
+        assert!(error(json!({ "field": "price", "op": "EXISTS", "value": 1 }))
+            .starts_with("The `EXISTS` operator doesn't expect a `value`"));
+        assert_eq!(
+            error(json!({ "and": [true] })),
+            "Invalid filter `true`: expected a filter expression or a filter object."
+        );
+
+        // the errors on the numbers point to the key of the object they come from
+        let filter = Filter::from_json(&json!({ "geoRadius": [100, 0, 10] })).unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err().to_string();
+        assert!(error.starts_with("Bad latitude `100`."), "{error}");
+        assert!(error.ends_with("\n1:10 geoRadius"), "{error}");
+    }
+
+    #[test]
+    fn filter_exact_facets() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("sku"), S("name") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "sku": "AbC-1", "name": "Abc" },
+                { "id": 1, "sku": "abc-1", "name": "abc" },
+                { "id": 2, "sku": "ÀBC-2", "name": "ABC" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(evaluate("sku = abc-1"), RoaringBitmap::from_iter([0, 1]));
+        drop(rtxn);
+
+        // the exact facets are reindexed without being normalized
+        index
+            .update_settings(|settings| {
+                settings.set_exact_facets(hashset! { S("sku") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(evaluate("sku = AbC-1"), RoaringBitmap::from_iter([0]));
+        assert_eq!(evaluate("sku = abc-1"), RoaringBitmap::from_iter([1]));
+        assert_eq!(evaluate("sku = abc-2"), RoaringBitmap::new());
+        assert_eq!(evaluate("sku != abc-1"), RoaringBitmap::from_iter([0, 2]));
+        assert_eq!(evaluate("sku IN [AbC-1, ÀBC-2]"), RoaringBitmap::from_iter([0, 2]));
+        // the other facets are still normalized
+        assert_eq!(evaluate("name = abc"), RoaringBitmap::from_iter([0, 1, 2]));
+        drop(rtxn);
+
+        // as are the documents added afterwards
+        index.add_documents(documents!([{ "id": 3, "sku": "ABC-1" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("sku = ABC-1").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap(), RoaringBitmap::from_iter([3]));
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_exact_facets();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("sku = abc-1").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap(), RoaringBitmap::from_iter([0, 1, 3]));
+        drop(rtxn);
+
+        // the fields of the elements of a nested exact facet are not normalized either
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("variants") });
+                settings.set_nested_attributes(hashset! { S("variants") });
+                settings.set_exact_facets(hashset! { S("variants.sku") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 4, "variants": [{ "sku": "AbC-1", "name": "Abc" }] },
+                { "id": 5, "variants": [{ "sku": "abc-1", "name": "abc" }] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(evaluate("variants[sku = AbC-1]"), RoaringBitmap::from_iter([4]));
+        assert_eq!(evaluate("variants[sku = abc-1 AND name = ABC]"), RoaringBitmap::from_iter([5]));
+    }
 }
@@ -0,0 +1,294 @@
+//! Parses the JSON object representation of a filter, which maps onto a [`FilterCondition`]
+//! without going through the filter syntax and its quoting rules.
+//!
+//! ```text
+//! filter    = string | object
+//! object    = { "and": [filter, ...] } | { "or": [filter, ...] } | { "not": filter }
+//!           | { "field": name, "op": operator, "value": value }
+//!           | { "field": name, "op": "<" | "<=" | ">" | ">=", "otherField": name }
+//!           | { "size": name, "op": operator, "value": value }
+//!           | { "nested": name, "filter": filter }
+//!           | { "geoRadius": [lat, lng, distance] }
+//!           | { "geoBoundingBox": [[lat, lng], [lat, lng]] }
+//!           | { "geoPolygon": [[lat, lng], [lat, lng], [lat, lng], ...] }
+//!           | { "geoContains": [lat, lng] }
+//! ```
+//!
+//! The `TO` operator expects a `[from, to]` value, the `IN` and `ALL` operators an array
+//! of values and the `EXISTS`, `IS NULL` and `IS EMPTY` operators no value at all.
+
+use filter_parser::{Comparison, Condition, FilterCondition, Span, Token};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, UserError};
+use crate::Result;
+
+const OBJECT_KEYS: &str = "`and`, `or`, `not`, `field`, `size`, `nested`, `geoRadius`, `geoBoundingBox`, `geoPolygon` or `geoContains`";
+
+const OPERATORS: &str = "`=`, `!=`, `>`, `>=`, `<`, `<=`, `TO`, `IN`, `ALL`, `EXISTS`, `IS NULL`, `IS EMPTY`, `CONTAINS`, `STARTS WITH` or `UNDER`";
+
+/// Parses a filter given either as a filter expression or as a filter object.
+///
+/// Empty expressions and empty `and` or `or` arrays are ignored, like an empty string is.
+pub(super) fn parse_filter_value(value: &Value) -> Result<Option<FilterCondition<'_>>> {
+    match value {
+        Value::String(expression) => FilterCondition::parse(expression)
+            .map_err(|e| Error::UserError(UserError::InvalidFilter(e.to_string()))),
+        Value::Object(object) => parse_filter_object(object),
+        value => Err(invalid_filter(format!(
+            "Invalid filter `{value}`: expected a filter expression or a filter object."
+        ))),
+    }
+}
+
+fn parse_filter_object(object: &Map<String, Value>) -> Result<Option<FilterCondition<'_>>> {
+    let shape = ["and", "or", "not", "field", "size", "nested"]
+        .into_iter()
+        .chain(["geoRadius", "geoBoundingBox", "geoPolygon", "geoContains"])
+        .find(|key| object.contains_key(*key))
+        .ok_or_else(|| {
+            invalid_filter(format!(
+                "Invalid filter object `{}`: expected one of the {OBJECT_KEYS} keys.",
+                Value::Object(object.clone())
+            ))
+        })?;
+
+    let allowed_keys: &[&str] = match shape {
+        "field" => &["field", "op", "value", "otherField"],
+        "size" => &["size", "op", "value"],
+        "nested" => &["nested", "filter"],
+        _ => &[shape],
+    };
+    if let Some(key) = object.keys().find(|key| !allowed_keys.contains(&key.as_str())) {
+        return Err(invalid_filter(format!(
+            "Unexpected key `{key}` in the filter object `{}`.",
+            Value::Object(object.clone())
+        )));
+    }
+
+    let condition = match shape {
+        "and" | "or" => {
+            let mut filters = Vec::new();
+            for value in expect_array(object, shape)? {
+                filters.extend(parse_filter_value(value)?);
+            }
+            match filters.len() {
+                0 => None,
+                1 => filters.pop(),
+                _ if shape == "and" => Some(FilterCondition::And(filters)),
+                _ => Some(FilterCondition::Or(filters)),
+            }
+        }
+        "not" => {
+            parse_filter_value(&object["not"])?.map(|filter| FilterCondition::Not(Box::new(filter)))
+        }
+        "field" => {
+            let fid = Token::from(expect_str(object, "field")?);
+            let op = expect_str(object, "op")?;
+            let condition = match op.to_ascii_uppercase().as_str() {
+                "IN" => FilterCondition::In { fid, els: value_tokens(object)? },
+                "ALL" => FilterCondition::All { fid, els: value_tokens(object)? },
+                _ => FilterCondition::Condition { fid, op: parse_condition(object, op)? },
+            };
+            Some(condition)
+        }
+        "size" => {
+            let fid = Token::from(expect_str(object, "size")?);
+            let op = parse_condition(object, expect_str(object, "op")?)?;
+            Some(FilterCondition::Size { fid, op })
+        }
+        "nested" => {
+            let fid = Token::from(expect_str(object, "nested")?);
+            let filter = object.get("filter").ok_or_else(|| missing_key(object, "filter"))?;
+            parse_filter_value(filter)?
+                .map(|filter| FilterCondition::Nested { fid, filter: Box::new(filter) })
+        }
+        "geoRadius" => {
+            let [lat, lng, radius] = expect_tokens(object, shape)?;
+            Some(FilterCondition::GeoLowerThan { point: [lat, lng], radius })
+        }
+        "geoBoundingBox" => {
+            let [top_right_point, bottom_left_point] = expect_array_of(object, shape)?;
+            Some(FilterCondition::GeoBoundingBox {
+                top_right_point: point_tokens(object, shape, top_right_point)?,
+                bottom_left_point: point_tokens(object, shape, bottom_left_point)?,
+            })
+        }
+        "geoPolygon" => {
+            let points = expect_array(object, shape)?
+                .iter()
+                .map(|point| point_tokens(object, shape, point))
+                .collect::<Result<Vec<_>>>()?;
+            if points.len() < 3 {
+                return Err(invalid_filter(format!(
+                    "The `geoPolygon` filter object expects at least three `[latitude, longitude]` points but found `{}`.",
+                    object["geoPolygon"]
+                )));
+            }
+            Some(FilterCondition::GeoPolygon { points })
+        }
+        "geoContains" => {
+            Some(FilterCondition::GeoContains { point: expect_tokens(object, shape)? })
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(condition)
+}
+
+/// Parses the operator of a `field` or `size` filter object into a condition.
+fn parse_condition<'a>(object: &'a Map<String, Value>, op: &'a str) -> Result<Condition<'a>> {
+    let comparison = match op {
+        ">" => Some(Comparison::GreaterThan),
+        ">=" => Some(Comparison::GreaterThanOrEqual),
+        "<" => Some(Comparison::LowerThan),
+        "<=" => Some(Comparison::LowerThanOrEqual),
+        _ => None,
+    };
+    if let Some(field) = object.get("otherField") {
+        let Some(comparison) = comparison else {
+            return Err(invalid_filter(format!(
+                "The `{op}` operator can't compare two fields in the filter object `{}`. Only the `>`, `>=`, `<` and `<=` operators can be used with `otherField`.",
+                Value::Object(object.clone())
+            )));
+        };
+        let field = field.as_str().ok_or_else(|| invalid_type(object, "otherField", "a string"))?;
+        return Ok(Condition::CompareField { comparison, field: Token::from(field) });
+    }
+
+    let condition = match op.to_ascii_uppercase().as_str() {
+        "=" => Condition::Equal(value_token(object)?),
+        "!=" => Condition::NotEqual(value_token(object)?),
+        ">" => Condition::GreaterThan(value_token(object)?),
+        ">=" => Condition::GreaterThanOrEqual(value_token(object)?),
+        "<" => Condition::LowerThan(value_token(object)?),
+        "<=" => Condition::LowerThanOrEqual(value_token(object)?),
+        "TO" => {
+            let [from, to] = expect_tokens(object, "value")?;
+            Condition::Between { from, to }
+        }
+        "CONTAINS" => Condition::Contains { keyword: Token::from(op), word: value_token(object)? },
+        "STARTS WITH" => {
+            Condition::StartsWith { keyword: Token::from(op), word: value_token(object)? }
+        }
+        "UNDER" => Condition::Under(value_token(object)?),
+        "EXISTS" | "IS NULL" | "IS EMPTY" => {
+            if object.contains_key("value") {
+                return Err(invalid_filter(format!(
+                    "The `{op}` operator doesn't expect a `value` in the filter object `{}`.",
+                    Value::Object(object.clone())
+                )));
+            }
+            match op.to_ascii_uppercase().as_str() {
+                "EXISTS" => Condition::Exists,
+                "IS NULL" => Condition::Null,
+                _ => Condition::Empty,
+            }
+        }
+        _ => {
+            return Err(invalid_filter(format!(
+                "Invalid operator `{op}` in the filter object `{}`: expected one of {OPERATORS}.",
+                Value::Object(object.clone())
+            )))
+        }
+    };
+
+    Ok(condition)
+}
+
+fn value_token(object: &Map<String, Value>) -> Result<Token<'_>> {
+    let value = object.get("value").ok_or_else(|| missing_key(object, "value"))?;
+    scalar_token(value, "value")
+        .ok_or_else(|| invalid_type(object, "value", "a string, a number or a boolean"))
+}
+
+fn value_tokens(object: &Map<String, Value>) -> Result<Vec<Token<'_>>> {
+    expect_array(object, "value")?
+        .iter()
+        .map(|value| {
+            scalar_token(value, "value").ok_or_else(|| {
+                invalid_type(object, "value", "an array of strings, numbers or booleans")
+            })
+        })
+        .collect()
+}
+
+fn expect_tokens<'a, const N: usize>(
+    object: &'a Map<String, Value>,
+    key: &'static str,
+) -> Result<[Token<'a>; N]> {
+    let values: [&Value; N] = expect_array_of(object, key)?;
+    let tokens = values.map(|value| scalar_token(value, key));
+    if tokens.iter().any(Option::is_none) {
+        return Err(invalid_type(object, key, "an array of strings or numbers"));
+    }
+    Ok(tokens.map(Option::unwrap))
+}
+
+fn point_tokens<'a>(
+    object: &'a Map<String, Value>,
+    key: &'static str,
+    point: &'a Value,
+) -> Result<[Token<'a>; 2]> {
+    match point.as_array().map(Vec::as_slice) {
+        Some([lat, lng]) => match (scalar_token(lat, key), scalar_token(lng, key)) {
+            (Some(lat), Some(lng)) => Ok([lat, lng]),
+            _ => Err(invalid_type(object, key, "an array of `[latitude, longitude]` points")),
+        },
+        _ => Err(invalid_type(object, key, "an array of `[latitude, longitude]` points")),
+    }
+}
+
+/// Converts a string, a number or a boolean into a token.
+///
+/// Numbers aren't borrowed from the JSON, so their token spans the `key` of the
+/// filter object they come from, which is what their errors point to, and carries
+/// the number as its value.
+fn scalar_token<'a>(value: &'a Value, key: &'static str) -> Option<Token<'a>> {
+    match value {
+        Value::String(s) => Some(Token::from(s.as_str())),
+        Value::Number(number) => {
+            Some(Token::new(Span::new_extra(key, key), Some(number.to_string())))
+        }
+        Value::Bool(true) => Some(Token::from("true")),
+        Value::Bool(false) => Some(Token::from("false")),
+        _ => None,
+    }
+}
+
+fn expect_str<'a>(object: &'a Map<String, Value>, key: &str) -> Result<&'a str> {
+    let value = object.get(key).ok_or_else(|| missing_key(object, key))?;
+    value.as_str().ok_or_else(|| invalid_type(object, key, "a string"))
+}
+
+fn expect_array<'a>(object: &'a Map<String, Value>, key: &str) -> Result<&'a [Value]> {
+    let value = object.get(key).ok_or_else(|| missing_key(object, key))?;
+    value.as_array().map(Vec::as_slice).ok_or_else(|| invalid_type(object, key, "an array"))
+}
+
+fn expect_array_of<'a, const N: usize>(
+    object: &'a Map<String, Value>,
+    key: &str,
+) -> Result<[&'a Value; N]> {
+    let values = expect_array(object, key)?;
+    let values: Vec<_> = values.iter().collect();
+    values.try_into().map_err(|_| invalid_type(object, key, &format!("an array of {N} elements")))
+}
+
+fn missing_key(object: &Map<String, Value>, key: &str) -> Error {
+    invalid_filter(format!(
+        "Missing `{key}` in the filter object `{}`.",
+        Value::Object(object.clone())
+    ))
+}
+
+fn invalid_type(object: &Map<String, Value>, key: &str, expected: &str) -> Error {
+    invalid_filter(format!(
+        "Invalid `{key}` in the filter object `{}`: expected {expected}.",
+        Value::Object(object.clone())
+    ))
+}
+
+fn invalid_filter(message: String) -> Error {
+    Error::UserError(UserError::InvalidFilter(message))
+}
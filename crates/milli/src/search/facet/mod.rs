@@ -4,7 +4,10 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    FacetDistribution, FacetGroup, FacetNumberStats, FacetRange, FacetRangeCount, OrderBy,
+    DEFAULT_VALUES_PER_FACET,
+};
 pub use self::filter::{BadGeoError, Filter};
 pub use self::search::{FacetValueHit, SearchForFacetValues};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
@@ -17,6 +20,7 @@ mod facet_range_search;
 mod facet_sort_ascending;
 mod facet_sort_descending;
 mod filter;
+mod filter_object;
 mod search;
 
 fn facet_extreme_value<'t>(
@@ -116,7 +116,12 @@ impl<'a> SearchForFacetValues<'a> {
 
         match self.query.as_ref() {
             Some(query) => {
-                let query = normalize_facet_string(query, self.locales.as_deref());
+                // the values of an exact facet are searched case-sensitively
+                let query = if index.exact_facets_ids(rtxn)?.contains(&fid) {
+                    query.to_string()
+                } else {
+                    normalize_facet_string(query, self.locales.as_deref())
+                };
                 let query = query.as_ref();
 
                 let authorize_typos = self.search_query.index.authorize_typos(rtxn)?;
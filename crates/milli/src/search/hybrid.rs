@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use itertools::Itertools;
 use roaring::RoaringBitmap;
 
-use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
+use crate::score_details::{Pinned, ScoreDetails, ScoreValue, ScoringStrategy};
 use crate::search::SemanticSearch;
 use crate::{MatchingWords, Result, Search, SearchResult};
 
@@ -106,8 +106,23 @@ impl ScoreWithRatioResult {
             vector_results.document_scores.len() + keyword_results.document_scores.len(),
         );
 
+        // Both searches return the documents pinned by the curation rules at their position,
+        // they are inserted back once the ranked documents are merged.
         let mut documents_seen = RoaringBitmap::new();
-        for ((docid, (main_score, _sub_score)), source) in vector_results
+        let mut pinned: Vec<_> = vector_results
+            .document_scores
+            .iter()
+            .chain(&keyword_results.document_scores)
+            .filter_map(|(docid, (scores, _))| match scores.as_slice() {
+                [ScoreDetails::Pinned(Pinned { position })] => Some((*position, *docid)),
+                _ => None,
+            })
+            .filter(|(_, docid)| documents_seen.insert(*docid))
+            .collect();
+        pinned.sort_unstable();
+        let mut pinned = pinned.into_iter().peekable();
+
+        let mut ranked = vector_results
             .document_scores
             .into_iter()
             .zip(std::iter::repeat(ResultSource::Semantic))
@@ -121,14 +136,33 @@ impl ScoreWithRatioResult {
                     compare_scores(left, right).is_ge()
                 },
             )
-            // remove documents we already saw
+            // remove documents we already saw, pinned documents included
             .filter(|((docid, _), _)| documents_seen.insert(*docid))
-            // start skipping **after** the filter
+            .peekable();
+
+        let mut position = 0;
+        let results = std::iter::from_fn(|| {
+            let next_pinned = pinned.next_if(|&(pinned_position, _)| {
+                pinned_position <= position || ranked.peek().is_none()
+            });
+            let next = match next_pinned {
+                Some((pinned_position, docid)) => {
+                    let score = ScoreDetails::Pinned(Pinned { position: pinned_position });
+                    ((docid, (vec![score], 1.0)), None)
+                }
+                None => ranked.next().map(|(document, source)| (document, Some(source)))?,
+            };
+            position += 1;
+            Some(next)
+        });
+
+        for ((docid, (main_score, _sub_score)), source) in results
+            // start skipping **after** the pinned documents are inserted
             .skip(from)
             // take **after** skipping
             .take(length)
         {
-            if let ResultSource::Semantic = source {
+            if let Some(ResultSource::Semantic) = source {
                 semantic_hit_count += 1;
             }
             documents_ids.push(docid);
@@ -142,6 +176,8 @@ impl ScoreWithRatioResult {
                 candidates: vector_results.candidates | keyword_results.candidates,
                 documents_ids,
                 document_scores,
+                // the merged results can't be resumed
+                cursor: None,
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
@@ -175,6 +211,7 @@ impl<'a> Search<'a> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            search_after: None,
         };
 
         let semantic = search.semantic.take();
@@ -186,7 +223,7 @@ impl<'a> Search<'a> {
         }
 
         // no vector search against placeholder search
-        let Some(query) = search.query.take() else {
+        let Some(query) = search.query.clone() else {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
         // no embedder, no semantic search
@@ -261,6 +298,7 @@ fn return_keyword_results(
         candidates,
         mut documents_ids,
         mut document_scores,
+        cursor: _,
         degraded,
         used_negative_operator,
     }: SearchResult,
@@ -287,6 +325,7 @@ fn return_keyword_results(
             candidates,
             documents_ids,
             document_scores,
+            cursor: None,
             degraded,
             used_negative_operator,
         },
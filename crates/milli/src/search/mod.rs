@@ -1,14 +1,16 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use charabia::Language;
+use charabia::{Language, Token, TokenizerBuilder};
+use fxhash::FxHasher;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
 pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult};
+use self::new::{execute_vector_search, PartialSearchResult, SearchCursor};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::Embedder;
 use crate::{
@@ -21,11 +23,13 @@ static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
 static LEVDIST1: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(1, true));
 static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 
+pub mod did_you_mean;
 pub mod facet;
 mod fst_utils;
 pub mod hybrid;
 pub mod new;
 pub mod similar;
+pub mod suggest;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
@@ -55,6 +59,7 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    search_after: Option<SearchCursor>,
 }
 
 impl<'a> Search<'a> {
@@ -78,6 +83,7 @@ impl<'a> Search<'a> {
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            search_after: None,
         }
     }
 
@@ -170,6 +176,16 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Resumes the search right after the documents returned by a previous search,
+    /// the offset is then counted from the cursor.
+    ///
+    /// The cursor must come from a search with the same parameters on the same version
+    /// of the index, otherwise the search fails.
+    pub fn search_after(&mut self, cursor: SearchCursor) -> &mut Search<'a> {
+        self.search_after = Some(cursor);
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
@@ -199,18 +215,25 @@ impl<'a> Search<'a> {
             }
         }
 
+        let fingerprint = self.cursor_fingerprint()?;
+        if self.search_after.as_ref().is_some_and(|cursor| cursor.fingerprint != fingerprint) {
+            return Err(UserError::InvalidSearchCursor.into());
+        }
+
         let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
         let PartialSearchResult {
             located_query_terms,
             candidates,
             documents_ids,
             document_scores,
+            mut cursor,
             degraded,
             used_negative_operator,
         } = match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder, quantized }) => {
                 execute_vector_search(
                     &mut ctx,
+                    self.query.as_deref(),
                     vector,
                     self.scoring_strategy,
                     universe,
@@ -224,6 +247,7 @@ impl<'a> Search<'a> {
                     *quantized,
                     self.time_budget.clone(),
                     self.ranking_score_threshold,
+                    self.search_after.as_ref(),
                 )?
             }
             _ => execute_search(
@@ -244,6 +268,7 @@ impl<'a> Search<'a> {
                 self.time_budget.clone(),
                 self.ranking_score_threshold,
                 self.locales.as_ref(),
+                self.search_after.as_ref(),
             )?,
         };
 
@@ -253,15 +278,38 @@ impl<'a> Search<'a> {
             None => MatchingWords::default(),
         };
 
+        cursor.fingerprint = fingerprint;
         Ok(SearchResult {
             matching_words,
             candidates,
             document_scores,
             documents_ids,
+            cursor: Some(cursor),
             degraded,
             used_negative_operator,
         })
     }
+
+    /// Hashes everything the order of the results depends on: the search parameters,
+    /// the ranking rules and the last update of the index.
+    fn cursor_fingerprint(&self) -> Result<u64> {
+        let mut hasher = FxHasher::default();
+        self.query.hash(&mut hasher);
+        format!("{:?}", self.filter).hash(&mut hasher);
+        format!("{:?}", self.sort_criteria).hash(&mut hasher);
+        self.distinct.hash(&mut hasher);
+        self.searchable_attributes.hash(&mut hasher);
+        format!("{:?}", self.terms_matching_strategy).hash(&mut hasher);
+        format!("{:?}", self.locales).hash(&mut hasher);
+        self.ranking_score_threshold.map(f64::to_bits).hash(&mut hasher);
+        if let Some(SemanticSearch { vector, embedder_name, .. }) = &self.semantic {
+            embedder_name.hash(&mut hasher);
+            vector.iter().flatten().for_each(|value| value.to_bits().hash(&mut hasher));
+        }
+        format!("{:?}", self.index.criteria(self.rtxn)?).hash(&mut hasher);
+        self.index.updated_at(self.rtxn)?.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
 }
 
 impl fmt::Debug for Search<'_> {
@@ -285,6 +333,7 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            search_after,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -306,6 +355,7 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("search_after", search_after)
             .finish()
     }
 }
@@ -316,6 +366,9 @@ pub struct SearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// The cursor to resume the search after the returned documents,
+    /// `None` if the search can't be resumed.
+    pub cursor: Option<SearchCursor>,
     pub degraded: bool,
     pub used_negative_operator: bool,
 }
@@ -357,6 +410,33 @@ pub fn build_dfa(word: &str, typos: u8, is_prefix: bool) -> DFA {
     }
 }
 
+/// Tokenizes a search query with the stop words, separators and dictionary of the index.
+fn tokenize_query<'o>(
+    index: &Index,
+    rtxn: &heed::RoTxn<'_>,
+    query: &'o str,
+) -> Result<Vec<Token<'o>>> {
+    let mut tokbuilder = TokenizerBuilder::new();
+    let stop_words = index.stop_words(rtxn)?;
+    if let Some(ref stop_words) = stop_words {
+        tokbuilder.stop_words(stop_words);
+    }
+    let separators = index.allowed_separators(rtxn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        tokbuilder.separators(separators);
+    }
+    let dictionary = index.dictionary(rtxn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        tokbuilder.words_dict(dictionary);
+    }
+    let tokenizer = tokbuilder.build();
+    Ok(tokenizer.tokenize(query).collect())
+}
+
 #[cfg(test)]
 mod test {
     #[allow(unused_imports)]
@@ -0,0 +1,85 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, ScoreDetails};
+use crate::{Filter, Index, Result};
+
+/// Ranks the documents matching a filter before the other ones, without filtering them out.
+pub struct Boost<Query> {
+    filter: String,
+    /// The documents of the whole index matching the filter.
+    matching: RoaringBitmap,
+    original_query: Option<Query>,
+    matching_returned: bool,
+}
+
+impl<Query> Boost<Query> {
+    pub fn new(index: &Index, rtxn: &heed::RoTxn<'_>, filter: String) -> Result<Self> {
+        let matching = match Filter::from_str(&filter)? {
+            Some(parsed) => parsed.evaluate(rtxn, index)?,
+            None => RoaringBitmap::new(),
+        };
+
+        Ok(Self { filter, matching, original_query: None, matching_returned: false })
+    }
+
+    fn score_details(&self, matched: bool) -> ScoreDetails {
+        ScoreDetails::Boost(score_details::Boost { filter: self.filter.clone(), matched })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Boost<Query> {
+    fn id(&self) -> String {
+        format!("boost({})", self.filter)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        _parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        self.original_query = Some(parent_query.clone());
+        self.matching_returned = false;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        if !self.matching_returned {
+            self.matching_returned = true;
+            let candidates = universe & &self.matching;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates,
+                    score: self.score_details(true),
+                }));
+            }
+        }
+
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe - &self.matching,
+            score: self.score_details(false),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+    }
+}
@@ -1,20 +1,39 @@
+use std::cmp::Ordering;
+
 use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 
 use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
-use crate::{Result, TimeBudget};
+use crate::{DocumentId, Result, TimeBudget};
 
 pub struct BucketSortOutput {
     pub docids: Vec<u32>,
     pub scores: Vec<Vec<ScoreDetails>>,
     pub all_candidates: RoaringBitmap,
+    /// The position of the last returned document, if any.
+    pub last_position: Option<BucketPosition>,
 
     pub degraded: bool,
 }
 
+/// The position of a document in the bucket sort.
+///
+/// The bucket sort being deterministic, a search can be resumed right after
+/// a document by skipping the buckets that were sorted before it. The ranking
+/// rules still yield these buckets, but they are never sorted any deeper.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BucketPosition {
+    /// For each ranking rule, the index of the bucket containing the document,
+    /// among the buckets yielded by the ranking rule for the parent bucket.
+    pub buckets: Vec<usize>,
+    /// The documents of a bucket are sorted by id.
+    pub docid: DocumentId,
+}
+
 // TODO: would probably be good to regroup some of these inside of a struct?
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "search::bucket_sort")]
@@ -30,6 +49,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     logger: &mut dyn SearchLogger<Q>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    search_after: Option<&BucketPosition>,
 ) -> Result<BucketSortOutput> {
     logger.initial_query(query);
     logger.ranking_rules(&ranking_rules);
@@ -51,15 +71,22 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             docids: vec![],
             scores: vec![],
             all_candidates: universe.clone(),
+            last_position: None,
             degraded: false,
         });
     }
     if ranking_rules.is_empty() {
+        // without ranking rules, the documents are sorted by id in a single bucket
+        let is_after_cursor = |docid| search_after.map_or(true, |cursor| docid > cursor.docid);
+        let last_position = |results: &[u32]| {
+            results.last().map(|&docid| BucketPosition { buckets: vec![], docid })
+        };
         if let Some(distinct_fid) = distinct_fid {
             let mut excluded = RoaringBitmap::new();
             let mut results = vec![];
+            let mut before_cursor = 0;
             for docid in universe.iter() {
-                if results.len() >= from + length {
+                if results.len() >= before_cursor + from + length {
                     break;
                 }
                 if excluded.contains(docid) {
@@ -68,6 +95,9 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
                 distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
                 results.push(docid);
+                if !is_after_cursor(docid) {
+                    before_cursor += 1;
+                }
             }
 
             let mut all_candidates = universe - excluded;
@@ -75,22 +105,29 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             // drain the results of the skipped elements
             // this **must** be done **after** writing the entire results in `all_candidates` to ensure
             // e.g. estimatedTotalHits is correct.
-            if results.len() >= from {
-                results.drain(..from);
+            if results.len() >= before_cursor + from {
+                results.drain(..before_cursor + from);
             } else {
                 results.clear();
             }
 
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); results.len()],
+                last_position: last_position(&results),
                 docids: results,
                 all_candidates,
                 degraded: false,
             });
         } else {
-            let docids: Vec<u32> = universe.iter().skip(from).take(length).collect();
+            let docids: Vec<u32> = universe
+                .iter()
+                .skip_while(|&docid| !is_after_cursor(docid))
+                .skip(from)
+                .take(length)
+                .collect();
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); docids.len()],
+                last_position: last_position(&docids),
                 docids,
                 all_candidates: universe.clone(),
                 degraded: false,
@@ -145,8 +182,16 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     let mut valid_scores = vec![];
     let mut cur_offset = 0usize;
 
+    // The index of the next bucket of each ranking rule, and the index of the bucket
+    // currently sorted by each ranking rule, to compute the position of the documents.
+    let mut next_bucket_indexes = vec![0usize; ranking_rules_len];
+    let mut bucket_path = vec![0usize; ranking_rules_len];
+    let mut last_position = None;
+    let cursor_depth = search_after.map_or(0, |cursor| cursor.buckets.len());
+
     macro_rules! maybe_add_to_results {
-        ($candidates:expr) => {
+        ($candidates:expr) => {{
+            let previous_len = valid_docids.len();
             maybe_add_to_results(
                 ctx,
                 from,
@@ -163,13 +208,89 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &ranking_rule_scores,
                 $candidates,
             )?;
+            if valid_docids.len() > previous_len {
+                last_position = valid_docids.last().map(|&docid| BucketPosition {
+                    buckets: bucket_path[..=cur_ranking_rule_index].to_vec(),
+                    docid,
+                });
+            }
+        }};
+    }
+
+    /// Skip the candidates sorted before the `search_after` document. They only go through
+    /// the distinct rule, to exclude the documents sharing their distinct value, and are
+    /// simply dropped when there is no distinct attribute.
+    macro_rules! skip_before_cursor {
+        ($candidates:expr) => {
+            if distinct_fid.is_some() {
+                maybe_add_to_results(
+                    ctx,
+                    usize::MAX,
+                    length,
+                    logger,
+                    &mut valid_docids,
+                    &mut valid_scores,
+                    &mut all_candidates,
+                    &mut ranking_rule_universes,
+                    &mut ranking_rules,
+                    cur_ranking_rule_index,
+                    &mut 0,
+                    distinct_fid,
+                    &ranking_rule_scores,
+                    $candidates,
+                )?;
+            } else {
+                logger.skip_bucket_ranking_rule(
+                    cur_ranking_rule_index,
+                    ranking_rules[cur_ranking_rule_index].as_ref(),
+                    &$candidates,
+                );
+            }
         };
     }
 
+    /// Register the next bucket of the current ranking rule and compare its index to the one
+    /// of the bucket of the `search_after` document, if they are in the same parent bucket.
+    macro_rules! next_bucket_position {
+        () => {{
+            let level = cur_ranking_rule_index;
+            let index = next_bucket_indexes[level];
+            next_bucket_indexes[level] += 1;
+            bucket_path[level] = index;
+            search_after.and_then(|cursor| {
+                (level < cursor.buckets.len() && cursor.buckets[..level] == bucket_path[..level])
+                    .then(|| index.cmp(&cursor.buckets[level]))
+            })
+        }};
+    }
+
+    /// Add a bucket to the results, skipping its documents sorted before the `search_after` one.
+    macro_rules! add_bucket_after_cursor {
+        ($candidates:expr, $position:expr) => {{
+            let mut candidates = $candidates;
+            match (search_after, $position) {
+                (_, Some(Ordering::Less)) => {
+                    skip_before_cursor!(candidates);
+                }
+                (Some(cursor), Some(Ordering::Equal)) => {
+                    let before: RoaringBitmap =
+                        candidates.iter().take_while(|&docid| docid <= cursor.docid).collect();
+                    candidates -= &before;
+                    skip_before_cursor!(before);
+                    // the skipped documents may have excluded some candidates
+                    candidates &= &all_candidates;
+                    maybe_add_to_results!(candidates);
+                }
+                _ => maybe_add_to_results!(candidates),
+            }
+        }};
+    }
+
     while valid_docids.len() < length {
         if time_budget.exceeded() {
             loop {
                 let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
+                let position = next_bucket_position!();
                 ranking_rule_scores.push(ScoreDetails::Skipped);
 
                 // remove candidates from the universe without adding them to result if their score is below the threshold
@@ -182,7 +303,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     }
                 }
 
-                maybe_add_to_results!(bucket);
+                add_bucket_after_cursor!(bucket, position);
 
                 ranking_rule_scores.pop();
 
@@ -197,6 +318,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 scores: valid_scores,
                 docids: valid_docids,
                 all_candidates,
+                last_position,
                 degraded: true,
             });
         }
@@ -208,7 +330,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 && ranking_rule_universes[cur_ranking_rule_index].len() == 1)
         {
             let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
-            maybe_add_to_results!(bucket);
+            let position = next_bucket_position!();
+            add_bucket_after_cursor!(bucket, position);
             back!();
             continue;
         }
@@ -223,6 +346,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             continue;
         };
 
+        let position = next_bucket_position!();
         ranking_rule_scores.push(next_bucket.score);
 
         logger.next_bucket_ranking_rule(
@@ -249,16 +373,35 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
         ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
 
-        if cur_ranking_rule_index == ranking_rules_len - 1
-            || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
-            || cur_offset + (next_bucket.candidates.len() as usize) < from
-        {
-            maybe_add_to_results!(next_bucket.candidates);
+        if position == Some(Ordering::Less) {
+            // the whole bucket was sorted before the `search_after` document
+            skip_before_cursor!(next_bucket.candidates);
+            ranking_rule_scores.pop();
+            continue;
+        }
+
+        let add_bucket = match position {
+            // the bucket containing the `search_after` document must be sorted
+            // as deep as it was when the document was returned
+            Some(Ordering::Equal) => {
+                cur_ranking_rule_index + 1 >= cursor_depth
+                    || cur_ranking_rule_index == ranking_rules_len - 1
+            }
+            _ => {
+                cur_ranking_rule_index == ranking_rules_len - 1
+                    || (scoring_strategy == ScoringStrategy::Skip
+                        && next_bucket.candidates.len() <= 1)
+                    || cur_offset + (next_bucket.candidates.len() as usize) < from
+            }
+        };
+        if add_bucket {
+            add_bucket_after_cursor!(next_bucket.candidates, position);
             ranking_rule_scores.pop();
             continue;
         }
 
         cur_ranking_rule_index += 1;
+        next_bucket_indexes[cur_ranking_rule_index] = 0;
         ranking_rule_universes[cur_ranking_rule_index].clone_from(&next_bucket.candidates);
         logger.start_iteration_ranking_rule(
             cur_ranking_rule_index,
@@ -278,6 +421,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         docids: valid_docids,
         scores: valid_scores,
         all_candidates,
+        last_position,
         degraded: false,
     })
 }
@@ -0,0 +1,168 @@
+use roaring::RoaringBitmap;
+
+use super::SearchContext;
+use crate::score_details::{self, ScoreDetails};
+use crate::{DocumentId, PinnedDocument, Result};
+
+/// The documents pinned and hidden by the curation rules matching a search query.
+#[derive(Default)]
+pub struct Curation {
+    /// The pinned documents and their position in the results, sorted by position.
+    ///
+    /// Two documents never share the same position.
+    pinned: Vec<(usize, DocumentId)>,
+    hidden: RoaringBitmap,
+}
+
+impl Curation {
+    /// Collects the documents pinned and hidden by the rules matching the query.
+    ///
+    /// Only the documents of the universe can be pinned, so that the filters are respected.
+    /// A document that is both pinned and hidden is hidden.
+    pub fn new(ctx: &SearchContext<'_>, query: &str, universe: &RoaringBitmap) -> Result<Self> {
+        let rules = ctx.index.curation_rules(ctx.txn)?;
+        let external_documents_ids = ctx.index.external_documents_ids();
+
+        let mut pinned = Vec::new();
+        let mut hidden = RoaringBitmap::new();
+        for rule in rules.iter().filter(|rule| rule.matches(query)) {
+            for id in &rule.hidden {
+                if let Some(docid) = external_documents_ids.get(ctx.txn, id)? {
+                    hidden.insert(docid);
+                }
+            }
+            for PinnedDocument { id, position } in &rule.pinned {
+                if let Some(docid) = external_documents_ids.get(ctx.txn, id)? {
+                    pinned.push((*position, docid));
+                }
+            }
+        }
+
+        let mut already_pinned = RoaringBitmap::new();
+        pinned.retain(|&(_, docid)| {
+            universe.contains(docid) && !hidden.contains(docid) && already_pinned.insert(docid)
+        });
+        pinned.sort_by_key(|&(position, _)| position);
+        // documents pinned at the same position are returned one after the other
+        let mut next_position = 0;
+        for (position, _) in &mut pinned {
+            *position = (*position).max(next_position);
+            next_position = *position + 1;
+        }
+
+        Ok(Self { pinned, hidden })
+    }
+
+    /// The documents that must not be ranked by the ranking rules.
+    pub fn excluded(&self) -> RoaringBitmap {
+        &self.hidden | self.pinned_docids()
+    }
+
+    pub fn pinned_docids(&self) -> RoaringBitmap {
+        self.pinned.iter().map(|&(_, docid)| docid).collect()
+    }
+
+    /// Returns the page of ranked documents that, once the pinned documents are inserted,
+    /// results in the requested page.
+    ///
+    /// `ranked_before_cursor` is the number of ranked documents returned before the cursor
+    /// the bucket sort resumes from.
+    pub fn ranked_page(
+        &self,
+        from: usize,
+        length: usize,
+        ranked_before_cursor: usize,
+    ) -> RankedPage {
+        let pinned_before = self.pinned.iter().filter(|&&(position, _)| position < from).count();
+        let pinned_within = self
+            .pinned
+            .iter()
+            .filter(|&&(position, _)| (from..from.saturating_add(length)).contains(&position))
+            .count();
+        let ranked_from = from - pinned_before;
+        // The documents pinned after the last ranked document are returned right after it,
+        // finding where the ranked documents end requires the ranked documents that precede
+        // the page, up to one per document that could be moved back that way.
+        let look_back = (self.pinned.len() - pinned_before).min(ranked_from - ranked_before_cursor);
+        RankedPage {
+            from: ranked_from - ranked_before_cursor - look_back,
+            length: length - pinned_within + look_back,
+            ranked_from,
+            look_back,
+        }
+    }
+
+    /// Inserts the pinned documents at their position in the page of ranked documents.
+    ///
+    /// When there are not enough ranked documents to reach the position of a pinned
+    /// document, it is returned right after the last ranked document, only once.
+    ///
+    /// Returns the documents of the page and the number of ranked documents up to
+    /// the end of the page.
+    pub fn insert_pinned(
+        &self,
+        page: &RankedPage,
+        from: usize,
+        length: usize,
+        mut docids: Vec<DocumentId>,
+        mut scores: Vec<Vec<ScoreDetails>>,
+    ) -> (Vec<DocumentId>, Vec<Vec<ScoreDetails>>, usize) {
+        // the ranked documents end within the page if the bucket sort didn't fill it
+        let ranked_end = (docids.len() < page.length)
+            .then_some(page.ranked_from - page.look_back + docids.len());
+        let look_back = page.look_back.min(docids.len());
+        docids.drain(..look_back);
+        scores.drain(..look_back);
+        let ranked_before = ranked_end.map_or(page.ranked_from, |end| end.min(page.ranked_from));
+        let ranked = ranked_before + docids.len();
+
+        if self.pinned.is_empty() {
+            return (docids, scores, ranked);
+        }
+
+        // a pinned document can't be further than the number of documents before it
+        let mut pinned = self
+            .pinned
+            .iter()
+            .enumerate()
+            .map(|(i, &(position, docid))| match ranked_end {
+                Some(end) => (position.min(end + i), position, docid),
+                None => (position, position, docid),
+            })
+            .filter(|&(actual_position, _, _)| actual_position >= from)
+            .peekable();
+        let mut ranked_docs = docids.into_iter().zip(scores).peekable();
+        let mut page_docids = Vec::new();
+        let mut page_scores = Vec::new();
+        while page_docids.len() < length {
+            let current_position = from + page_docids.len();
+            let next_pinned =
+                pinned.next_if(|&(actual_position, _, _)| actual_position <= current_position);
+            let (docid, score) = match next_pinned {
+                Some((_, position, docid)) => {
+                    (docid, vec![ScoreDetails::Pinned(score_details::Pinned { position })])
+                }
+                None => match ranked_docs.next() {
+                    Some(ranked) => ranked,
+                    None => break,
+                },
+            };
+            page_docids.push(docid);
+            page_scores.push(score);
+        }
+
+        (page_docids, page_scores, ranked)
+    }
+}
+
+/// The documents to request from the bucket sort to compute a page of results.
+pub struct RankedPage {
+    /// The number of ranked documents to skip, counted from the cursor.
+    pub from: usize,
+    /// The number of ranked documents to return.
+    pub length: usize,
+    /// The number of ranked documents before the page.
+    ranked_from: usize,
+    /// The number of ranked documents requested before the page.
+    look_back: usize,
+}
@@ -0,0 +1,138 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use heed::BytesDecode;
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::BytesRefCodec;
+use crate::score_details::{self, ScoreDetails};
+use crate::search::facet::ascending_facet_sort;
+use crate::{Decay as DecayCriterion, FieldId, Index, Result};
+
+/// The key of a bucket, from the best to the worst.
+///
+/// Documents are grouped by their quantized decay score, unless the decay has no scale
+/// (`closeTo`), in which case each distinct distance to the origin gets its own bucket.
+type BucketKey = (Reverse<u32>, OrderedFloat<f64>);
+
+/// Ranks the documents by the decay of the distance between the numbers
+/// of a field and an origin, see [`DecayCriterion`].
+pub struct Decay<Query> {
+    criterion: DecayCriterion,
+    field_id: Option<FieldId>,
+    /// The origin is resolved once per search, so that `now` is the same for all the documents.
+    origin: f64,
+    original_query: Option<Query>,
+    buckets: std::vec::IntoIter<(score_details::Decay, RoaringBitmap)>,
+    must_redact: bool,
+}
+
+impl<Query> Decay<Query> {
+    pub fn new(index: &Index, rtxn: &heed::RoTxn<'_>, criterion: DecayCriterion) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = fields_ids_map.id(&criterion.field);
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !displayed_fields.contains(&criterion.field.as_str()),
+            None => false,
+        };
+        let origin = criterion.origin.resolve();
+
+        Ok(Self {
+            criterion,
+            field_id,
+            origin,
+            original_query: None,
+            buckets: Vec::new().into_iter(),
+            must_redact,
+        })
+    }
+
+    fn score_details(&self, score: Option<f64>) -> score_details::Decay {
+        score_details::Decay::from_score(self.criterion.to_string(), self.must_redact, score)
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Decay<Query> {
+    fn id(&self) -> String {
+        self.criterion.to_string()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let mut buckets: BTreeMap<BucketKey, (score_details::Decay, RoaringBitmap)> =
+            BTreeMap::new();
+
+        if let Some(field_id) = self.field_id {
+            let number_db =
+                ctx.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+            for result in
+                ascending_facet_sort(ctx.txn, number_db, field_id, parent_candidates.clone())?
+            {
+                let (docids, bytes) = result?;
+                let value = OrderedF64Codec::bytes_decode(bytes).expect("some number");
+                let details = self.score_details(Some(self.criterion.score(self.origin, value)));
+                let distance = match self.criterion.scale {
+                    Some(_) => 0.0,
+                    None => (value - self.origin).abs(),
+                };
+                let key = (Reverse(details.rank.rank), OrderedFloat(distance));
+                let (_, bucket) =
+                    buckets.entry(key).or_insert_with(|| (details, RoaringBitmap::new()));
+                *bucket |= docids;
+            }
+        }
+
+        self.original_query = Some(parent_query.clone());
+        self.buckets = buckets.into_values().collect::<Vec<_>>().into_iter();
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        // a document with several values is returned in the bucket of its best value,
+        // it is then removed from the universe of the following buckets.
+        for (details, candidates) in self.buckets.by_ref() {
+            let candidates = candidates & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates,
+                    score: ScoreDetails::Decay(details),
+                }));
+            }
+        }
+
+        // the remaining documents don't have a number in the field
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe.clone(),
+            score: ScoreDetails::Decay(self.score_details(None)),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = Vec::new().into_iter();
+    }
+}
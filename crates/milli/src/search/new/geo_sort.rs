@@ -5,14 +5,17 @@ use heed::types::{Bytes, Unit};
 use heed::{RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
 use rstar::RTree;
+use serde_json::value::RawValue;
 
 use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
 use crate::score_details::{self, ScoreDetails};
+use crate::update::new::extract_geo_coordinates;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, InternalError, Result,
+    SearchContext, SearchLogger,
 };
 
 const FID_SIZE: usize = 2;
@@ -79,7 +82,8 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     strategy: Strategy,
     ascending: bool,
     point: [f64; 2],
-    field_ids: Option<[u16; 2]>,
+    /// The field ids of `_geo.lat`, `_geo.lng` and `_geo`.
+    field_ids: Option<[u16; 3]>,
     rtree: Option<RTree<GeoPoint>>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
@@ -132,16 +136,30 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
 
         let cache_size = self.strategy.cache_size();
         if let Some(rtree) = rtree {
+            // the documents located at several places are ranked by their closest point
+            // thus we only keep the first point of a document we encounter.
+            let mut seen = RoaringBitmap::new();
             if self.ascending {
                 let point = lat_lng_to_xyz(&self.point);
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
+                    if geo_candidates.contains(point.data.0) && seen.insert(point.data.0) {
                         self.cached_sorted_docids.push_back(point.data);
                         if self.cached_sorted_docids.len() >= cache_size {
                             break;
                         }
                     }
                 }
+            } else if rtree.size() as u64 != self.geo_candidates.len() {
+                // some documents have several points, the farthest point of a document from
+                // the queried point doesn't tell us how far its closest point is, we must
+                // sort all of them and we insert them in ascending order as they get
+                // reversed when emptying the cache later on
+                let point = lat_lng_to_xyz(&self.point);
+                for point in rtree.nearest_neighbor_iter(&point) {
+                    if geo_candidates.contains(point.data.0) && seen.insert(point.data.0) {
+                        self.cached_sorted_docids.push_back(point.data);
+                    }
+                }
             } else {
                 // in the case of the desc geo sort we look for the closest point to the opposite of the queried point
                 // and we insert the points in reverse order they get reversed when emptying the cache later on
@@ -157,51 +175,83 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             }
         } else {
             // the iterative version
-            let [lat, lng] = self.field_ids.unwrap();
+            let field_ids = self.field_ids.unwrap();
 
             let mut documents = geo_candidates
                 .iter()
-                .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
-                .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
-            // computing the distance between two points is expensive thus we cache the result
-            documents
-                .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
-            self.cached_sorted_docids.extend(documents);
+                .map(|id| -> Result<_> {
+                    // computing the distance between two points is expensive thus we cache the result
+                    let closest = geo_values(id, field_ids, ctx.index, ctx.txn)?
+                        .into_iter()
+                        .map(|p| (p, distance_between_two_points(&self.point, &p)))
+                        .min_by(|(_, left), (_, right)| left.total_cmp(right))
+                        .expect("A geo faceted document doesn't contain any point");
+                    Ok((id, closest))
+                })
+                .collect::<Result<Vec<(u32, ([f64; 2], f64))>>>()?;
+            documents.sort_by_key(|(_, (_, distance))| *distance as usize);
+            self.cached_sorted_docids.extend(documents.into_iter().map(|(id, (p, _))| (id, p)));
         };
 
         Ok(())
     }
 }
 
-/// Extracts the lat and long values from a single document.
+/// Extracts the lat and lng values of each point of a single document.
 ///
-/// If it is not able to find it in the facet number index it will extract it
-/// from the facet string index and parse it as f64 (as the geo extraction behaves).
-fn geo_value(
+/// The points are read from the facet databases. These databases don't keep which latitude
+/// goes with which longitude, so the points of a document located at several places are
+/// read from the document itself.
+fn geo_values(
     docid: u32,
-    field_lat: u16,
-    field_lng: u16,
+    [field_lat, field_lng, field_geo]: [u16; 3],
     index: &Index,
     rtxn: &RoTxn<'_>,
-) -> Result<[f64; 2]> {
-    let extract_geo = |geo_field: u16| -> Result<f64> {
-        match facet_number_values(docid, geo_field, index, rtxn)?.next() {
-            Some(Ok(((_, _, geo), ()))) => Ok(geo),
-            Some(Err(e)) => Err(e.into()),
-            None => match facet_string_values(docid, geo_field, index, rtxn)?.next() {
-                Some(Ok((_, geo))) => {
-                    Ok(geo.parse::<f64>().expect("cannot parse geo field as f64"))
-                }
-                Some(Err(e)) => Err(e.into()),
-                None => panic!("A geo faceted document doesn't contain any lat or lng"),
-            },
-        }
-    };
+) -> Result<Vec<[f64; 2]>> {
+    let lats = geo_facet_values(docid, field_lat, index, rtxn)?;
+    let lngs = geo_facet_values(docid, field_lng, index, rtxn)?;
+    match (lats.as_slice(), lngs.as_slice()) {
+        ([lat], [lng]) => Ok(vec![[*lat, *lng]]),
+        _ => document_geo_values(docid, field_geo, index, rtxn),
+    }
+}
 
-    let lat = extract_geo(field_lat)?;
-    let lng = extract_geo(field_lng)?;
+/// Extracts the values of a `_geo.lat` or `_geo.lng` field of a single document.
+///
+/// The values that are not in the facet number index are extracted from the facet
+/// string index and parsed as f64 (as the geo extraction behaves).
+fn geo_facet_values(
+    docid: u32,
+    field_id: u16,
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+) -> Result<Vec<f64>> {
+    let mut values = Vec::new();
+    for result in facet_number_values(docid, field_id, index, rtxn)? {
+        let ((_, _, value), ()) = result?;
+        values.push(value);
+    }
+    for result in facet_string_values(docid, field_id, index, rtxn)? {
+        let (_, value) = result?;
+        values.push(value.parse::<f64>().expect("cannot parse geo field as f64"));
+    }
+    Ok(values)
+}
 
-    Ok([lat, lng])
+/// Extracts the points of a single document from its `_geo` field.
+fn document_geo_values(
+    docid: u32,
+    field_id: u16,
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+) -> Result<Vec<[f64; 2]>> {
+    let document = index.document(rtxn, docid)?;
+    let Some(geo) = document.get(field_id) else {
+        return Ok(Vec::new());
+    };
+    let geo: &RawValue = serde_json::from_slice(geo).map_err(InternalError::SerdeJson)?;
+    // the geo field has been validated when indexing the document
+    extract_geo_coordinates(&docid.to_string(), geo)
 }
 
 impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
@@ -230,7 +280,8 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
         let fid_map = ctx.index.fields_ids_map(ctx.txn)?;
         let lat = fid_map.id("_geo.lat").expect("geo candidates but no fid for lat");
         let lng = fid_map.id("_geo.lng").expect("geo candidates but no fid for lng");
-        self.field_ids = Some([lat, lng]);
+        let geo = fid_map.id(RESERVED_GEO_FIELD_NAME).expect("geo candidates but no fid for _geo");
+        self.field_ids = Some([lat, lng, geo]);
         self.fill_buffer(ctx, &geo_candidates)?;
         Ok(())
     }
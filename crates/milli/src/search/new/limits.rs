@@ -3,6 +3,8 @@ pub const MAX_TOKEN_COUNT: usize = 1_000;
 
 /// Maximum number of prefixes that can be derived from a single word.
 pub const MAX_PREFIX_COUNT: usize = 1_000;
+/// Maximum number of words containing a single word that can be derived from it, e.g. `*4471*`.
+pub const MAX_INFIX_COUNT: usize = 1_000;
 /// Maximum number of words that can be derived from a single word with a distance of one to that word.
 pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
@@ -15,3 +17,6 @@ pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
 ///
 /// This limit is meant to gracefully handle the case where a word would have very long phrases as synonyms.
 pub const MAX_SYNONYM_WORD_COUNT: usize = 100;
+
+/// Maximum number of extra positions that can separate the words of a sloppy phrase, e.g. `"quick fox"~3`.
+pub const MAX_PHRASE_SLOP: u8 = 64;
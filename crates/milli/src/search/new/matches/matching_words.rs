@@ -40,9 +40,15 @@ impl MatchingWords {
         // and wrap them in dedicated structures.
         for located_term in located_terms {
             let term = ctx.term_interner.get(located_term.value);
-            let (matching_words, matching_phrases) = term.all_computed_derivations();
+            let (mut matching_words, matching_phrases) = term.all_computed_derivations();
 
             for matching_phrase in matching_phrases {
+                let phrase = ctx.phrase_interner.get(matching_phrase);
+                // The words of a sloppy phrase are not side-by-side, we match them individually.
+                if phrase.slop > 0 {
+                    matching_words.extend(phrase.words.iter().flatten().copied());
+                    continue;
+                }
                 phrases.push(LocatedMatchingPhrase {
                     value: matching_phrase,
                     positions: located_term.positions.clone(),
@@ -242,6 +248,7 @@ pub(crate) mod tests {
     use super::*;
     use crate::index::tests::TempIndex;
     use crate::search::new::query_term::ExtractedTokens;
+    use crate::TimeBudget;
 
     pub(crate) fn temp_index_with_documents() -> TempIndex {
         let temp_index = TempIndex::new();
@@ -262,9 +269,11 @@ pub(crate) mod tests {
         let mut ctx = SearchContext::new(&temp_index, &rtxn).unwrap();
         let mut builder = TokenizerBuilder::default();
         let tokenizer = builder.build();
-        let tokens = tokenizer.tokenize("split this world");
+        let query = "split this world";
+        let tokens = tokenizer.tokenize(query);
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None).unwrap();
+            located_query_terms_from_tokens(&mut ctx, tokens, query, None, &TimeBudget::max())
+                .unwrap();
         let matching_words = MatchingWords::new(ctx, query_terms);
 
         assert_eq!(
@@ -510,6 +510,7 @@ mod tests {
                 TimeBudget::max(),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1,10 +1,12 @@
+mod boost;
 mod bucket_sort;
+mod curation;
 mod db_cache;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
 mod interner;
-mod limits;
+pub(crate) mod limits;
 mod logger;
 pub mod matches;
 mod query_graph;
@@ -14,6 +16,7 @@ mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
 
+mod decay;
 mod exact_attribute;
 mod sort;
 mod vector_sort;
@@ -23,9 +26,12 @@ mod tests;
 
 use std::collections::HashSet;
 
-use bucket_sort::{bucket_sort, BucketSortOutput};
+use boost::Boost;
+use bucket_sort::{bucket_sort, BucketPosition, BucketSortOutput};
 use charabia::{Language, TokenizerBuilder};
+use curation::Curation;
 use db_cache::DatabaseCache;
+use decay::Decay;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
@@ -41,6 +47,7 @@ use ranking_rules::{
 };
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 use sort::Sort;
 
 use self::distinct::facet_string_values;
@@ -71,6 +78,8 @@ pub struct SearchContext<'ctx> {
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
+    /// The fields in which the words can be matched by their middle or their end
+    pub infix_searchable_fids: Vec<FieldId>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -89,6 +98,9 @@ impl<'ctx> SearchContext<'ctx> {
         }
 
         let prefix_search = index.prefix_search(txn)?.unwrap_or_default();
+        let mut infix_searchable_fids: Vec<_> =
+            index.infix_searchable_attributes_ids(txn)?.into_iter().collect();
+        infix_searchable_fids.sort_unstable();
 
         Ok(Self {
             index,
@@ -100,6 +112,7 @@ impl<'ctx> SearchContext<'ctx> {
             phrase_docids: <_>::default(),
             restricted_fids: None,
             prefix_search,
+            infix_searchable_fids,
         })
     }
 
@@ -317,6 +330,12 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, decay)?));
+            }
+            crate::Criterion::Boost(filter) => {
+                ranking_rules.push(Box::new(Boost::new(ctx.index, ctx.txn, filter)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -393,6 +412,12 @@ fn get_ranking_rules_for_vector<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, decay)?));
+            }
+            crate::Criterion::Boost(filter) => {
+                ranking_rules.push(Box::new(Boost::new(ctx.index, ctx.txn, filter)?));
+            }
         }
     }
 
@@ -503,6 +528,12 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, decay)?));
+            }
+            crate::Criterion::Boost(filter) => {
+                ranking_rules.push(Box::new(Boost::new(ctx.index, ctx.txn, filter)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -579,9 +610,10 @@ pub fn filtered_universe(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_vector_search(
     ctx: &mut SearchContext<'_>,
+    query: Option<&str>,
     vector: &[f32],
     scoring_strategy: ScoringStrategy,
-    universe: RoaringBitmap,
+    mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
     distinct: &Option<String>,
     geo_strategy: geo_sort::Strategy,
@@ -592,9 +624,24 @@ pub fn execute_vector_search(
     quantized: bool,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
+    let curation = match query {
+        Some(query) => Curation::new(ctx, query, &universe)?,
+        None => Curation::default(),
+    };
+    universe -= curation.excluded();
+    let from = search_after.map_or(0, |cursor| cursor.offset) + from;
+    let last_ranked = search_after.and_then(|cursor| cursor.last_ranked.as_ref());
+    // the bucket sort resumes after the last ranked document of the cursor
+    let ranked_before_cursor = match (search_after, last_ranked) {
+        (Some(cursor), Some(_)) => cursor.ranked,
+        _ => 0,
+    };
+    let ranked_page = curation.ranked_page(from, length, ranked_before_cursor);
+
     // FIXME: input universe = universe & documents_with_vectors
     // for now if we're computing embeddings for ALL documents, we can assume that this is just universe
     let ranking_rules = get_ranking_rules_for_vector(
@@ -612,25 +659,37 @@ pub fn execute_vector_search(
     let placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery> =
         &mut placeholder_search_logger;
 
-    let BucketSortOutput { docids, scores, all_candidates, degraded } = bucket_sort(
-        ctx,
-        ranking_rules,
-        &PlaceholderQuery,
-        distinct.as_deref(),
-        &universe,
-        from,
-        length,
-        scoring_strategy,
-        placeholder_search_logger,
-        time_budget,
-        ranking_score_threshold,
-    )?;
+    let BucketSortOutput { docids, scores, mut all_candidates, last_position, degraded } =
+        bucket_sort(
+            ctx,
+            ranking_rules,
+            &PlaceholderQuery,
+            distinct.as_deref(),
+            &universe,
+            ranked_page.from,
+            ranked_page.length,
+            scoring_strategy,
+            placeholder_search_logger,
+            time_budget,
+            ranking_score_threshold,
+            last_ranked,
+        )?;
 
+    let (docids, scores, ranked) =
+        curation.insert_pinned(&ranked_page, from, length, docids, scores);
+    let cursor = SearchCursor {
+        offset: from + docids.len(),
+        ranked,
+        last_ranked: last_position.or_else(|| last_ranked.cloned()),
+        ..Default::default()
+    };
+    all_candidates |= curation.pinned_docids();
     Ok(PartialSearchResult {
         candidates: all_candidates,
         document_scores: scores,
         documents_ids: docids,
         located_query_terms: None,
+        cursor,
         degraded,
         used_negative_operator: false,
     })
@@ -656,9 +715,24 @@ pub fn execute_search(
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<&Vec<Language>>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
+    let curation = match query {
+        Some(query) => Curation::new(ctx, query, &universe)?,
+        None => Curation::default(),
+    };
+    universe -= curation.excluded();
+    let from = search_after.map_or(0, |cursor| cursor.offset) + from;
+    let last_ranked = search_after.and_then(|cursor| cursor.last_ranked.as_ref());
+    // the bucket sort resumes after the last ranked document of the cursor
+    let ranked_before_cursor = match (search_after, last_ranked) {
+        (Some(cursor), Some(_)) => cursor.ranked,
+        _ => 0,
+    };
+    let ranked_page = curation.ranked_page(from, length, ranked_before_cursor);
+
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
     let query_terms = if let Some(query) = query {
@@ -735,7 +809,7 @@ pub fn execute_search(
         drop(entered);
 
         let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+            located_query_terms_from_tokens(ctx, tokens, query, words_limit, &time_budget)?;
         used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
 
         let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
@@ -774,12 +848,13 @@ pub fn execute_search(
             &graph,
             distinct.as_deref(),
             &universe,
-            from,
-            length,
+            ranked_page.from,
+            ranked_page.length,
             scoring_strategy,
             query_graph_logger,
             time_budget,
             ranking_score_threshold,
+            last_ranked,
         )?
     } else {
         let ranking_rules =
@@ -790,16 +865,27 @@ pub fn execute_search(
             &PlaceholderQuery,
             distinct.as_deref(),
             &universe,
-            from,
-            length,
+            ranked_page.from,
+            ranked_page.length,
             scoring_strategy,
             placeholder_search_logger,
             time_budget,
             ranking_score_threshold,
+            last_ranked,
         )?
     };
 
-    let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
+    let BucketSortOutput { docids, scores, mut all_candidates, last_position, degraded } =
+        bucket_sort_output;
+    let (docids, scores, ranked) =
+        curation.insert_pinned(&ranked_page, from, length, docids, scores);
+    let cursor = SearchCursor {
+        offset: from + docids.len(),
+        ranked,
+        last_ranked: last_position.or_else(|| last_ranked.cloned()),
+        ..Default::default()
+    };
+    all_candidates |= curation.pinned_docids();
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
     // The candidates is the universe unless the exhaustive number of hits
@@ -822,6 +908,7 @@ pub fn execute_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
+        cursor,
         degraded,
         used_negative_operator,
     })
@@ -882,11 +969,28 @@ fn check_sort_criteria(
     Ok(())
 }
 
+/// The position in the results after which a search can be resumed, see [`Search::search_after`].
+///
+/// [`Search::search_after`]: crate::Search::search_after
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    /// The number of documents returned before the cursor, pinned documents included.
+    offset: usize,
+    /// The number of ranked documents returned before the cursor.
+    ranked: usize,
+    /// The position in the bucket sort of the last ranked document returned before the cursor.
+    last_ranked: Option<BucketPosition>,
+    /// A hash of the search parameters and of the index state the cursor was computed with,
+    /// the positions of the cursor are meaningless for another search.
+    pub(crate) fingerprint: u64,
+}
+
 pub struct PartialSearchResult {
     pub located_query_terms: Option<Vec<LocatedQueryTerm>>,
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    pub cursor: SearchCursor,
 
     pub degraded: bool,
     pub used_negative_operator: bool,
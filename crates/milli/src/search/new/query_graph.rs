@@ -92,6 +92,9 @@ pub struct QueryGraph {
 impl QueryGraph {
     /// Build the query graph from the parsed user search query, return an updated list of the located query terms
     /// which contains ngrams.
+    ///
+    /// Consecutive terms starting at the same position are alternatives of each other, e.g. `cable`
+    /// and `cord` in `cable OR cord`. They are given the same term id and are never part of an ngram.
     pub fn from_query(
         ctx: &mut SearchContext<'_>,
         // The terms here must be consecutive
@@ -105,54 +108,65 @@ impl QueryGraph {
         let root_node = 0;
         let end_node = 1;
 
+        let groups: Vec<&[LocatedQueryTerm]> =
+            terms.chunk_by(|a, b| a.positions.start() == b.positions.start()).collect();
+
         // Ee could consider generalizing to 4,5,6,7,etc. ngrams
         let (mut prev2, mut prev1, mut prev0): (Vec<u16>, Vec<u16>, Vec<u16>) =
             (vec![], vec![], vec![root_node]);
 
-        let original_terms_len = terms.len();
+        let original_terms_len = groups.len();
         for term_idx in 0..original_terms_len {
             let mut new_nodes = vec![];
 
-            let new_node_idx = add_node(
-                &mut nodes_data,
-                QueryNodeData::Term(LocatedQueryTermSubset {
-                    term_subset: QueryTermSubset::full(terms[term_idx].value),
-                    positions: terms[term_idx].positions.clone(),
-                    term_ids: term_idx as u8..=term_idx as u8,
-                }),
-            );
-            new_nodes.push(new_node_idx);
+            // The alternatives are mandatory as soon as one of them is marked as such.
+            let mandatory = groups[term_idx].iter().any(|term| term.mandatory);
+            for term in groups[term_idx] {
+                let mut term_subset = QueryTermSubset::full(term.value);
+                if mandatory {
+                    term_subset.make_mandatory();
+                }
+                let new_node_idx = add_node(
+                    &mut nodes_data,
+                    QueryNodeData::Term(LocatedQueryTermSubset {
+                        term_subset,
+                        positions: term.positions.clone(),
+                        term_ids: term_idx as u8..=term_idx as u8,
+                    }),
+                );
+                new_nodes.push(new_node_idx);
+            }
 
             if !prev1.is_empty() {
-                if let Some(ngram) =
-                    query_term::make_ngram(ctx, &terms[term_idx - 1..=term_idx], &nbr_typos)?
-                {
-                    new_located_query_terms.push(ngram.clone());
-                    let ngram_idx = add_node(
-                        &mut nodes_data,
-                        QueryNodeData::Term(LocatedQueryTermSubset {
-                            term_subset: QueryTermSubset::full(ngram.value),
-                            positions: ngram.positions,
-                            term_ids: term_idx as u8 - 1..=term_idx as u8,
-                        }),
-                    );
-                    new_nodes.push(ngram_idx);
+                if let Some(ngram_terms) = single_terms(&groups[term_idx - 1..=term_idx]) {
+                    if let Some(ngram) = query_term::make_ngram(ctx, &ngram_terms, &nbr_typos)? {
+                        new_located_query_terms.push(ngram.clone());
+                        let ngram_idx = add_node(
+                            &mut nodes_data,
+                            QueryNodeData::Term(LocatedQueryTermSubset {
+                                term_subset: QueryTermSubset::full(ngram.value),
+                                positions: ngram.positions,
+                                term_ids: term_idx as u8 - 1..=term_idx as u8,
+                            }),
+                        );
+                        new_nodes.push(ngram_idx);
+                    }
                 }
             }
             if !prev2.is_empty() {
-                if let Some(ngram) =
-                    query_term::make_ngram(ctx, &terms[term_idx - 2..=term_idx], &nbr_typos)?
-                {
-                    new_located_query_terms.push(ngram.clone());
-                    let ngram_idx = add_node(
-                        &mut nodes_data,
-                        QueryNodeData::Term(LocatedQueryTermSubset {
-                            term_subset: QueryTermSubset::full(ngram.value),
-                            positions: ngram.positions,
-                            term_ids: term_idx as u8 - 2..=term_idx as u8,
-                        }),
-                    );
-                    new_nodes.push(ngram_idx);
+                if let Some(ngram_terms) = single_terms(&groups[term_idx - 2..=term_idx]) {
+                    if let Some(ngram) = query_term::make_ngram(ctx, &ngram_terms, &nbr_typos)? {
+                        new_located_query_terms.push(ngram.clone());
+                        let ngram_idx = add_node(
+                            &mut nodes_data,
+                            QueryNodeData::Term(LocatedQueryTermSubset {
+                                term_subset: QueryTermSubset::full(ngram.value),
+                                positions: ngram.positions,
+                                term_ids: term_idx as u8 - 2..=term_idx as u8,
+                            }),
+                        );
+                        new_nodes.push(ngram_idx);
+                    }
                 }
             }
             (prev0, prev1, prev2) = (new_nodes, prev0, prev1);
@@ -414,6 +428,17 @@ impl QueryGraph {
     }
 }
 
+/// Returns the terms of the given groups if none of them has alternatives.
+fn single_terms(groups: &[&[LocatedQueryTerm]]) -> Option<Vec<LocatedQueryTerm>> {
+    groups
+        .iter()
+        .map(|group| match group {
+            [term] => Some(term.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
 fn add_node(nodes_data: &mut Vec<QueryNodeData>, node_data: QueryNodeData) -> u16 {
     let new_node_idx = nodes_data.len() as u16;
     nodes_data.push(node_data);
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::collections::BTreeSet;
 use std::ops::ControlFlow;
 
@@ -12,7 +13,7 @@ use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
-use crate::{Result, MAX_WORD_LENGTH};
+use crate::{Result, TimeBudget, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NumberOfTypos {
@@ -70,6 +71,124 @@ fn find_zero_typo_prefix_derivations(
     Ok(())
 }
 
+/// An automaton matching the words that contain a given infix, or only the words ending with it.
+///
+/// Any word can contain the infix, so the whole words FST is explored: the exploration
+/// stops once the time budget is exceeded.
+struct Infix<'a> {
+    infix: &'a [u8],
+    /// For each number of matched bytes, the length of the longest proper prefix
+    /// of the infix that is also a suffix of these matched bytes.
+    fallback: Vec<usize>,
+    suffix_only: bool,
+    time_budget: &'a TimeBudget,
+    /// The number of FST nodes explored since the time budget was last checked.
+    explored: Cell<usize>,
+    budget_exceeded: Cell<bool>,
+}
+
+impl<'a> Infix<'a> {
+    /// The number of FST nodes explored between two checks of the time budget.
+    const BUDGET_CHECK_INTERVAL: usize = 4096;
+
+    fn new(infix: &'a str, suffix_only: bool, time_budget: &'a TimeBudget) -> Self {
+        let infix = infix.as_bytes();
+        let mut fallback = vec![0; infix.len() + 1];
+        let mut matched = 0;
+        for i in 1..infix.len() {
+            while matched > 0 && infix[i] != infix[matched] {
+                matched = fallback[matched];
+            }
+            if infix[i] == infix[matched] {
+                matched += 1;
+            }
+            fallback[i + 1] = matched;
+        }
+        Self {
+            infix,
+            fallback,
+            suffix_only,
+            time_budget,
+            explored: Cell::new(0),
+            budget_exceeded: Cell::new(false),
+        }
+    }
+}
+
+impl Automaton for Infix<'_> {
+    /// The number of bytes of the infix matched by the end of the word.
+    type State = usize;
+
+    fn start(&self) -> usize {
+        0
+    }
+    fn is_match(&self, state: &usize) -> bool {
+        *state == self.infix.len()
+    }
+    fn can_match(&self, _state: &usize) -> bool {
+        if !self.budget_exceeded.get() {
+            let explored = self.explored.get() + 1;
+            if explored >= Self::BUDGET_CHECK_INTERVAL {
+                self.explored.set(0);
+                self.budget_exceeded.set(self.time_budget.exceeded());
+            } else {
+                self.explored.set(explored);
+            }
+        }
+        !self.budget_exceeded.get()
+    }
+    fn will_always_match(&self, state: &usize) -> bool {
+        !self.suffix_only && self.is_match(state)
+    }
+    fn accept(&self, state: &usize, byte: u8) -> usize {
+        if self.infix.is_empty() || self.will_always_match(state) {
+            return *state;
+        }
+        let mut matched = *state;
+        if matched == self.infix.len() {
+            matched = self.fallback[matched];
+        }
+        loop {
+            if self.infix[matched] == byte {
+                return matched + 1;
+            } else if matched == 0 {
+                return 0;
+            }
+            matched = self.fallback[matched];
+        }
+    }
+}
+
+/// Adds to the zero typo derivations of the term the words containing its original word,
+/// e.g. `*4471*`, or only the words ending with it if `suffix_only` is `true`, e.g. `*4471`.
+///
+/// These words must only be matched in the infix searchable attributes. Only the words found
+/// before the time budget is exceeded are derived.
+pub fn compute_infix_derivations(
+    ctx: &mut SearchContext<'_>,
+    term: &mut QueryTerm,
+    suffix_only: bool,
+    time_budget: &TimeBudget,
+) -> Result<()> {
+    let fst = ctx.get_words_fst()?;
+    let word = ctx.word_interner.get(term.original).to_owned();
+    let mut stream = fst.search(Infix::new(&word, suffix_only, time_budget)).into_stream();
+
+    while let Some(derived_word) = stream.next() {
+        if term.zero_typo.infix_of.len() >= limits::MAX_INFIX_COUNT {
+            break;
+        }
+        let derived_word = std::str::from_utf8(derived_word)?;
+        // the words starting with the original word are already matched as prefixes
+        if derived_word == word || (term.is_prefix && derived_word.starts_with(word.as_str())) {
+            continue;
+        }
+        let derived_word = ctx.word_interner.insert(derived_word.to_owned());
+        term.zero_typo.infix_of.insert(derived_word);
+    }
+    Ok(())
+}
+
 fn find_zero_one_typo_derivations(
     ctx: &mut SearchContext<'_>,
     word_interned: Interned<String>,
@@ -190,6 +309,7 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                restricted_fid: None,
             }
         });
     }
@@ -248,11 +368,17 @@ pub fn partially_initialized_term_from_word(
             }
             synonym_word_count += words.len();
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
+            Some(ctx.phrase_interner.insert(Phrase { words, slop: 0 }))
         })
         .collect();
-    let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+    let zero_typo = ZeroTypoTerm {
+        phrase: None,
+        exact: zero_typo,
+        prefix_of,
+        infix_of: BTreeSet::new(),
+        synonyms,
+        use_prefix_db,
+    };
 
     Ok(QueryTerm {
         original: word_interned,
@@ -262,12 +388,13 @@ pub fn partially_initialized_term_from_word(
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        restricted_fid: None,
     })
 }
 
 fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
-        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
+        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)], slop: 0 })))
     } else {
         Ok(None)
     }
@@ -325,7 +452,7 @@ impl Interned<QueryTerm> {
         let split_words = if let Some((ngram_words, split_words)) =
             self_mut.ngram_words.as_ref().zip(split_words.as_ref())
         {
-            let Phrase { words } = ctx.phrase_interner.get(*split_words);
+            let Phrase { words, .. } = ctx.phrase_interner.get(*split_words);
             if ngram_words.iter().ne(words.iter().flatten()) {
                 Some(*split_words)
             } else {
@@ -16,7 +16,7 @@ pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
-use crate::Result;
+use crate::{FieldId, Result};
 
 /// A set of word derivations attached to a location in the search query.
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -51,6 +51,8 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// The field the term must be found in, e.g. `title` in `title:rust`
+    restricted_fid: Option<FieldId>,
 }
 
 // SubTerms will be in a dedup interner
@@ -62,6 +64,9 @@ struct ZeroTypoTerm {
     exact: Option<Interned<String>>,
     /// All the words that contain the original word as prefix
     prefix_of: BTreeSet<Interned<String>>,
+    /// All the words that contain the original word in their middle or at their end,
+    /// only matched in the infix searchable attributes
+    infix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
     /// A prefix in the prefix databases matching the original word
@@ -212,6 +217,7 @@ impl QueryTermSubset {
                     phrase: _,
                     exact: zero_typo,
                     prefix_of,
+                    infix_of,
                     synonyms: _,
                     use_prefix_db: _,
                 } = &original.zero_typo;
@@ -229,12 +235,14 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(infix_of.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let ZeroTypoTerm {
                     phrase: _,
                     exact: zero_typo,
                     prefix_of,
+                    infix_of,
                     synonyms: _,
                     use_prefix_db: _,
                 } = &original.zero_typo;
@@ -254,6 +262,7 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(infix_of.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         }
@@ -298,8 +307,14 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
-            &original.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: _,
+            prefix_of: _,
+            infix_of: _,
+            synonyms,
+            use_prefix_db: _,
+        } = &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
 
@@ -328,6 +343,46 @@ impl QueryTermSubset {
         Ok(result)
     }
 
+    /// Returns the words of the subset only derived from the original word as an infix or
+    /// a suffix, which must only be matched in the infix searchable attributes.
+    ///
+    /// A word can be derived in several ways, e.g. `scat` both contains `cat` and is one typo
+    /// away from it: such a word is also an exact, prefix or typo derivation of the subset and
+    /// is matched in every attribute.
+    pub fn infix_only_words(
+        &self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<BTreeSet<Interned<String>>> {
+        if !self.one_typo_subset.is_empty() || !self.two_typo_subset.is_empty() {
+            self.original.compute_fully_if_needed(ctx)?;
+        }
+
+        let original = ctx.term_interner.get(self.original);
+        let ZeroTypoTerm { exact, prefix_of, infix_of, .. } = &original.zero_typo;
+        let mut words: BTreeSet<_> = infix_of
+            .iter()
+            .copied()
+            .filter(|&word| self.zero_typo_subset.contains_word(word))
+            .collect();
+
+        words.retain(|word| *exact != Some(*word) && !prefix_of.contains(word));
+        if let Lazy::Init(OneTypoTerm { one_typo, .. }) = &original.one_typo {
+            words.retain(|&word| {
+                !(one_typo.contains(&word) && self.one_typo_subset.contains_word(word))
+            });
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo {
+            words.retain(|&word| {
+                !(two_typos.contains(&word) && self.two_typo_subset.contains_word(word))
+            });
+        }
+
+        Ok(words)
+    }
+    /// The field the original term is restricted to, if it was written as `field:term`
+    pub fn restricted_fid(&self, ctx: &SearchContext<'_>) -> Option<FieldId> {
+        ctx.term_interner.get(self.original).restricted_fid
+    }
     pub fn original_phrase(&self, ctx: &SearchContext<'_>) -> Option<Interned<Phrase>> {
         let t = ctx.term_interner.get(self.original);
         if let Some(p) = t.zero_typo.phrase {
@@ -407,10 +462,12 @@ impl QueryTermSubset {
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, infix_of, synonyms, use_prefix_db } =
+            self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
+            && infix_of.is_empty()
             && synonyms.is_empty()
             && use_prefix_db.is_none()
     }
@@ -461,6 +518,8 @@ impl Interned<QueryTerm> {
 pub struct LocatedQueryTerm {
     pub value: Interned<QueryTerm>,
     pub positions: RangeInclusive<u16>,
+    /// `true` if the term was marked as required with the `+` operator
+    pub mandatory: bool,
 }
 
 impl LocatedQueryTerm {
@@ -489,10 +548,17 @@ impl QueryTerm {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
-            &self.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: zero_typo,
+            prefix_of,
+            infix_of,
+            synonyms,
+            use_prefix_db: _,
+        } = &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
+        words.extend(infix_of.iter().copied());
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
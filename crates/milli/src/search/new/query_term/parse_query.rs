@@ -1,13 +1,13 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use charabia::normalizer::NormalizedTokenIter;
 use charabia::{SeparatorKind, TokenKind};
 
-use super::compute_derivations::partially_initialized_term_from_word;
+use super::compute_derivations::{compute_infix_derivations, partially_initialized_term_from_word};
 use super::{LocatedQueryTerm, ZeroTypoTerm};
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
 use crate::search::new::Word;
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::{FieldId, Result, SearchContext, TimeBudget, MAX_WORD_LENGTH};
 
 #[derive(Clone)]
 /// Extraction of the content of a query.
@@ -21,11 +21,18 @@ pub struct ExtractedTokens {
 }
 
 /// Convert the tokenised search query into a list of located query terms.
+///
+/// The `original_query` is the text that was tokenised, it is used to recognize
+/// the case-sensitive `OR` operator, the field names of field-scoped terms, e.g. `title:rust`,
+/// and the wildcards of infix and suffix terms, e.g. `*4471*`, whose derivations are searched
+/// within the `time_budget`.
 #[tracing::instrument(level = "trace", skip_all, target = "search::query")]
 pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext<'_>,
     query: NormalizedTokenIter<'_, '_, '_, '_>,
+    original_query: &str,
     words_limit: Option<usize>,
+    time_budget: &TimeBudget,
 ) -> Result<ExtractedTokens> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
     let allow_prefix_search = ctx.is_prefix_search_allowed();
@@ -34,10 +41,20 @@ pub fn located_query_terms_from_tokens(
 
     let mut negative_phrase = false;
     let mut phrase: Option<PhraseBuilder> = None;
+    // A phrase that was just closed and that can still be given a slop, e.g. `"quick fox"~3`.
+    let mut closed_phrase: Option<(PhraseBuilder, bool)> = None;
+    let mut slop_next_token = false;
     let mut encountered_whitespace = true;
     let mut negative_next_token = false;
+    let mut mandatory_next_token = false;
     let mut negative_words = Vec::new();
     let mut negative_phrases = Vec::new();
+    // The indexes of the query terms that are alternatives of the previous term, e.g. `cord` in `cable OR cord`.
+    let mut alternatives = Vec::new();
+    // The field the next term is restricted to, and the end of its `field:` prefix in the original query.
+    let scopable_fields = scopable_fields(ctx, original_query)?;
+    let mut field_scope: Option<FieldScope> = None;
+    let mut field_scope_end = 0;
 
     let parts_limit = words_limit.unwrap_or(usize::MAX);
 
@@ -50,15 +67,61 @@ pub fn located_query_terms_from_tokens(
             continue;
         }
 
+        if let Some((mut closed, negative)) = closed_phrase.take() {
+            if slop_next_token {
+                slop_next_token = false;
+                if let Some(slop) = parse_phrase_slop(&token) {
+                    // the slop is not a word of the query, it does not take any position.
+                    closed.slop = slop;
+                    push_phrase(ctx, closed, negative, &mut query_terms, &mut negative_phrases);
+                    encountered_whitespace = false;
+                    continue;
+                }
+            } else if matches!(token.kind, TokenKind::Separator(_)) && token.lemma() == "~" {
+                slop_next_token = true;
+                closed_phrase = Some((closed, negative));
+                encountered_whitespace = false;
+                continue;
+            }
+            push_phrase(ctx, closed, negative, &mut query_terms, &mut negative_phrases);
+        }
+
+        // the field name and the colon of a field-scoped term do not take any position
+        if token.byte_end <= field_scope_end {
+            continue;
+        }
+        if phrase.is_none()
+            && (encountered_whitespace || mandatory_next_token)
+            && matches!(token.kind, TokenKind::Word)
+        {
+            if let Some((end, scope)) = field_scope_at(original_query, &token, &scopable_fields) {
+                field_scope = Some(scope);
+                field_scope_end = end;
+                continue;
+            }
+        }
+
         // early return if word limit is exceeded
         if query_terms.len() >= parts_limit {
+            group_alternatives(&mut query_terms, &alternatives);
             return Ok(ExtractedTokens { query_terms, negative_words, negative_phrases });
         }
 
         match token.kind {
+            // The `OR` operator makes the next term an alternative of the previous one.
+            TokenKind::Word
+                if phrase.is_none()
+                    && !query_terms.is_empty()
+                    && peekable.peek().is_some()
+                    && is_or_operator(original_query, &token) =>
+            {
+                alternatives.push(query_terms.len());
+            }
             TokenKind::Word | TokenKind::StopWord => {
                 // On first loop, goes from u16::MAX to 0, then normal increment.
                 position = position.wrapping_add(1);
+                let scope = field_scope.take();
+                let infix = infix_wildcard(ctx, original_query, &token);
 
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
                 // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
@@ -74,35 +137,52 @@ pub fn located_query_terms_from_tokens(
                     match token.kind {
                         TokenKind::Word => {
                             let word = token.lemma();
-                            let term = partially_initialized_term_from_word(
+                            let mut term = partially_initialized_term_from_word(
                                 ctx,
                                 word,
-                                nbr_typos(word),
+                                FieldScope::max_typos(scope, word, &nbr_typos),
                                 false,
                                 false,
                             )?;
+                            term.restricted_fid = scope.map(|scope| scope.fid);
+                            if let Some(suffix_only) = infix {
+                                compute_infix_derivations(
+                                    ctx,
+                                    &mut term,
+                                    suffix_only,
+                                    time_budget,
+                                )?;
+                            }
                             let located_term = LocatedQueryTerm {
                                 value: ctx.term_interner.push(term),
                                 positions: position..=position,
+                                mandatory: mandatory_next_token,
                             };
                             query_terms.push(located_term);
                         }
                         TokenKind::StopWord | TokenKind::Separator(_) | TokenKind::Unknown => (),
                     }
+                    mandatory_next_token = false;
                 } else {
                     let word = token.lemma();
-                    let term = partially_initialized_term_from_word(
+                    let mut term = partially_initialized_term_from_word(
                         ctx,
                         word,
-                        nbr_typos(word),
+                        FieldScope::max_typos(scope, word, &nbr_typos),
                         allow_prefix_search,
                         false,
                     )?;
+                    term.restricted_fid = scope.map(|scope| scope.fid);
+                    if let Some(suffix_only) = infix {
+                        compute_infix_derivations(ctx, &mut term, suffix_only, time_budget)?;
+                    }
                     let located_term = LocatedQueryTerm {
                         value: ctx.term_interner.push(term),
                         positions: position..=position,
+                        mandatory: mandatory_next_token,
                     };
                     query_terms.push(located_term);
+                    mandatory_next_token = false;
                 }
             }
             TokenKind::Separator(separator_kind) => {
@@ -117,6 +197,7 @@ pub fn located_query_terms_from_tokens(
                     // If we have a hard separator inside a phrase, we immediately start a new phrase
                     let phrase = if separator_kind == SeparatorKind::Hard {
                         if let Some(phrase) = phrase {
+                            let restricted_fid = phrase.restricted_fid;
                             if let Some(located_query_term) = phrase.build(ctx) {
                                 // as we are evaluating a negative operator we put the phrase
                                 // in the negative one *but* we don't reset the negative operator
@@ -127,7 +208,7 @@ pub fn located_query_terms_from_tokens(
                                     query_terms.push(located_query_term);
                                 }
                             }
-                            Some(PhraseBuilder::empty())
+                            Some(PhraseBuilder { restricted_fid, ..PhraseBuilder::empty() })
                         } else {
                             None
                         }
@@ -141,26 +222,23 @@ pub fn located_query_terms_from_tokens(
                         break 'phrase phrase;
                     }
 
-                    // Consume the closing quote and the phrase
+                    // Consume the closing quote and keep the phrase aside until we know
+                    // whether a slop follows it.
                     if let Some(phrase) = phrase {
                         // Per the check above, quote_count > 0
                         quote_count -= 1;
-                        if let Some(located_query_term) = phrase.build(ctx) {
-                            // we were evaluating a negative operator so we
-                            // put the phrase in the negative phrases
-                            if negative_phrase {
-                                negative_phrases.push(located_query_term);
-                                negative_phrase = false;
-                            } else {
-                                query_terms.push(located_query_term);
-                            }
-                        }
+                        // we were evaluating a negative operator so the
+                        // phrase will be put in the negative phrases
+                        closed_phrase = Some((phrase, negative_phrase));
+                        negative_phrase = false;
+                        slop_next_token = token.lemma().rsplit('"').next() == Some("~");
                     }
 
                     // Start new phrase if the token ends with an opening quote
                     if quote_count % 2 == 1 {
                         negative_phrase = negative_next_token;
-                        Some(PhraseBuilder::empty())
+                        let restricted_fid = field_scope.take().map(|scope| scope.fid);
+                        Some(PhraseBuilder { restricted_fid, ..PhraseBuilder::empty() })
                     } else {
                         None
                     }
@@ -168,6 +246,10 @@ pub fn located_query_terms_from_tokens(
 
                 negative_next_token =
                     phrase.is_none() && token.lemma() == "-" && encountered_whitespace;
+                // the `+` operator also applies to the alternatives in parentheses, e.g. `+(cable OR cord)`
+                mandatory_next_token = phrase.is_none()
+                    && ((token.lemma() == "+" && encountered_whitespace)
+                        || (mandatory_next_token && token.lemma() == "("));
             }
             _ => (),
         }
@@ -176,6 +258,10 @@ pub fn located_query_terms_from_tokens(
             token.lemma().chars().last().filter(|c| c.is_whitespace()).is_some();
     }
 
+    if let Some((closed, negative)) = closed_phrase.take() {
+        push_phrase(ctx, closed, negative, &mut query_terms, &mut negative_phrases);
+    }
+
     // If a quote is never closed, we consider all of the end of the query as a phrase.
     if let Some(phrase) = phrase.take() {
         if let Some(located_query_term) = phrase.build(ctx) {
@@ -188,9 +274,152 @@ pub fn located_query_terms_from_tokens(
         }
     }
 
+    group_alternatives(&mut query_terms, &alternatives);
+
     Ok(ExtractedTokens { query_terms, negative_words, negative_phrases })
 }
 
+/// Returns `true` if the token is the `OR` operator, which must be written in uppercase.
+fn is_or_operator(original_query: &str, token: &charabia::Token<'_>) -> bool {
+    original_query.get(token.byte_start..token.byte_end) == Some("OR")
+}
+
+/// Returns `Some(suffix_only)` if the word is preceded by a `*` wildcard, e.g. `*4471*` or `*4471`,
+/// and must also match the words containing it, or only ending with it when there is no wildcard after it.
+///
+/// The wildcards are ignored when there are no infix searchable attributes.
+fn infix_wildcard(
+    ctx: &SearchContext<'_>,
+    original_query: &str,
+    token: &charabia::Token<'_>,
+) -> Option<bool> {
+    if ctx.infix_searchable_fids.is_empty() {
+        return None;
+    }
+    let before = original_query.get(..token.byte_start)?;
+    let after = original_query.get(token.byte_end..)?;
+    before.ends_with('*').then(|| !after.starts_with('*'))
+}
+
+/// The field a term is restricted to, e.g. `title` in `title:rust`.
+#[derive(Clone, Copy)]
+struct FieldScope {
+    fid: FieldId,
+    /// `true` if the field is an exact attribute, on which typos are not allowed
+    exact: bool,
+}
+
+impl FieldScope {
+    fn max_typos(scope: Option<Self>, word: &str, nbr_typos: &impl Fn(&str) -> u8) -> u8 {
+        match scope {
+            Some(FieldScope { exact: true, .. }) => 0,
+            _ => nbr_typos(word),
+        }
+    }
+}
+
+/// The searchable fields that can restrict a term of the query, by name.
+///
+/// They are only fetched when the query contains a colon.
+fn scopable_fields(
+    ctx: &SearchContext<'_>,
+    original_query: &str,
+) -> Result<HashMap<String, FieldScope>> {
+    if !original_query.contains(':') {
+        return Ok(HashMap::new());
+    }
+    let exact_fids = ctx.index.exact_attributes_ids(ctx.txn)?;
+    Ok(ctx
+        .index
+        .searchable_fields_and_weights(ctx.txn)?
+        .into_iter()
+        .map(|(name, fid, _weight)| {
+            (name.into_owned(), FieldScope { fid, exact: exact_fids.contains(&fid) })
+        })
+        .collect())
+}
+
+/// Returns the field scope starting at the token, along with the end of its `field:` prefix in the
+/// original query, if the token is the beginning of a field-scoped term like `title:rust`.
+///
+/// Unknown and non-searchable fields are not recognized, the term is then searched as usual.
+fn field_scope_at(
+    original_query: &str,
+    token: &charabia::Token<'_>,
+    scopable_fields: &HashMap<String, FieldScope>,
+) -> Option<(usize, FieldScope)> {
+    let (field, term) = original_query.get(token.byte_start..)?.split_once(':')?;
+    if field.contains(char::is_whitespace)
+        || term.is_empty()
+        || term.starts_with(char::is_whitespace)
+    {
+        return None;
+    }
+    let scope = scopable_fields.get(field)?;
+    Some((token.byte_start + field.len() + 1, *scope))
+}
+
+/// Make the alternatives start at the same position as the terms they are an alternative of,
+/// shifting the positions of the following terms accordingly.
+///
+/// The query graph considers the consecutive terms starting at the same position as alternatives.
+fn group_alternatives(query_terms: &mut [LocatedQueryTerm], alternatives: &[usize]) {
+    if alternatives.is_empty() {
+        return;
+    }
+
+    // The positions of the current group of alternatives, and the original end of the previous term.
+    let mut group: Option<(u16, u16)> = None;
+    let mut previous_end = 0;
+    for (index, term) in query_terms.iter_mut().enumerate() {
+        let (start, end) = (*term.positions.start(), *term.positions.end());
+        let is_alternative = index != 0 && alternatives.contains(&index);
+        let new_start = match group {
+            Some((group_start, _)) if is_alternative => group_start,
+            // keep the distance between the terms, it can be larger because of hard separators
+            Some((_, group_end)) => group_end + start.saturating_sub(previous_end),
+            None => start,
+        };
+        let new_end = new_start + (end - start);
+        term.positions = new_start..=new_end;
+
+        group = match group {
+            Some((group_start, group_end)) if is_alternative => {
+                Some((group_start, group_end.max(new_end)))
+            }
+            _ => Some((new_start, new_end)),
+        };
+        previous_end = end;
+    }
+}
+
+/// Push a closed phrase in the query terms or, if it was negated, in the negative phrases.
+fn push_phrase(
+    ctx: &mut SearchContext<'_>,
+    phrase: PhraseBuilder,
+    negative: bool,
+    query_terms: &mut Vec<LocatedQueryTerm>,
+    negative_phrases: &mut Vec<LocatedQueryTerm>,
+) {
+    if let Some(located_query_term) = phrase.build(ctx) {
+        if negative {
+            negative_phrases.push(located_query_term);
+        } else {
+            query_terms.push(located_query_term);
+        }
+    }
+}
+
+/// Parse the slop of a phrase, the number following the `~` in `"quick fox"~3`.
+fn parse_phrase_slop(token: &charabia::Token<'_>) -> Option<u8> {
+    let lemma = token.lemma();
+    if !matches!(token.kind, TokenKind::Word) || !lemma.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let slop = lemma.parse::<usize>().unwrap_or(usize::MAX);
+    Some(slop.min(super::limits::MAX_PHRASE_SLOP as usize) as u8)
+}
+
 pub fn number_of_typos_allowed<'ctx>(
     ctx: &SearchContext<'ctx>,
 ) -> Result<impl Fn(&str) -> u8 + 'ctx> {
@@ -221,7 +450,11 @@ pub fn make_ngram(
 ) -> Result<Option<LocatedQueryTerm>> {
     assert!(!terms.is_empty());
     for t in terms {
-        if ctx.term_interner.get(t.value).zero_typo.phrase.is_some() {
+        let term = ctx.term_interner.get(t.value);
+        if term.zero_typo.phrase.is_some()
+            || term.restricted_fid.is_some()
+            || !term.zero_typo.infix_of.is_empty()
+        {
             return Ok(None);
         }
     }
@@ -263,7 +496,7 @@ pub fn make_ngram(
     term.zero_typo.synonyms.extend(
         index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
+            ctx.phrase_interner.insert(Phrase { words, slop: 0 })
         }),
     );
 
@@ -275,22 +508,35 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        restricted_fid: None,
     };
 
-    let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
+    let term = LocatedQueryTerm {
+        value: ctx.term_interner.push(term),
+        positions: start..=end,
+        mandatory: false,
+    };
 
     Ok(Some(term))
 }
 
 struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
+    slop: u8,
+    restricted_fid: Option<FieldId>,
     start: u16,
     end: u16,
 }
 
 impl PhraseBuilder {
     fn empty() -> Self {
-        Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
+        Self {
+            words: Default::default(),
+            slop: 0,
+            restricted_fid: None,
+            start: u16::MAX,
+            end: u16::MAX,
+        }
     }
 
     fn is_empty(&self) -> bool {
@@ -323,7 +569,8 @@ impl PhraseBuilder {
         }
         Some(LocatedQueryTerm {
             value: ctx.term_interner.push({
-                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words });
+                let phrase =
+                    ctx.phrase_interner.insert(Phrase { words: self.words, slop: self.slop });
                 let phrase_desc = phrase.description(ctx);
                 QueryTerm {
                     original: ctx.word_interner.insert(phrase_desc),
@@ -334,14 +581,17 @@ impl PhraseBuilder {
                         phrase: Some(phrase),
                         exact: None,
                         prefix_of: BTreeSet::default(),
+                        infix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
                         use_prefix_db: None,
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    restricted_fid: self.restricted_fid,
                 }
             }),
             positions: self.start..=self.end,
+            mandatory: false,
         })
     }
 }
@@ -369,13 +619,14 @@ mod tests {
     fn start_with_hard_separator() -> Result<()> {
         let mut builder = TokenizerBuilder::default();
         let tokenizer = builder.build();
-        let tokens = tokenizer.tokenize(".");
+        let query = ".";
+        let tokens = tokenizer.tokenize(query);
         let index = temp_index_with_documents();
         let rtxn = index.read_txn()?;
         let mut ctx = SearchContext::new(&index, &rtxn)?;
         // panics with `attempt to add with overflow` before <https://github.com/meilisearch/meilisearch/issues/3785>
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None)?;
+            located_query_terms_from_tokens(&mut ctx, tokens, query, None, &TimeBudget::max())?;
         assert!(query_terms.is_empty());
 
         Ok(())
@@ -5,17 +5,29 @@ use crate::SearchContext;
 
 /// A phrase in the user's search query, consisting of several words
 /// that must appear side-by-side in the search results.
+///
+/// When `slop` is greater than zero, the words of the phrase only need to appear
+/// within `slop` extra positions of each other, in the same attribute.
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 pub struct Phrase {
     pub words: Vec<Option<Interned<String>>>,
+    pub slop: u8,
 }
 impl Interned<Phrase> {
     pub fn description(self, ctx: &SearchContext<'_>) -> String {
         let p = ctx.phrase_interner.get(self);
-        p.words.iter().flatten().map(|w| ctx.word_interner.get(*w)).join(" ")
+        let words = p.words.iter().flatten().map(|w| ctx.word_interner.get(*w)).join(" ");
+        if p.slop == 0 {
+            words
+        } else {
+            format!("{words}~{}", p.slop)
+        }
     }
     pub fn words(self, ctx: &SearchContext<'_>) -> Vec<Option<Interned<String>>> {
         let p = ctx.phrase_interner.get(self);
         p.words.clone()
     }
+    pub fn slop(self, ctx: &SearchContext<'_>) -> u8 {
+        ctx.phrase_interner.get(self).slop
+    }
 }
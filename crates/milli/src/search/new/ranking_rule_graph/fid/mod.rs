@@ -74,6 +74,11 @@ impl RankingRuleGraphTrait for FidGraph {
             all_fields.extend(fields);
         }
 
+        // a field-scoped term can only be found in its field
+        if let Some(restricted_fid) = term.term_subset.restricted_fid(ctx) {
+            all_fields.retain(|&fid| fid == restricted_fid);
+        }
+
         let weights_map = ctx.index.fieldids_weights_map(ctx.txn)?;
 
         let mut edges = vec![];
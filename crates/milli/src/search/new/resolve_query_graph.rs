@@ -10,6 +10,7 @@ use super::query_graph::QueryNodeData;
 use super::query_term::{Phrase, QueryTermSubset};
 use super::small_bitmap::SmallBitmap;
 use super::{QueryGraph, SearchContext, Word};
+use crate::proximity::MAX_DISTANCE;
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::Result;
 
@@ -35,9 +36,26 @@ pub fn compute_query_term_subset_docids(
     universe: Option<&RoaringBitmap>,
     term: &QueryTermSubset,
 ) -> Result<RoaringBitmap> {
+    // a field-scoped term, e.g. `title:rust`, only matches the documents containing it in this field
+    if let Some(fid) = term.restricted_fid(ctx) {
+        return compute_query_term_subset_docids_within_field_id(ctx, universe, term, fid);
+    }
+
     let mut docids = RoaringBitmap::new();
+    let infix_only_words = term.infix_only_words(ctx)?;
     // TODO use the MultiOps trait to do large intersections
     for word in term.all_single_words_except_prefix_db(ctx)? {
+        if infix_only_words.contains(&word.interned()) {
+            // the words derived from an infix, e.g. `*4471*`, only match in the infix searchable attributes
+            for fid in ctx.infix_searchable_fids.clone() {
+                if let Some(word_fid_docids) =
+                    ctx.get_db_word_fid_docids(universe, word.interned(), fid)?
+                {
+                    docids |= word_fid_docids;
+                }
+            }
+            continue;
+        }
         if let Some(word_docids) = ctx.word_docids(universe, word)? {
             docids |= word_docids;
         }
@@ -65,7 +83,12 @@ pub fn compute_query_term_subset_docids_within_field_id(
     fid: u16,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    let infix_only_words = term.infix_only_words(ctx)?;
     for word in term.all_single_words_except_prefix_db(ctx)? {
+        if infix_only_words.contains(&word.interned()) && !ctx.infix_searchable_fids.contains(&fid)
+        {
+            continue;
+        }
         if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(universe, word.interned(), fid)? {
             docids |= word_fid_docids;
         }
@@ -126,6 +149,13 @@ pub fn compute_query_term_subset_docids_within_position(
             docids |= word_position_docids;
         }
     }
+
+    // The positions are not stored by field, so we can only make sure
+    // that a field-scoped term appears in its field.
+    if let Some(fid) = term.restricted_fid(ctx) {
+        docids &= compute_query_term_subset_docids_within_field_id(ctx, universe, term, fid)?;
+    }
+
     Ok(docids)
 }
 
@@ -188,7 +218,7 @@ pub fn compute_phrase_docids(
     ctx: &mut SearchContext<'_>,
     phrase: Interned<Phrase>,
 ) -> Result<RoaringBitmap> {
-    let Phrase { words } = ctx.phrase_interner.get(phrase).clone();
+    let Phrase { words, slop } = ctx.phrase_interner.get(phrase).clone();
 
     if words.is_empty() {
         return Ok(RoaringBitmap::new());
@@ -210,6 +240,10 @@ pub fn compute_phrase_docids(
         return Ok(RoaringBitmap::new());
     };
 
+    if slop > 0 {
+        return compute_sloppy_phrase_docids(ctx, &words, slop, candidates);
+    }
+
     let winsize = words.len().min(3);
 
     for win in words.windows(winsize) {
@@ -266,3 +300,131 @@ pub fn compute_phrase_docids(
     }
     Ok(candidates)
 }
+
+/// Returns the subset of the candidates in which the words of the phrase appear in order in the
+/// same attribute, each word being at most `slop` extra positions away from the previous one.
+///
+/// The word positions are bucketed in the database, so there may be false positives when the
+/// words appear far into an attribute.
+fn compute_sloppy_phrase_docids(
+    ctx: &mut SearchContext<'_>,
+    words: &[Option<Interned<String>>],
+    slop: u8,
+    mut candidates: RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    // All the words must appear in the same attribute.
+    let mut same_fid_docids = RoaringBitmap::new();
+    for fid in ctx.index.searchable_fields_ids(ctx.txn)? {
+        let mut fid_docids = candidates.clone();
+        for word in words.iter().flatten().copied() {
+            match ctx.get_db_word_fid_docids(Some(&fid_docids), word, fid)? {
+                Some(word_fid_docids) => fid_docids = word_fid_docids,
+                None => fid_docids.clear(),
+            }
+            if fid_docids.is_empty() {
+                break;
+            }
+        }
+        same_fid_docids |= fid_docids;
+    }
+    candidates &= same_fid_docids;
+
+    let located_words: Vec<_> = words
+        .iter()
+        .enumerate()
+        .filter_map(|(index, word)| word.map(|word| (index, word)))
+        .collect();
+    for pair in located_words.windows(2) {
+        let [(left_index, left), (right_index, right)] = *pair else { unreachable!() };
+        if candidates.is_empty() {
+            break;
+        }
+        // the stop words between the two words still count as positions
+        let max_distance = (right_index - left_index) + slop as usize;
+
+        let mut pair_docids = RoaringBitmap::new();
+        // The closest pairs are directly stored in the word pair proximity database.
+        for proximity in 1..=max_distance.min(MAX_DISTANCE as usize - 1) {
+            if let Some(docids) = ctx.get_db_word_pair_proximity_docids(
+                Some(&candidates),
+                left,
+                right,
+                proximity as u8,
+            )? {
+                pair_docids |= docids;
+            }
+        }
+        // The farther ones must be computed from the word positions. The positions are
+        // relative to an attribute, so the two words must be searched in the same one.
+        if max_distance >= MAX_DISTANCE as usize {
+            let left_positions = ctx.get_db_word_positions(left)?;
+            let right_positions = ctx.get_db_word_positions(right)?;
+            for fid in ctx.index.searchable_fields_ids(ctx.txn)? {
+                let Some(left_fid_docids) =
+                    ctx.get_db_word_fid_docids(Some(&candidates), left, fid)?
+                else {
+                    continue;
+                };
+                let Some(mut fid_docids) =
+                    ctx.get_db_word_fid_docids(Some(&left_fid_docids), right, fid)?
+                else {
+                    continue;
+                };
+                fid_docids -= &pair_docids;
+                if fid_docids.is_empty() {
+                    continue;
+                }
+
+                for &left_position in &left_positions {
+                    let Some(left_docids) =
+                        ctx.get_db_word_position_docids(Some(&fid_docids), left, left_position)?
+                    else {
+                        continue;
+                    };
+                    // The positions are sorted and bucketed, the distance only grows with
+                    // the right position: only the few buckets close enough are read.
+                    for &right_position in &right_positions {
+                        match bucketed_positions_distance(left_position, right_position) {
+                            None => continue,
+                            Some(distance) if distance > max_distance => break,
+                            Some(_) => (),
+                        }
+                        if let Some(right_docids) = ctx.get_db_word_position_docids(
+                            Some(&left_docids),
+                            right,
+                            right_position,
+                        )? {
+                            pair_docids |= right_docids;
+                        }
+                    }
+                }
+            }
+        }
+
+        candidates &= pair_docids;
+    }
+
+    Ok(candidates)
+}
+
+/// Returns the smallest distance between a position of the `left` bucket and a following
+/// position of the `right` bucket, see [`crate::bucketed_position`].
+fn bucketed_positions_distance(left: u16, right: u16) -> Option<usize> {
+    let (left_start, left_end) = bucketed_position_range(left);
+    let (right_start, right_end) = bucketed_position_range(right);
+    if right_end <= left_start {
+        None
+    } else {
+        Some(right_start.saturating_sub(left_end).max(1) as usize)
+    }
+}
+
+/// Returns the range of relative positions that are reduced to the given bucketed position.
+fn bucketed_position_range(bucket: u16) -> (u16, u16) {
+    match bucket {
+        0..=15 => (bucket, bucket),
+        24 => (16, 23),
+        32 => (24, 32),
+        bucket => (bucket / 2 + 1, bucket),
+    }
+}
@@ -0,0 +1,115 @@
+/*!
+This module tests the following properties:
+
+1. The `OR` operator makes the words around it alternatives of each other.
+2. The `+` operator marks a term as required, so that it is never removed
+   by the terms matching strategy.
+3. The `+` operator applies to a whole group of alternatives in parentheses.
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "usb cable",
+            },
+            {
+                "id": 1,
+                "text": "usb cord",
+            },
+            {
+                "id": 2,
+                "text": "hdmi cable",
+            },
+            {
+                "id": 3,
+                "text": "usb charger",
+            },
+            {
+                "id": 4,
+                "text": "cable cord usb",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_or_operator() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("cable OR cord usb");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 2]");
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"usb cable\"",
+        "\"usb cord\"",
+        "\"cable cord usb\"",
+        "\"hdmi cable\"",
+    ]
+    "###);
+
+    // a lowercase `or` is a regular word
+    let mut s = Search::new(&txn, &index);
+    s.query("cable or cord");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn test_required_operator() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("cable usb");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 4, 2]");
+
+    // `usb` can't be removed by the terms matching strategy anymore
+    let mut s = Search::new(&txn, &index);
+    s.query("cable +usb");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 4, 1, 3]");
+}
+
+#[test]
+fn test_required_alternatives() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("usb (cable OR cord)");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("usb +(cable OR cord)");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 2]");
+}
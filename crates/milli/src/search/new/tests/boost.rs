@@ -0,0 +1,116 @@
+/*!
+This module tests the `boost` ranking rule:
+
+1. the documents matching the filter are returned before the others, which are not filtered out
+2. several boost ranking rules are applied in the order of the settings, they are not weighted
+3. the filter can only use filterable attributes, the settings using other attributes are refused
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("inStock"), S("brand") });
+            s.set_criteria(vec![
+                Criterion::Words,
+                "boost(inStock = true)".parse().unwrap(),
+                "boost(brand = acme)".parse().unwrap(),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "red shoes", "inStock": false, "brand": "acme" },
+            { "id": 1, "text": "red shoes", "inStock": true, "brand": "globex" },
+            { "id": 2, "text": "red shoes", "inStock": true, "brand": "acme" },
+            { "id": 3, "text": "red shoes", "inStock": false, "brand": "globex" },
+            { "id": 4, "text": "red shoes", "brand": "acme" },
+            { "id": 5, "text": "shoes", "inStock": true, "brand": "acme" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_boost() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 5, 1, 0, 4, 3]");
+
+    // the boost ranking rules are applied after the words ranking rule
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0, 4, 3, 5]");
+    insta::assert_json_snapshot!(ScoreDetails::to_json_map(document_scores[1].iter()), @r###"
+    {
+      "words": {
+        "order": 0,
+        "matchingWords": 2,
+        "maxMatchingWords": 2,
+        "score": 1.0
+      },
+      "boost(inStock = true)": {
+        "order": 1,
+        "matched": true,
+        "score": 1.0
+      },
+      "boost(brand = acme)": {
+        "order": 2,
+        "matched": false,
+        "score": 0.5
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_boost_not_filterable() {
+    let index = create_index();
+
+    // an attribute used by a boost can't be removed from the filterable attributes
+    let error = index
+        .update_settings(|s| {
+            s.set_filterable_fields(hashset! { S("brand") });
+        })
+        .unwrap_err();
+    insta::assert_snapshot!(error, @"`boost(inStock = true)` ranking rule is invalid. Attribute `inStock` is not filterable. Available filterable attributes are: `brand`.\n1:8 inStock = true");
+
+    // and a boost can't filter on an attribute that is not filterable
+    let error = index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Words, "boost(price < 10)".parse().unwrap()]);
+        })
+        .unwrap_err();
+    insta::assert_snapshot!(error, @"`boost(price < 10)` ranking rule is invalid. Attribute `price` is not filterable. Available filterable attributes are: `brand`, `inStock`.\n1:6 price < 10");
+
+    // the settings are checked together
+    index
+        .update_settings(|s| {
+            s.set_filterable_fields(hashset! { S("price") });
+            s.set_criteria(vec![Criterion::Words, "boost(price < 10)".parse().unwrap()]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+}
@@ -0,0 +1,180 @@
+/*!
+This module tests the curation rules:
+
+1. the documents pinned by a matching rule are returned at their position, even if they
+   don't match the query, and the other documents are shifted
+2. the documents hidden by a matching rule are never returned
+3. the pagination takes the pinned documents into account, the documents pinned after
+   the last result are returned once, right after it
+4. the pinned documents must match the filters
+5. the rules only apply to the queries they match
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails};
+use crate::{
+    Criterion, CurationRule, Filter, PinnedDocument, QueryMatchType, Search, SearchResult,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("color") });
+            s.set_criteria(vec![Criterion::Words]);
+            s.set_curation_rules(vec![
+                CurationRule {
+                    query: S("red shoes"),
+                    match_type: QueryMatchType::Exact,
+                    pinned: vec![
+                        PinnedDocument { id: S("4"), position: 0 },
+                        PinnedDocument { id: S("5"), position: 2 },
+                    ],
+                    hidden: vec![S("1")],
+                },
+                CurationRule {
+                    query: S("boots"),
+                    match_type: QueryMatchType::ContainsWords,
+                    pinned: vec![PinnedDocument { id: S("5"), position: 1 }],
+                    hidden: vec![],
+                },
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "red shoes", "color": "red" },
+            { "id": 1, "text": "red shoes", "color": "red" },
+            { "id": 2, "text": "red shoes", "color": "red" },
+            { "id": 3, "text": "red shoes and blue boots", "color": "blue" },
+            { "id": 4, "text": "sandals", "color": "red" },
+            { "id": 5, "text": "rain boots", "color": "yellow" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_curation_pinned_and_hidden() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("Red Shoes");
+    let SearchResult { documents_ids, candidates, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 0, 5, 2, 3]");
+    insta::assert_snapshot!(format!("{candidates:?}"), @"RoaringBitmap<[0, 2, 3, 4, 5]>");
+    assert_eq!(
+        document_scores[2],
+        vec![ScoreDetails::Pinned(score_details::Pinned { position: 2 })]
+    );
+
+    // without the rule, the hidden document is returned and nothing is pinned
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes and");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 0, 1, 2]");
+}
+
+#[test]
+fn test_curation_pagination() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.offset(1);
+    s.limit(2);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 5]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.offset(3);
+    s.limit(10);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3]");
+}
+
+#[test]
+fn test_curation_pinned_after_the_last_result() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_curation_rules(vec![CurationRule {
+                query: S("rain"),
+                match_type: QueryMatchType::Exact,
+                pinned: vec![
+                    PinnedDocument { id: S("4"), position: 50 },
+                    PinnedDocument { id: S("2"), position: 51 },
+                ],
+                hidden: vec![],
+            }]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let search = |offset: usize, limit: usize| {
+        let mut s = Search::new(&txn, &index);
+        s.query("rain");
+        s.offset(offset);
+        s.limit(limit);
+        s.execute().unwrap()
+    };
+
+    let SearchResult { documents_ids, .. } = search(0, 20);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 4, 2]");
+    let SearchResult { documents_ids, .. } = search(1, 1);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4]");
+    let SearchResult { documents_ids, .. } = search(2, 5);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+    let SearchResult { documents_ids, .. } = search(3, 5);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+    let SearchResult { documents_ids, .. } = search(20, 20);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    // following the cursors returns each pinned document once
+    let mut documents_ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let mut s = Search::new(&txn, &index);
+        s.query("rain");
+        s.limit(1);
+        if let Some(cursor) = cursor.take() {
+            s.search_after(cursor);
+        }
+        let result = s.execute().unwrap();
+        if result.documents_ids.is_empty() {
+            break;
+        }
+        documents_ids.extend(result.documents_ids);
+        cursor = result.cursor;
+    }
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 4, 2]");
+}
+
+#[test]
+fn test_curation_contains_words_and_filters() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("blue boots");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 5]");
+
+    // the pinned document doesn't match the filter
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.filter(Filter::from_str("color = red").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 0, 2]");
+}
@@ -0,0 +1,107 @@
+/*!
+This module tests the `decay` and `closeTo` ranking rules:
+
+1. `closeTo` sorts the documents by increasing distance between their value and the origin
+2. `decay` groups the documents by their quantized decay score, the next ranking rules
+   are applied to the documents of a same bucket
+3. documents without a number in the field are returned last
+4. if a field contains an array, the document is ranked by its best value
+*/
+
+use big_s::S;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Decay, DecayFunction, DecayOrigin, DecayScale, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 100 },
+            { "id": 1, "price": 105 },
+            { "id": 2, "price": 140 },
+            { "id": 3, "price": 95 },
+            { "id": 4, "price": 300 },
+            { "id": 5, "price": "cheap" },
+            { "id": 6, "price": [500, 101] },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_close_to() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec!["closeTo(price, 100)".parse().unwrap()]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 6, 1, 3, 2, 4, 5]");
+}
+
+#[test]
+fn test_decay() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Decay(Decay {
+                field: S("price"),
+                origin: DecayOrigin::Value(100.0),
+                scale: Some(DecayScale { value: 100.0, unit: None }),
+                function: DecayFunction::Gauss,
+                decay: 0.5,
+            })]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    // 95, 100, 101 and 105 are all in the first bucket
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3, 6, 2, 4, 5]");
+    let global_scores: Vec<_> =
+        document_scores.iter().map(|scores| ScoreDetails::global_score(scores.iter())).collect();
+    insta::assert_snapshot!(format!("{global_scores:?}"), @"[1.0, 1.0, 1.0, 1.0, 0.9, 0.07, 0.0]");
+    insta::assert_json_snapshot!(ScoreDetails::to_json_map(document_scores[4].iter()), @r###"
+    {
+      "decay(price, origin=100, scale=100)": {
+        "order": 0,
+        "score": 0.9
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_decay_is_followed_by_the_next_ranking_rules() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![
+                "decay(price, origin=100, scale=100)".parse().unwrap(),
+                Criterion::Desc(S("price")),
+            ]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[6, 1, 0, 3, 2, 4, 5]");
+}
@@ -0,0 +1,135 @@
+/*!
+This module tests the following properties:
+
+1. A term prefixed by a searchable field name, e.g. `author:tolkien`, only matches
+   the documents containing it in this field.
+2. Field-scoped terms can be mixed with regular terms and phrases.
+3. Typos are not allowed on a field-scoped term when the field is an exact attribute.
+4. A prefix that is not the name of a searchable field is searched as regular words.
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "author".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "the lord of the rings",
+                "author": "tolkien",
+            },
+            {
+                "id": 1,
+                "title": "tolkien biography",
+                "author": "carpenter",
+            },
+            {
+                "id": 2,
+                "title": "the hobbit",
+                "author": "tolkien",
+            },
+            {
+                "id": 3,
+                "title": "rings of power",
+                "author": "amazon",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_field_scoped_term() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("tolkien");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("author:tolkien");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2]");
+    let authors = collect_field_values(&index, &txn, "author", &documents_ids);
+    insta::assert_debug_snapshot!(authors, @r###"
+    [
+        "\"tolkien\"",
+        "\"tolkien\"",
+    ]
+    "###);
+
+    // typos are allowed on field-scoped terms
+    let mut s = Search::new(&txn, &index);
+    s.query("author:tolkein");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2]");
+}
+
+#[test]
+fn test_field_scoped_term_with_other_terms() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("author:tolkien rings");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("title:\"the hobbit\"");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("author:\"the hobbit\"");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn test_field_scoped_term_on_exact_attribute() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_exact_attributes(["author".to_owned()].into_iter().collect());
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("author:tolkein");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("author:tolkien");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2]");
+}
+
+#[test]
+fn test_unknown_field_scope() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("publisher:tolkien");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
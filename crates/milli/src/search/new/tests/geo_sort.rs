@@ -308,3 +308,46 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_multiple_points() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 10, "lng": 10 }, { "lat": 1, "lng": 1 }] },
+            { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 2, "lng": 2 } },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: [{ "lat": -3, "lng": 0 }, { "lat": 50, "lng": 50 }] },
+            { "id": 3, RESERVED_GEO_FIELD_NAME: [{ "lat": 5, "lng": 5 }] },
+            { "id": 4 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    let closest_points = |scores: &[Vec<ScoreDetails>]| -> Vec<Option<[f64; 2]>> {
+        scores
+            .iter()
+            .map(|scores| {
+                scores.iter().find_map(|score| match score {
+                    ScoreDetails::GeoSort(geo_sort) => geo_sort.value,
+                    _ => None,
+                })
+            })
+            .collect()
+    };
+
+    // the documents are ranked by their closest point
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3, 4]");
+    insta::assert_snapshot!(format!("{:?}", closest_points(&scores)), @"[Some([1.0, 1.0]), Some([2.0, 2.0]), Some([-3.0, 0.0]), Some([5.0, 5.0]), None]");
+
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 2, 1, 0, 4]");
+    insta::assert_snapshot!(format!("{:?}", closest_points(&scores)), @"[Some([5.0, 5.0]), Some([-3.0, 0.0]), Some([2.0, 2.0]), Some([1.0, 1.0]), None]");
+}
@@ -0,0 +1,128 @@
+/*!
+This module tests the following properties:
+
+1. A word preceded and followed by a `*` wildcard, e.g. `*kore*`, also matches the words
+   containing it, but only in the infix searchable attributes.
+2. A word only preceded by a `*` wildcard, e.g. `*kore`, also matches the words ending with it.
+3. The wildcards are ignored when there are no infix searchable attributes.
+4. A word containing the infix that is also a typo derivation of it matches in every attribute.
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["sku".to_owned(), "description".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "sku": "abkorecd",
+                "description": "red cable",
+            },
+            {
+                "id": 1,
+                "sku": "korexy",
+                "description": "blue cable",
+            },
+            {
+                "id": 2,
+                "sku": "zzzz",
+                "description": "replaces abkorecd",
+            },
+            {
+                "id": 3,
+                "sku": "xykore",
+                "description": "green cable",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn set_infix_searchable_sku(index: &TempIndex) {
+    index
+        .update_settings(|s| {
+            s.set_infix_searchable_attributes(["sku".to_owned()].into_iter().collect());
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_infix_wildcard() {
+    let index = create_index();
+    set_infix_searchable_sku(&index);
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("*kore*");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3]");
+    let skus = collect_field_values(&index, &txn, "sku", &documents_ids);
+    insta::assert_debug_snapshot!(skus, @r###"
+    [
+        "\"abkorecd\"",
+        "\"korexy\"",
+        "\"xykore\"",
+    ]
+    "###);
+}
+
+#[test]
+fn test_suffix_wildcard() {
+    let index = create_index();
+    set_infix_searchable_sku(&index);
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("*kore cable");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3]");
+}
+
+#[test]
+fn test_wildcard_without_infix_searchable_attributes() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("*kore*");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+}
+
+#[test]
+fn test_infix_wildcard_typo_derivation() {
+    let index = create_index();
+    set_infix_searchable_sku(&index);
+    index
+        .add_documents(documents!([
+            {
+                "id": 4,
+                "sku": "zzzz",
+                "description": "red cables",
+            },
+        ]))
+        .unwrap();
+
+    // `cables` contains `cable` but is also one typo away from it
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("*cable* red");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 4]");
+}
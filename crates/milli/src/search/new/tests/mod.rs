@@ -1,16 +1,24 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod boolean_operators;
+pub mod boost;
+pub mod curation;
 pub mod cutoff;
+pub mod decay;
 pub mod distinct;
 pub mod exactness;
+pub mod field_scoped_terms;
 pub mod geo_sort;
+pub mod infix_search;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 #[cfg(not(feature = "chinese-pinyin"))]
 pub mod language;
 pub mod ngram_split_words;
+pub mod phrase_slop;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod search_after;
 pub mod sort;
 pub mod stop_words;
 pub mod typo;
@@ -0,0 +1,171 @@
+/*!
+This module tests the following properties:
+
+1. A phrase followed by `~N` matches documents where its words appear in order
+   with at most `N` extra words between them.
+2. The words of a sloppy phrase must appear in the same attribute, even when they are
+   far apart.
+3. The slop is not considered as a word of the query.
+4. Sloppy phrases can be negated.
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned(), "other".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "the quick brown fox",
+            },
+            {
+                "id": 1,
+                "text": "the quick fox",
+            },
+            {
+                "id": 2,
+                "text": "the fox is quick",
+            },
+            {
+                "id": 3,
+                "text": "quick and then a very slow and lazy brown fox",
+            },
+            {
+                "id": 4,
+                "text": "quick",
+                "other": "fox",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_strict_phrase() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+}
+
+#[test]
+fn test_sloppy_phrase() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~1");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // the words must appear in order, so "the fox is quick" does not match
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"the quick brown fox\"",
+        "\"the quick fox\"",
+    ]
+    "###);
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~7");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // "quick" and "fox" are separated by 8 words in the document 3
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~8");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3]");
+}
+
+#[test]
+fn test_sloppy_phrase_same_attribute() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~60");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // the document 4 contains both words but in different attributes
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3]");
+}
+
+#[test]
+fn test_sloppy_phrase_far_words() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned(), "other".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    let filler = |count: usize| vec!["word"; count].join(" ");
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": format!("quick {} fox", filler(30)) },
+            { "id": 1, "text": format!("quick {} fox", filler(100)) },
+            { "id": 2, "text": format!("quick {}", filler(30)), "other": "fox" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~40");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~64");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
+
+#[test]
+fn test_sloppy_phrase_slop_is_not_a_word() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~1 brown");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
+
+#[test]
+fn test_negative_sloppy_phrase() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("-\"quick fox\"~1");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, used_negative_operator, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3, 4]");
+    assert!(used_negative_operator);
+}
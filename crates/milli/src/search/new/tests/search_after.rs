@@ -0,0 +1,190 @@
+/*!
+This module tests the cursors returned by the searches:
+
+1. going through the pages after each cursor returns the same documents,
+   in the same order, as a single search
+2. a cursor is rejected by a search with other parameters or on an updated index
+3. the cursors take the distinct attribute and the pinned documents into account
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{
+    AscDesc, Criterion, CurationRule, Member, PinnedDocument, QueryMatchType, Search, SearchCursor,
+    SearchResult,
+};
+
+const TEXTS: [&str; 4] = ["the quick brown fox", "the quick brown", "the quikc brown fox", "a dog"];
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_filterable_fields(hashset! { S("group") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo, Criterion::Sort]);
+            s.set_curation_rules(vec![CurationRule {
+                query: S("brown fox"),
+                match_type: QueryMatchType::Exact,
+                pinned: vec![
+                    PinnedDocument { id: S("3"), position: 2 },
+                    PinnedDocument { id: S("7"), position: 9 },
+                ],
+                hidden: vec![S("0")],
+            }]);
+        })
+        .unwrap();
+
+    let documents: Vec<_> = (0..30)
+        .map(|i| {
+            serde_json::json!({
+                "id": i,
+                "text": TEXTS[i % TEXTS.len()],
+                "rank": (i * 7) % 10,
+                "group": i % 5,
+            })
+        })
+        .collect();
+    index.add_documents(documents!(documents)).unwrap();
+    index
+}
+
+fn sort_by_rank(s: &mut Search<'_>) {
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+}
+
+/// Returns the documents of a single search and of all the pages following each other's cursor.
+fn single_search_and_pages(
+    index: &TempIndex,
+    configure: impl Fn(&mut Search<'_>),
+    page_size: usize,
+) -> (Vec<u32>, Vec<u32>) {
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, index);
+    configure(&mut s);
+    s.limit(1000);
+    let SearchResult { documents_ids: single_search, .. } = s.execute().unwrap();
+
+    let mut pages = Vec::new();
+    let mut cursor: Option<SearchCursor> = None;
+    loop {
+        let mut s = Search::new(&txn, index);
+        configure(&mut s);
+        s.limit(page_size);
+        if let Some(cursor) = cursor.take() {
+            s.search_after(cursor);
+        }
+        let SearchResult { documents_ids, cursor: next_cursor, .. } = s.execute().unwrap();
+        if documents_ids.is_empty() {
+            break;
+        }
+        pages.extend(documents_ids);
+        cursor = next_cursor;
+    }
+
+    (single_search, pages)
+}
+
+#[test]
+fn test_search_after_pages() {
+    let index = create_index();
+
+    // without ranking rules
+    let (single_search, pages) = single_search_and_pages(&index, |_| (), 7);
+    assert_eq!(single_search.len(), 30);
+    assert_eq!(pages, single_search);
+
+    let (single_search, pages) = single_search_and_pages(&index, sort_by_rank, 4);
+    assert_eq!(single_search.len(), 30);
+    assert_eq!(pages, single_search);
+
+    let (single_search, pages) = single_search_and_pages(
+        &index,
+        |s| {
+            s.query("the quick brown fox");
+            sort_by_rank(s);
+        },
+        3,
+    );
+    assert_eq!(single_search.len(), 23);
+    assert_eq!(pages, single_search);
+}
+
+#[test]
+fn test_search_after_distinct_and_curation() {
+    let index = create_index();
+
+    let (single_search, pages) = single_search_and_pages(
+        &index,
+        |s| {
+            s.query("quick fox");
+            s.distinct(S("group"));
+        },
+        2,
+    );
+    insta::assert_snapshot!(format!("{single_search:?}"), @"[0, 4, 8, 12, 16]");
+    assert_eq!(pages, single_search);
+
+    // the pinned documents are inserted at their position in the pages
+    let (single_search, pages) = single_search_and_pages(
+        &index,
+        |s| {
+            s.query("brown fox");
+            sort_by_rank(s);
+        },
+        4,
+    );
+    assert_eq!(single_search[2], 3);
+    assert_eq!(single_search[9], 7);
+    assert!(!single_search.contains(&0));
+    assert_eq!(pages, single_search);
+}
+
+#[test]
+fn test_search_after_rejected_cursor() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    sort_by_rank(&mut s);
+    s.limit(5);
+    let SearchResult { documents_ids, cursor, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 10, 20, 3, 13]");
+    let cursor = cursor.unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    sort_by_rank(&mut s);
+    s.limit(5);
+    s.search_after(cursor.clone());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[23, 6, 16, 26, 9]");
+
+    // the cursor can't resume a search with other parameters
+    let mut s = Search::new(&txn, &index);
+    s.query("quick");
+    sort_by_rank(&mut s);
+    s.search_after(cursor.clone());
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"The search cannot be resumed from this cursor, it was returned by a search with other parameters or the index was updated since.");
+    drop(txn);
+
+    // nor a search on an updated index, the buckets sorted before the cursor may have changed
+    index
+        .add_documents(documents!([{ "id": 30, "text": "a cat", "rank": 0, "group": 0 }]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    sort_by_rank(&mut s);
+    s.limit(5);
+    s.search_after(cursor);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"The search cannot be resumed from this cursor, it was returned by a search with other parameters or the index was updated since.");
+}
@@ -125,6 +125,7 @@ impl<'a> Similar<'a> {
             candidates,
             documents_ids,
             document_scores,
+            cursor: None,
             degraded: false,
             used_negative_operator: false,
         })
@@ -0,0 +1,226 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use charabia::TokenKind;
+use fst::automaton::{Automaton, Str};
+use fst::{IntoStreamer, Streamer};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::new::limits::MAX_PREFIX_COUNT;
+use super::new::Word;
+use super::tokenize_query;
+use crate::{filtered_universe, Filter, Index, Result, SearchContext};
+
+/// The default maximum number of suggestions returned.
+const DEFAULT_LIMIT: usize = 10;
+
+/// Completes the last word of a partial search query, for search-as-you-type interfaces.
+///
+/// The completions are the words of the index starting with the last word of the query,
+/// sorted by the number of documents containing them along with the other words of the query.
+pub struct Suggest<'a> {
+    query: &'a str,
+    filter: Option<Filter<'a>>,
+    searchable_attributes: Option<&'a [String]>,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, ToSchema)]
+pub struct SuggestionHit {
+    /// The query with its last word completed
+    pub suggestion: String,
+    /// The number of documents matching the suggestion
+    pub count: u64,
+}
+
+impl<'a> Suggest<'a> {
+    pub fn new(query: &'a str, rtxn: &'a heed::RoTxn<'a>, index: &'a Index) -> Self {
+        Self { query, filter: None, searchable_attributes: None, limit: DEFAULT_LIMIT, rtxn, index }
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Self {
+        self.searchable_attributes = Some(searchable);
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<SuggestionHit>> {
+        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            ctx.attributes_to_search_on(searchable_attributes)?;
+        }
+        let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+
+        let words: Vec<_> = tokenize_query(self.index, self.rtxn, self.query)?
+            .into_iter()
+            .filter(|token| matches!(token.kind, TokenKind::Word))
+            .collect();
+        // there is nothing to complete when the query ends with a separator
+        let Some(prefix) = words.last().filter(|token| token.byte_end == self.query.len()) else {
+            return Ok(Vec::new());
+        };
+        if self.limit == 0 || !ctx.is_prefix_search_allowed() {
+            return Ok(Vec::new());
+        }
+
+        // the suggestions must match the words preceding the completed one
+        for token in &words[..words.len() - 1] {
+            let word = ctx.word_interner.insert(token.lemma().to_string());
+            universe = ctx.word_docids(Some(&universe), Word::Original(word))?.unwrap_or_default();
+        }
+
+        // the prefixes database contains the documents of the most frequent prefixes,
+        // we use it to skip the completions when none of them can match
+        let prefix_word = prefix.lemma();
+        if self.index.words_prefixes_fst(self.rtxn)?.contains(prefix_word) {
+            let interned = ctx.word_interner.insert(prefix_word.to_string());
+            let prefix_docids =
+                ctx.word_prefix_docids(Some(&universe), Word::Original(interned))?;
+            if prefix_docids.map_or(true, |docids| docids.is_empty()) {
+                return Ok(Vec::new());
+            }
+        }
+
+        // keeps the best completions, the worst one being on the top of the heap
+        let mut completions = BinaryHeap::new();
+        let words_fst = ctx.get_words_fst()?;
+        let mut stream = words_fst.search(Str::new(prefix_word).starts_with()).into_stream();
+        // like the prefix derivations of a search, only the first words starting with
+        // the prefix are considered so that a short prefix doesn't scan the whole index
+        for _ in 0..MAX_PREFIX_COUNT {
+            let Some(completion) = stream.next() else { break };
+            let completion = std::str::from_utf8(completion)?;
+            let interned = ctx.word_interner.insert(completion.to_string());
+            let count = ctx
+                .word_docids(Some(&universe), Word::Original(interned))?
+                .map_or(0, |docids| docids.len());
+            if count == 0 {
+                continue;
+            }
+            completions.push((Reverse(count), completion.to_string()));
+            if completions.len() > self.limit {
+                completions.pop();
+            }
+        }
+
+        let query_start = &self.query[..prefix.byte_start];
+        Ok(completions
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(Reverse(count), completion)| SuggestionHit {
+                suggestion: format!("{query_start}{completion}"),
+                count,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::hashset;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn suggest() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| {
+                s.set_searchable_fields(vec![S("title"), S("description")]);
+                s.set_filterable_fields(hashset! { S("color") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "red shoes", "description": "shiny", "color": "red" },
+                { "id": 1, "title": "red shirt", "description": "shiny", "color": "red" },
+                { "id": 2, "title": "blue shirt", "description": "shoes", "color": "blue" },
+                { "id": 3, "title": "blue shirt", "color": "blue" },
+                { "id": 4, "title": "red shorts", "color": "blue" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let suggestions = |suggest: &Suggest| {
+            suggest
+                .execute()
+                .unwrap()
+                .into_iter()
+                .map(|SuggestionHit { suggestion, count }| format!("{suggestion} ({count})"))
+                .collect::<Vec<_>>()
+        };
+
+        let suggest = Suggest::new("sh", &rtxn, &index);
+        insta::assert_debug_snapshot!(suggestions(&suggest), @r###"
+        [
+            "shirt (3)",
+            "shiny (2)",
+            "shoes (2)",
+            "shorts (1)",
+        ]
+        "###);
+
+        // the completions must match the other words of the query
+        let mut suggest = Suggest::new("Red sh", &rtxn, &index);
+        suggest.limit(2);
+        insta::assert_debug_snapshot!(suggestions(&suggest), @r###"
+        [
+            "Red shiny (2)",
+            "Red shirt (1)",
+        ]
+        "###);
+
+        let searchable_attributes = [S("title")];
+        let mut suggest = Suggest::new("sh", &rtxn, &index);
+        suggest.searchable_attributes(&searchable_attributes);
+        suggest.filter(Filter::from_str("color = blue").unwrap().unwrap());
+        insta::assert_debug_snapshot!(suggestions(&suggest), @r###"
+        [
+            "shirt (2)",
+            "shorts (1)",
+        ]
+        "###);
+
+        // the last word is complete
+        let suggest = Suggest::new("red ", &rtxn, &index);
+        insta::assert_debug_snapshot!(suggestions(&suggest), @"[]");
+    }
+
+    #[test]
+    fn suggest_considers_a_limited_number_of_words() {
+        let index = TempIndex::new();
+        // more words starting with `w` than the number of completions considered
+        let letter = |n: usize| char::from(b'a' + (n % 26) as u8);
+        let words: Vec<_> = (0..MAX_PREFIX_COUNT + 100)
+            .map(|i| format!("w{}{}{}", letter(i / 676), letter(i / 26), letter(i)))
+            .collect();
+        index
+            .add_documents(documents!([
+                { "id": 0, "text": words.join(" ") },
+                { "id": 1, "text": "wzzz" },
+                { "id": 2, "text": "wzzz" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut suggest = Suggest::new("w", &rtxn, &index);
+        suggest.limit(1);
+        // `wzzz` is the most frequent word but comes after the considered ones
+        let suggestions = suggest.execute().unwrap();
+        assert_eq!(suggestions, vec![SuggestionHit { suggestion: S("waaa"), count: 1 }]);
+    }
+}
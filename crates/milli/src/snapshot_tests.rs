@@ -262,6 +262,19 @@ pub fn snap_facet_id_is_empty_docids(index: &Index) -> String {
         &format!("{facet_id:<3} {}", display_bitmap(&docids))
     })
 }
+pub fn snap_facet_id_size_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, facet_id_size_docids, |((facet_id, size), docids)| {
+        &format!("{facet_id:<3} {size:<3} {}", display_bitmap(&docids))
+    })
+}
+pub fn snap_facet_id_element_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, facet_id_element_docids, |(
+        (facet_id, position, facet),
+        docids,
+    )| {
+        &format!("{facet_id:<3} {position:<3} {facet:?} {}", display_bitmap(&docids))
+    })
+}
 pub fn snap_facet_id_string_docids(index: &Index) -> String {
     make_db_snap_from_iter!(index, facet_id_string_docids, |(
         FacetGroupKey { field_id, level, left_bound },
@@ -476,6 +489,12 @@ macro_rules! full_snap_of_db {
     ($index:ident, facet_id_is_empty_docids) => {{
         $crate::snapshot_tests::snap_facet_id_is_empty_docids(&$index)
     }};
+    ($index:ident, facet_id_size_docids) => {{
+        $crate::snapshot_tests::snap_facet_id_size_docids(&$index)
+    }};
+    ($index:ident, facet_id_element_docids) => {{
+        $crate::snapshot_tests::snap_facet_id_element_docids(&$index)
+    }};
     ($index:ident, documents_ids) => {{
         $crate::snapshot_tests::snap_documents_ids(&$index)
     }};
@@ -43,8 +43,11 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_size_docids,
+            facet_id_element_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
+            geojson_geometries,
             vector_arroy,
             embedder_category_id: _,
             documents,
@@ -61,6 +64,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
@@ -88,9 +92,12 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         facet_id_exists_docids.clear(self.wtxn)?;
         facet_id_is_null_docids.clear(self.wtxn)?;
         facet_id_is_empty_docids.clear(self.wtxn)?;
+        facet_id_size_docids.clear(self.wtxn)?;
+        facet_id_element_docids.clear(self.wtxn)?;
         facet_id_string_docids.clear(self.wtxn)?;
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
+        geojson_geometries.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
 
@@ -135,6 +142,7 @@ mod tests {
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geojson_rtree(&rtxn).unwrap().is_none());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
@@ -145,6 +153,7 @@ mod tests {
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_strings.is_empty(&rtxn).unwrap());
+        assert!(index.geojson_geometries.is_empty(&rtxn).unwrap());
         assert!(index.documents.is_empty(&rtxn).unwrap());
     }
 }
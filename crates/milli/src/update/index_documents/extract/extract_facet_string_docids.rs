@@ -1,4 +1,5 @@
-use std::collections::BTreeSet;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 use std::iter::FromIterator;
@@ -34,11 +35,13 @@ pub fn extract_facet_string_docids<R: io::Read + io::Seek>(
         extract_facet_string_docids_settings(docid_fid_facet_string, indexer, settings_diff)
     } else {
         let localized_field_ids = &settings_diff.new.localized_faceted_fields_ids;
+        let exact_facets_fields_ids = &settings_diff.new.exact_facets_fields_ids;
         let facet_search = settings_diff.new.facet_search;
         extract_facet_string_docids_document_update(
             docid_fid_facet_string,
             indexer,
             localized_field_ids,
+            exact_facets_fields_ids,
             facet_search,
         )
     }
@@ -53,6 +56,7 @@ fn extract_facet_string_docids_document_update<R: io::Read + io::Seek>(
     docid_fid_facet_string: grenad::Reader<R>,
     indexer: GrenadParameters,
     localized_field_ids: &LocalizedFieldIds,
+    exact_facets_fields_ids: &HashSet<FieldId>,
     facet_search: bool,
 ) -> Result<(grenad::Reader<BufReader<File>>, grenad::Reader<BufReader<File>>)> {
     let max_memory = indexer.max_memory_by_thread();
@@ -101,7 +105,8 @@ fn extract_facet_string_docids_document_update<R: io::Read + io::Seek>(
         // Facet search normalization
         if facet_search {
             let locales = localized_field_ids.locales(field_id);
-            let hyper_normalized_value = normalize_facet_string(normalized_value, locales);
+            let exact = exact_facets_fields_ids.contains(&field_id);
+            let hyper_normalized_value = normalize_facet_string(normalized_value, locales, exact);
 
             let set = BTreeSet::from_iter(std::iter::once(normalized_value));
 
@@ -181,11 +186,15 @@ fn extract_facet_string_docids_settings<R: io::Read + io::Seek>(
         let old_locales = settings_diff.old.localized_faceted_fields_ids.locales(field_id);
         let new_locales = settings_diff.new.localized_faceted_fields_ids.locales(field_id);
 
+        let old_exact = settings_diff.old.exact_facets_fields_ids.contains(&field_id);
+        let new_exact = settings_diff.new.exact_facets_fields_ids.contains(&field_id);
+
         let are_same_locales = old_locales == new_locales;
+        let is_same_normalization = are_same_locales && old_exact == new_exact;
         let reindex_facet_search =
             settings_diff.new.facet_search && !settings_diff.old.facet_search;
 
-        if is_same_value && are_same_locales && !reindex_facet_search {
+        if is_same_value && is_same_normalization && !reindex_facet_search {
             continue;
         }
 
@@ -197,7 +206,8 @@ fn extract_facet_string_docids_settings<R: io::Read + io::Seek>(
 
         // Facet search normalization
         if settings_diff.new.facet_search {
-            let new_hyper_normalized_value = normalize_facet_string(normalized_value, new_locales);
+            let new_hyper_normalized_value =
+                normalize_facet_string(normalized_value, new_locales, new_exact);
             let old_hyper_normalized_value;
             let old_hyper_normalized_value = if !settings_diff.old.facet_search
                 || deladd_reader.get(DelAdd::Deletion).is_none()
@@ -205,10 +215,11 @@ fn extract_facet_string_docids_settings<R: io::Read + io::Seek>(
                 // if the facet search is disabled in the old settings or if no facet string is deleted,
                 // we don't need to normalize the facet string.
                 None
-            } else if are_same_locales {
+            } else if is_same_normalization {
                 Some(&new_hyper_normalized_value)
             } else {
-                old_hyper_normalized_value = normalize_facet_string(normalized_value, old_locales);
+                old_hyper_normalized_value =
+                    normalize_facet_string(normalized_value, old_locales, old_exact);
                 Some(&old_hyper_normalized_value)
             };
 
@@ -286,29 +297,30 @@ fn extract_facet_string_docids_settings<R: io::Read + io::Seek>(
 }
 
 /// Normalizes the facet string and truncates it to the max length.
-fn normalize_facet_string(facet_string: &str, locales: Option<&[Language]>) -> String {
-    let options: NormalizerOption = NormalizerOption { lossy: true, ..Default::default() };
-    let mut detection = StrDetection::new(facet_string, locales);
-
-    let script = detection.script();
-    // Detect the language of the facet string only if several locales are explicitly provided.
-    let language = match locales {
-        Some(&[language]) => Some(language),
-        Some(multiple_locales) if multiple_locales.len() > 1 => detection.language(),
-        _ => None,
-    };
-
-    let token = Token {
-        lemma: std::borrow::Cow::Borrowed(facet_string),
-        script,
-        language,
-        ..Default::default()
+///
+/// The facet strings of an exact facet are only truncated, they are searched case-sensitively.
+fn normalize_facet_string(facet_string: &str, locales: Option<&[Language]>, exact: bool) -> String {
+    let normalized = if exact {
+        Cow::Borrowed(facet_string)
+    } else {
+        let options: NormalizerOption = NormalizerOption { lossy: true, ..Default::default() };
+        let mut detection = StrDetection::new(facet_string, locales);
+
+        let script = detection.script();
+        // Detect the language of the facet string only if several locales are explicitly provided.
+        let language = match locales {
+            Some(&[language]) => Some(language),
+            Some(multiple_locales) if multiple_locales.len() > 1 => detection.language(),
+            _ => None,
+        };
+
+        let token =
+            Token { lemma: Cow::Borrowed(facet_string), script, language, ..Default::default() };
+        token.normalize(&options).lemma
     };
 
     // truncate the facet string to the max length
-    token
-        .normalize(&options)
-        .lemma
+    normalized
         .char_indices()
         .take_while(|(idx, _)| *idx < MAX_FACET_VALUE_LENGTH)
         .map(|(_, c)| c)
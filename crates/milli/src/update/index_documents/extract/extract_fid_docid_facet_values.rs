@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::{self, BufReader};
@@ -15,11 +15,15 @@ use FilterableValues::{Empty, Null, Values};
 
 use super::helpers::{create_sorter, sorter_into_reader, GrenadParameters, KeepFirst};
 use crate::error::InternalError;
+use crate::facet::for_each_element_facet;
 use crate::facet::value_encoding::f64_into_bytes;
+use crate::heed_codec::{FieldIdElementFacetCodec, FieldIdSizeCodec};
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::index_documents::{create_writer, writer_into_reader};
 use crate::update::settings::InnerIndexSettingsDiff;
-use crate::{CboRoaringBitmapCodec, DocumentId, FieldId, Result, MAX_FACET_VALUE_LENGTH};
+use crate::{
+    truncate_facet_value, CboRoaringBitmapCodec, DocumentId, FieldId, FieldsIdsMap, Result,
+};
 
 /// The length of the elements that are always in the buffer when inserting new values.
 const TRUNCATE_SIZE: usize = size_of::<FieldId>() + size_of::<DocumentId>();
@@ -31,6 +35,8 @@ pub struct ExtractedFacetValues {
     pub fid_facet_is_null_docids_chunk: grenad::Reader<BufReader<File>>,
     pub fid_facet_is_empty_docids_chunk: grenad::Reader<BufReader<File>>,
     pub fid_facet_exists_docids_chunk: grenad::Reader<BufReader<File>>,
+    pub fid_facet_size_docids_chunk: grenad::Reader<BufReader<File>>,
+    pub fid_facet_element_docids_chunk: grenad::Reader<BufReader<File>>,
 }
 
 /// Extracts the facet values of each faceted field of each document.
@@ -70,6 +76,8 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     let mut facet_exists_docids = BTreeMap::<FieldId, (RoaringBitmap, RoaringBitmap)>::new();
     let mut facet_is_null_docids = BTreeMap::<FieldId, (RoaringBitmap, RoaringBitmap)>::new();
     let mut facet_is_empty_docids = BTreeMap::<FieldId, (RoaringBitmap, RoaringBitmap)>::new();
+    let mut facet_size_docids = BTreeMap::<(FieldId, u32), (RoaringBitmap, RoaringBitmap)>::new();
+    let mut facet_element_docids = BTreeMap::<Vec<u8>, (RoaringBitmap, RoaringBitmap)>::new();
 
     // We create two buffers for mutable ref issues with closures.
     let mut numbers_key_buffer = Vec::new();
@@ -157,6 +165,32 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         add_exists.insert(document);
                     }
 
+                    // We insert the document id on the Del and the Add side of the values of the elements of a nested field.
+                    if let Some(value) = del_value.as_ref() {
+                        if settings_diff.old.nested_fields_ids.contains(&field_id) {
+                            insert_element_facets(
+                                &mut facet_element_docids,
+                                &settings_diff.old.fields_ids_map,
+                                &settings_diff.old.exact_facets_fields_ids,
+                                field_id,
+                                value,
+                                |(del, _add)| del.insert(document),
+                            )?;
+                        }
+                    }
+                    if let Some(value) = add_value.as_ref() {
+                        if settings_diff.new.nested_fields_ids.contains(&field_id) {
+                            insert_element_facets(
+                                &mut facet_element_docids,
+                                &settings_diff.new.fields_ids_map,
+                                &settings_diff.new.exact_facets_fields_ids,
+                                field_id,
+                                value,
+                                |(_del, add)| add.insert(document),
+                            )?;
+                        }
+                    }
+
                     let del_geo_support = settings_diff
                         .old
                         .geo_fields_ids
@@ -165,10 +199,38 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         .new
                         .geo_fields_ids
                         .map_or(false, |(lat, lng)| field_id == lat || field_id == lng);
-                    let del_filterable_values =
-                        del_value.map(|value| extract_facet_values(&value, del_geo_support));
-                    let add_filterable_values =
-                        add_value.map(|value| extract_facet_values(&value, add_geo_support));
+                    let del_date_field = settings_diff.old.date_fields_ids.contains(&field_id);
+                    let add_date_field = settings_diff.new.date_fields_ids.contains(&field_id);
+                    let del_exact_field =
+                        settings_diff.old.exact_facets_fields_ids.contains(&field_id);
+                    let add_exact_field =
+                        settings_diff.new.exact_facets_fields_ids.contains(&field_id);
+                    let del_size = del_value.as_ref().map(facet_size);
+                    let add_size = add_value.as_ref().map(facet_size);
+                    let del_filterable_values = del_value.map(|value| {
+                        extract_facet_values(
+                            &value,
+                            del_geo_support,
+                            del_date_field,
+                            del_exact_field,
+                        )
+                    });
+                    let add_filterable_values = add_value.map(|value| {
+                        extract_facet_values(
+                            &value,
+                            add_geo_support,
+                            add_date_field,
+                            add_exact_field,
+                        )
+                    });
+
+                    // We insert the document id on the Del and the Add side of the number of values of the field.
+                    if let Some(size) = del_size {
+                        facet_size_docids.entry((field_id, size)).or_default().0.insert(document);
+                    }
+                    if let Some(size) = add_size {
+                        facet_size_docids.entry((field_id, size)).or_default().1.insert(document);
+                    }
 
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
@@ -294,12 +356,67 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     }
     let facet_is_empty_docids_reader = writer_into_reader(facet_is_empty_docids_writer)?;
 
+    let mut facet_size_docids_writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    for ((fid, size), (del_bitmap, add_bitmap)) in facet_size_docids.into_iter() {
+        deladd_obkv_cbo_roaring_bitmaps(&mut buffer, &del_bitmap, &add_bitmap)?;
+        let key = FieldIdSizeCodec::bytes_encode(&(fid, size)).map_err(heed::Error::Encoding)?;
+        facet_size_docids_writer.insert(key, &buffer)?;
+    }
+    let facet_size_docids_reader = writer_into_reader(facet_size_docids_writer)?;
+
+    let mut facet_element_docids_writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    for (key, (del_bitmap, add_bitmap)) in facet_element_docids.into_iter() {
+        deladd_obkv_cbo_roaring_bitmaps(&mut buffer, &del_bitmap, &add_bitmap)?;
+        facet_element_docids_writer.insert(key, &buffer)?;
+    }
+    let facet_element_docids_reader = writer_into_reader(facet_element_docids_writer)?;
+
     Ok(ExtractedFacetValues {
         fid_docid_facet_numbers_chunk: sorter_into_reader(fid_docid_facet_numbers_sorter, indexer)?,
         fid_docid_facet_strings_chunk: sorter_into_reader(fid_docid_facet_strings_sorter, indexer)?,
         fid_facet_is_null_docids_chunk: facet_is_null_docids_reader,
         fid_facet_is_empty_docids_chunk: facet_is_empty_docids_reader,
         fid_facet_exists_docids_chunk: facet_exists_docids_reader,
+        fid_facet_size_docids_chunk: facet_size_docids_reader,
+        fid_facet_element_docids_chunk: facet_element_docids_reader,
+    })
+}
+
+/// Registers the document on one side of the bitmaps of the values of the elements of a nested field.
+fn insert_element_facets(
+    facet_element_docids: &mut BTreeMap<Vec<u8>, (RoaringBitmap, RoaringBitmap)>,
+    fields_ids_map: &FieldsIdsMap,
+    exact_facets_fields_ids: &HashSet<FieldId>,
+    field_id: FieldId,
+    value: &Value,
+    insert: impl Fn(&mut (RoaringBitmap, RoaringBitmap)) -> bool,
+) -> Result<()> {
+    let Some(name) = fields_ids_map.name(field_id) else { return Ok(()) };
+    let is_exact = |path: &str| {
+        fields_ids_map
+            .id(&format!("{name}.{path}"))
+            .is_some_and(|field_id| exact_facets_fields_ids.contains(&field_id))
+    };
+    for_each_element_facet(name, value, &is_exact, &mut |position, path, facet| {
+        let field_id = if path.is_empty() {
+            Some(field_id)
+        } else {
+            fields_ids_map.id(&format!("{name}.{path}"))
+        };
+        if let Some(field_id) = field_id {
+            let key = FieldIdElementFacetCodec::bytes_encode(&(field_id, position, facet))
+                .map_err(heed::Error::Encoding)?;
+            insert(facet_element_docids.entry(key.into_owned()).or_default());
+        }
+        Ok(())
     })
 }
 
@@ -318,18 +435,6 @@ fn deladd_obkv_cbo_roaring_bitmaps(
     obkv.finish()
 }
 
-/// Truncates a string to the biggest valid LMDB key size.
-fn truncate_str(s: &str) -> &str {
-    let index = s
-        .char_indices()
-        .map(|(idx, _)| idx)
-        .chain(std::iter::once(s.len()))
-        .take_while(|idx| idx <= &MAX_FACET_VALUE_LENGTH)
-        .last();
-
-    &s[..index.unwrap_or(0)]
-}
-
 /// Computes the diff between both Del and Add numbers and
 /// only inserts the parts that differ in the sorter.
 fn insert_numbers_diff(
@@ -466,7 +571,7 @@ fn insert_strings_diff(
                     obkv.insert(DelAdd::Addition, add)?;
                 }
 
-                let truncated = truncate_str(normalized);
+                let truncated = truncate_facet_value(normalized);
                 key_buffer.extend_from_slice(truncated.as_bytes());
 
                 let bytes = obkv.into_inner()?;
@@ -490,7 +595,7 @@ fn insert_strings_diff(
                 (DelAdd::Addition, normalized, original)
             }
         };
-        let truncated = truncate_str(normalized);
+        let truncated = truncate_facet_value(normalized);
         key_buffer.extend_from_slice(truncated.as_bytes());
 
         let mut obkv = KvWriterDelAdd::memory();
@@ -512,14 +617,37 @@ enum FilterableValues {
     Values { numbers: Vec<f64>, strings: Vec<(String, String)> },
 }
 
+/// The number of values of a document field: every string, number and boolean it contains,
+/// the arrays being flattened.
+///
+/// The values are counted as they are in the document, an empty string counts as one
+/// value even if it can't be matched by a filter, the same way the new indexer counts them.
+fn facet_size(value: &Value) -> u32 {
+    match value {
+        Value::Null | Value::Object(_) => 0,
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => 1,
+        Value::Array(values) => values.iter().map(facet_size).sum(),
+    }
+}
+
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+///
+/// The strings of a date field that are valid RFC 3339 dates are extracted as numbers
+/// and the strings of an exact field are not normalized.
+fn extract_facet_values(
+    value: &Value,
+    geo_field: bool,
+    date_field: bool,
+    exact_field: bool,
+) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
         geo_field: bool,
+        date_field: bool,
+        exact_field: bool,
     ) {
         match value {
             Value::Null => (),
@@ -541,7 +669,13 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                         )
                     }
                 }
-                let normalized = crate::normalize_facet(original);
+                if date_field {
+                    if let Some(timestamp) = filter_parser::parse_rfc3339_date(original) {
+                        output_numbers.push(timestamp);
+                        return;
+                    }
+                }
+                let normalized = crate::normalize_facet_unless_exact(original, exact_field);
                 output_strings.push((normalized, original.clone()));
             }
             Value::Array(values) => {
@@ -553,6 +687,8 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             output_numbers,
                             output_strings,
                             geo_field,
+                            date_field,
+                            exact_field,
                         );
                     }
                 }
@@ -569,7 +705,15 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                geo_field,
+                date_field,
+                exact_field,
+            );
             FilterableValues::Values { numbers, strings }
         }
     }
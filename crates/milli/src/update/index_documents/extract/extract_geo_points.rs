@@ -9,11 +9,12 @@ use crate::error::GeoError;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::index_documents::extract_finite_float_from_value;
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
-use crate::{FieldId, InternalError, Result};
+use crate::{FieldId, GeoJsonGeometry, InternalError, Result};
 
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
 ///
 /// Returns the generated grenad reader containing the docid as key associated to the (latitude, longitude)
+/// of each of its points.
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -46,14 +47,60 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
 
         if del_lat_lng != add_lat_lng {
             let mut obkv = KvWriterDelAdd::memory();
-            if let Some([lat, lng]) = del_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            if !del_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Deletion, lat_lng_points_to_bytes(&del_lat_lng))?;
+            }
+            if !add_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Addition, lat_lng_points_to_bytes(&add_lat_lng))?;
+            }
+            let bytes = obkv.into_inner()?;
+            writer.insert(docid_bytes, bytes)?;
+        }
+    }
+
+    writer_into_reader(writer)
+}
+
+/// Extracts the GeoJSON geometry contained in each document under the `_geojson` field.
+///
+/// Returns the generated grenad reader containing the docid as key associated to the
+/// JSON serialized geometry.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_geojson_geometries<R: io::Read + io::Seek>(
+    obkv_documents: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    primary_key_id: FieldId,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<grenad::Reader<BufReader<File>>> {
+    let mut writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+
+    let mut cursor = obkv_documents.into_cursor()?;
+    while let Some((docid_bytes, value)) = cursor.move_on_next()? {
+        let obkv = obkv::KvReader::from_slice(value);
+        let document_id = || -> Value {
+            let reader = KvReaderDelAdd::from_slice(obkv.get(primary_key_id).unwrap());
+            let document_id =
+                reader.get(DelAdd::Deletion).or(reader.get(DelAdd::Addition)).unwrap();
+            serde_json::from_slice(document_id).unwrap()
+        };
+
+        let del_geometry =
+            extract_geojson(obkv, &settings_diff.old, DelAdd::Deletion, document_id)?;
+        let add_geometry =
+            extract_geojson(obkv, &settings_diff.new, DelAdd::Addition, document_id)?;
+
+        if del_geometry != add_geometry {
+            let mut obkv = KvWriterDelAdd::memory();
+            if let Some(geometry) = del_geometry {
+                let bytes = serde_json::to_vec(&geometry).map_err(InternalError::SerdeJson)?;
                 obkv.insert(DelAdd::Deletion, bytes)?;
             }
-            if let Some([lat, lng]) = add_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            if let Some(geometry) = add_geometry {
+                let bytes = serde_json::to_vec(&geometry).map_err(InternalError::SerdeJson)?;
                 obkv.insert(DelAdd::Addition, bytes)?;
             }
             let bytes = obkv.into_inner()?;
@@ -64,13 +111,49 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     writer_into_reader(writer)
 }
 
+/// Extract and validate the GeoJSON geometry of a document.
+fn extract_geojson(
+    document: &obkv::KvReader<FieldId>,
+    settings: &InnerIndexSettings,
+    deladd: DelAdd,
+    document_id: impl Fn() -> Value,
+) -> Result<Option<GeoJsonGeometry>> {
+    let Some(geojson_fid) = settings.geojson_fid else { return Ok(None) };
+    let Some(value) =
+        document.get(geojson_fid).map(KvReaderDelAdd::from_slice).and_then(|r| r.get(deladd))
+    else {
+        return Ok(None);
+    };
+    match serde_json::from_slice(value).map_err(InternalError::SerdeJson)? {
+        Value::Null => Ok(None),
+        value => GeoJsonGeometry::from_value(&value)
+            .map(Some)
+            .map_err(|reason| GeoError::BadGeoJson { document_id: document_id(), reason }.into()),
+    }
+}
+
+/// Concatenates the latitude and longitude of every point, 16 bytes per point.
+fn lat_lng_points_to_bytes(points: &[[f64; 2]]) -> Vec<u8> {
+    points
+        .iter()
+        .flat_map(|[lat, lng]| {
+            #[allow(clippy::drop_non_drop)]
+            let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            bytes
+        })
+        .collect()
+}
+
 /// Extract the finite floats lat and lng from two bytes slices.
+///
+/// When the document is located at several places the flattened lat and lng
+/// are arrays containing the coordinates of each point in the same order.
 fn extract_lat_lng(
     document: &obkv::KvReader<FieldId>,
     settings: &InnerIndexSettings,
     deladd: DelAdd,
     document_id: impl Fn() -> Value,
-) -> Result<Option<[f64; 2]>> {
+) -> Result<Vec<[f64; 2]>> {
     match settings.geo_fields_ids {
         Some((lat_fid, lng_fid)) => {
             let lat =
@@ -85,19 +168,34 @@ fn extract_lat_lng(
                 (None, Some(_)) => {
                     return Err(GeoError::MissingLongitude { document_id: document_id() }.into())
                 }
-                (None, None) => return Ok(None),
+                (None, None) => return Ok(Vec::new()),
+            };
+            let lat: Value = serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?;
+            let lng: Value = serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?;
+            let (lats, lngs) = match (lat, lng) {
+                (Value::Array(lats), Value::Array(lngs)) => (lats, lngs),
+                (lat, lng) => (vec![lat], vec![lng]),
             };
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })?;
-
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })?;
-            Ok(Some([lat, lng]))
+            if lats.len() > lngs.len() {
+                return Err(GeoError::MissingLongitude { document_id: document_id() }.into());
+            }
+            if lats.len() < lngs.len() {
+                return Err(GeoError::MissingLatitude { document_id: document_id() }.into());
+            }
+
+            lats.into_iter()
+                .zip(lngs)
+                .map(|(lat, lng)| {
+                    let lat = extract_finite_float_from_value(lat).map_err(|lat| {
+                        GeoError::BadLatitude { document_id: document_id(), value: lat }
+                    })?;
+                    let lng = extract_finite_float_from_value(lng).map_err(|lng| {
+                        GeoError::BadLongitude { document_id: document_id(), value: lng }
+                    })?;
+                    Ok([lat, lng])
+                })
+                .collect()
         }
-        None => Ok(None),
+        None => Ok(Vec::new()),
     }
 }
@@ -21,7 +21,7 @@ use self::extract_facet_number_docids::extract_facet_number_docids;
 use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
-use self::extract_geo_points::extract_geo_points;
+use self::extract_geo_points::{extract_geo_points, extract_geojson_geometries};
 use self::extract_vector_points::{
     extract_embeddings, extract_vector_points, ExtractedVectorPoints,
 };
@@ -343,6 +343,24 @@ fn send_and_extract_flattened_documents_data(
         });
     }
 
+    if settings_diff.run_geojson_indexing() {
+        let documents_chunk_cloned = flattened_documents_chunk.clone();
+        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        let settings_diff = settings_diff.clone();
+        rayon::spawn(move || {
+            let result = extract_geojson_geometries(
+                documents_chunk_cloned,
+                indexer,
+                primary_key_id,
+                &settings_diff,
+            );
+            let _ = match result {
+                Ok(geojson) => lmdb_writer_sx_cloned.send(Ok(TypedChunk::GeoJson(geojson))),
+                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
+            };
+        });
+    }
+
     let (docid_word_positions_chunk, fid_docid_facet_values_chunks): (Result<_>, Result<_>) =
         rayon::join(
             || {
@@ -366,6 +384,8 @@ fn send_and_extract_flattened_documents_data(
                     fid_facet_is_null_docids_chunk,
                     fid_facet_is_empty_docids_chunk,
                     fid_facet_exists_docids_chunk,
+                    fid_facet_size_docids_chunk,
+                    fid_facet_element_docids_chunk,
                 } = extract_fid_docid_facet_values(
                     flattened_documents_chunk.clone(),
                     indexer,
@@ -398,6 +418,13 @@ fn send_and_extract_flattened_documents_data(
                 let _ = lmdb_writer_sx
                     .send(Ok(TypedChunk::FieldIdFacetExistsDocids(fid_facet_exists_docids_chunk)));
 
+                let _ = lmdb_writer_sx
+                    .send(Ok(TypedChunk::FieldIdFacetSizeDocids(fid_facet_size_docids_chunk)));
+
+                let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdFacetElementDocids(
+                    fid_facet_element_docids_chunk,
+                )));
+
                 Ok((fid_docid_facet_numbers_chunk, fid_docid_facet_strings_chunk))
             },
         );
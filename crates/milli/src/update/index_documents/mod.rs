@@ -1066,6 +1066,26 @@ mod tests {
             &error.to_string(),
             r#"Could not parse longitude in the document with the id: `"0"`. Was expecting a finite number but instead got `"hello"`."#
         );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 12, "lng": 42 }, { "lat": 12 }] }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"Could not find longitude in the document with the id: `"0"`. Was expecting a `_geo.lng` field."#
+        );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 12, "lng": 42 }, "hello"] }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"The `_geo` field in the document with the id: `"0"` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `"hello"`."#
+        );
     }
 
     #[test]
@@ -28,8 +28,8 @@ use crate::update::index_documents::helpers::{
 use crate::update::settings::InnerIndexSettingsDiff;
 use crate::vector::ArroyWrapper;
 use crate::{
-    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Index, InternalError,
-    Result, SerializationError, U8StrStrCodec,
+    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoJsonGeometry, GeoJsonShape,
+    GeoPoint, Index, InternalError, Result, SerializationError, U8StrStrCodec,
 };
 
 /// This struct accumulates and group the TypedChunks
@@ -83,7 +83,10 @@ pub(crate) enum TypedChunk {
     FieldIdFacetExistsDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetIsNullDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetIsEmptyDocids(grenad::Reader<BufReader<File>>),
+    FieldIdFacetSizeDocids(grenad::Reader<BufReader<File>>),
+    FieldIdFacetElementDocids(grenad::Reader<BufReader<File>>),
     GeoPoints(grenad::Reader<BufReader<File>>),
+    GeoJson(grenad::Reader<BufReader<File>>),
     VectorPoints {
         remove_vectors: grenad::Reader<BufReader<File>>,
         embeddings: Option<grenad::Reader<BufReader<File>>>,
@@ -111,7 +114,10 @@ impl TypedChunk {
             | (FieldIdFacetExistsDocids(_), FieldIdFacetExistsDocids(_))
             | (FieldIdFacetIsNullDocids(_), FieldIdFacetIsNullDocids(_))
             | (FieldIdFacetIsEmptyDocids(_), FieldIdFacetIsEmptyDocids(_))
-            | (GeoPoints(_), GeoPoints(_)) => true,
+            | (FieldIdFacetSizeDocids(_), FieldIdFacetSizeDocids(_))
+            | (FieldIdFacetElementDocids(_), FieldIdFacetElementDocids(_))
+            | (GeoPoints(_), GeoPoints(_))
+            | (GeoJson(_), GeoJson(_)) => true,
             (
                 VectorPoints { embedder_name: left, expected_dimension: left_dim, .. },
                 VectorPoints { embedder_name: right, expected_dimension: right_dim, .. },
@@ -472,6 +478,54 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::FieldIdFacetSizeDocids(_) => {
+            let span =
+                tracing::trace_span!(target: "indexing::write_db", "field_id_facet_size_docids");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(MergeDeladdCboRoaringBitmaps);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::FieldIdFacetSizeDocids(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            write_entries_into_database(
+                merger,
+                &index.facet_id_size_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
+        TypedChunk::FieldIdFacetElementDocids(_) => {
+            let span =
+                tracing::trace_span!(target: "indexing::write_db", "field_id_facet_element_docids");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(MergeDeladdCboRoaringBitmaps);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::FieldIdFacetElementDocids(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            write_entries_into_database(
+                merger,
+                &index.facet_id_element_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::WordPairProximityDocids(_) => {
             let span =
                 tracing::trace_span!(target: "indexing::write_db", "word_pair_proximity_docids");
@@ -597,19 +651,57 @@ pub(crate) fn write_typed_chunk_into_index(
 
                 let deladd_obkv = KvReaderDelAdd::from_slice(value);
                 if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.remove(&geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.remove(&geopoint);
+                    }
                     geo_faceted_docids.remove(docid);
                 }
                 if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.insert(geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.insert(geopoint);
+                    }
                     geo_faceted_docids.insert(docid);
                 }
             }
             index.put_geo_rtree(wtxn, &rtree)?;
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
+        TypedChunk::GeoJson(_) => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "geojson");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(KeepFirst);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::GeoJson(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            let mut rtree = index.geojson_rtree(wtxn)?.unwrap_or_default();
+
+            let mut iter = merger.into_stream_merger_iter()?;
+            while let Some((key, value)) = iter.next()? {
+                // convert the key back to a u32 (4 bytes)
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+
+                // the rtree only contains the bounding boxes of the geometries
+                let deladd_obkv = KvReaderDelAdd::from_slice(value);
+                if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
+                    let geometry = extract_geojson_geometry(value)?;
+                    rtree.remove(&GeoJsonShape::new(geometry.bounding_box(), docid));
+                    index.geojson_geometries.delete(wtxn, &docid)?;
+                }
+                if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
+                    let geometry = extract_geojson_geometry(value)?;
+                    rtree.insert(GeoJsonShape::new(geometry.bounding_box(), docid));
+                    index.geojson_geometries.put(wtxn, &docid, &geometry)?;
+                }
+            }
+            index.put_geojson_rtree(wtxn, &rtree)?;
+        }
         TypedChunk::VectorPoints { .. } => {
             let span = tracing::trace_span!(target: "indexing::write_db", "vector_points");
             let _entered = span.enter();
@@ -735,13 +827,20 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-/// Converts the latitude and longitude back to an xyz GeoPoint.
-pub fn extract_geo_point(value: &[u8], docid: DocumentId) -> GeoPoint {
-    let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
-    let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
-    let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
-    let xyz_point = lat_lng_to_xyz(&point);
-    GeoPoint::new(xyz_point, (docid, point))
+/// Converts the latitudes and longitudes back to xyz GeoPoints.
+pub fn extract_geo_points(value: &[u8], docid: DocumentId) -> impl Iterator<Item = GeoPoint> + '_ {
+    value.chunks_exact(16).map(move |bytes| {
+        let (lat, tail) = helpers::try_split_array_at::<u8, 8>(bytes).unwrap();
+        let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+        let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
+        let xyz_point = lat_lng_to_xyz(&point);
+        GeoPoint::new(xyz_point, (docid, point))
+    })
+}
+
+/// Converts the serialized GeoJSON geometry back to a GeoJsonGeometry.
+fn extract_geojson_geometry(value: &[u8]) -> Result<GeoJsonGeometry> {
+    Ok(serde_json::from_slice(value).map_err(InternalError::SerdeJson)?)
 }
 
 fn merge_word_docids_reader_into_fst<MF>(
@@ -22,10 +22,10 @@ use super::thread_local::{FullySend, ThreadLocal};
 use super::StdResult;
 use crate::heed_codec::facet::{FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec};
 use crate::index::db_name;
-use crate::index::main_key::{GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
+use crate::index::main_key::{GEOJSON_RTREE_KEY, GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
 use crate::update::new::KvReaderFieldId;
 use crate::vector::Embedding;
-use crate::{CboRoaringBitmapCodec, DocumentId, Error, Index, InternalError};
+use crate::{CboRoaringBitmapCodec, DocumentId, Error, GeoJsonGeometry, Index, InternalError};
 
 /// Creates a tuple of senders/receiver to be used by
 /// the extractors and the writer loop.
@@ -354,11 +354,14 @@ pub enum Database {
     WordPositionDocids,
     FacetIdIsNullDocids,
     FacetIdIsEmptyDocids,
+    FacetIdSizeDocids,
+    FacetIdElementDocids,
     FacetIdExistsDocids,
     FacetIdF64Docids,
     FacetIdStringDocids,
     FieldIdDocidFacetStrings,
     FieldIdDocidFacetF64s,
+    GeoJsonGeometries,
 }
 
 impl Database {
@@ -375,11 +378,14 @@ impl Database {
             Database::WordPairProximityDocids => index.word_pair_proximity_docids.remap_types(),
             Database::FacetIdIsNullDocids => index.facet_id_is_null_docids.remap_types(),
             Database::FacetIdIsEmptyDocids => index.facet_id_is_empty_docids.remap_types(),
+            Database::FacetIdSizeDocids => index.facet_id_size_docids.remap_types(),
+            Database::FacetIdElementDocids => index.facet_id_element_docids.remap_types(),
             Database::FacetIdExistsDocids => index.facet_id_exists_docids.remap_types(),
             Database::FacetIdF64Docids => index.facet_id_f64_docids.remap_types(),
             Database::FacetIdStringDocids => index.facet_id_string_docids.remap_types(),
             Database::FieldIdDocidFacetStrings => index.field_id_docid_facet_strings.remap_types(),
             Database::FieldIdDocidFacetF64s => index.field_id_docid_facet_f64s.remap_types(),
+            Database::GeoJsonGeometries => index.geojson_geometries.remap_types(),
         }
     }
 
@@ -396,11 +402,14 @@ impl Database {
             Database::WordPairProximityDocids => db_name::WORD_PAIR_PROXIMITY_DOCIDS,
             Database::FacetIdIsNullDocids => db_name::FACET_ID_IS_NULL_DOCIDS,
             Database::FacetIdIsEmptyDocids => db_name::FACET_ID_IS_EMPTY_DOCIDS,
+            Database::FacetIdSizeDocids => db_name::FACET_ID_SIZE_DOCIDS,
+            Database::FacetIdElementDocids => db_name::FACET_ID_ELEMENT_DOCIDS,
             Database::FacetIdExistsDocids => db_name::FACET_ID_EXISTS_DOCIDS,
             Database::FacetIdF64Docids => db_name::FACET_ID_F64_DOCIDS,
             Database::FacetIdStringDocids => db_name::FACET_ID_STRING_DOCIDS,
             Database::FieldIdDocidFacetStrings => db_name::FIELD_ID_DOCID_FACET_STRINGS,
             Database::FieldIdDocidFacetF64s => db_name::FIELD_ID_DOCID_FACET_F64S,
+            Database::GeoJsonGeometries => db_name::GEOJSON_GEOMETRIES,
         }
     }
 }
@@ -413,6 +422,8 @@ impl From<FacetKind> for Database {
             FacetKind::Null => Database::FacetIdIsNullDocids,
             FacetKind::Empty => Database::FacetIdIsEmptyDocids,
             FacetKind::Exists => Database::FacetIdExistsDocids,
+            FacetKind::Size => Database::FacetIdSizeDocids,
+            FacetKind::Element => Database::FacetIdElementDocids,
         }
     }
 }
@@ -753,7 +764,11 @@ impl FacetDocidsSender<'_, '_> {
             // We must take the facet group size into account
             // when we serialize strings and numbers.
             FacetKind::Number | FacetKind::String => value_length + 1,
-            FacetKind::Null | FacetKind::Empty | FacetKind::Exists => value_length,
+            FacetKind::Null
+            | FacetKind::Empty
+            | FacetKind::Exists
+            | FacetKind::Size
+            | FacetKind::Element => value_length,
         };
         let key_length = key.len().try_into().ok().and_then(NonZeroU16::new).ok_or_else(|| {
             InternalError::StorePut {
@@ -779,7 +794,11 @@ impl FacetDocidsSender<'_, '_> {
                         *first = 1;
                         remaining
                     }
-                    FacetKind::Null | FacetKind::Empty | FacetKind::Exists => value_out,
+                    FacetKind::Null
+                    | FacetKind::Empty
+                    | FacetKind::Exists
+                    | FacetKind::Size
+                    | FacetKind::Element => value_out,
                 };
 
                 CboRoaringBitmapCodec::serialize_into_writer(bitmap, value_out)?;
@@ -885,6 +904,30 @@ impl GeoSender<'_, '_> {
             .map_err(|_| SendError(()))
     }
 
+    pub fn set_geojson_rtree(&self, value: Mmap) -> StdResult<(), SendError<()>> {
+        self.0
+            .sender
+            .send(ReceiverAction::LargeEntry(LargeEntry {
+                database: Database::Main,
+                key: GEOJSON_RTREE_KEY.to_string().into_bytes().into_boxed_slice(),
+                value,
+            }))
+            .map_err(|_| SendError(()))
+    }
+
+    pub fn write_geojson_geometry(
+        &self,
+        docid: DocumentId,
+        geometry: &GeoJsonGeometry,
+    ) -> crate::Result<()> {
+        let value = bincode::serialize(geometry).map_err(InternalError::BincodeError)?;
+        self.0.write_key_value(Database::GeoJsonGeometries, &docid.to_be_bytes(), &value)
+    }
+
+    pub fn delete_geojson_geometry(&self, docid: DocumentId) -> crate::Result<()> {
+        self.0.delete_entry(Database::GeoJsonGeometries, &docid.to_be_bytes())
+    }
+
     pub fn set_geo_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
         let database = Database::Main;
         let value_length = bitmap.serialized_size();
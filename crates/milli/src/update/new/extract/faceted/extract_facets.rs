@@ -5,13 +5,14 @@ use std::ops::DerefMut as _;
 use bumpalo::collections::Vec as BVec;
 use bumpalo::Bump;
 use hashbrown::HashMap;
-use heed::RoTxn;
+use heed::{BytesEncode, RoTxn};
 use serde_json::Value;
 
 use super::super::cache::BalancedCaches;
 use super::facet_document::extract_document_facets;
 use super::FacetKind;
 use crate::heed_codec::facet::OrderedF64Codec;
+use crate::heed_codec::{ElementFacet, FieldIdElementFacetCodec};
 use crate::update::del_add::DelAdd;
 use crate::update::new::channel::FieldIdDocidFacetSender;
 use crate::update::new::extract::perm_json_p;
@@ -23,10 +24,13 @@ use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, ThreadLocal};
 use crate::update::new::DocumentChange;
 use crate::update::GrenadParameters;
-use crate::{DocumentId, FieldId, Index, Result, MAX_FACET_VALUE_LENGTH};
+use crate::{truncate_facet_value, DocumentId, FieldId, Index, Result};
 
 pub struct FacetedExtractorData<'a, 'b> {
     attributes_to_extract: &'a [&'a str],
+    date_attributes: &'a [&'a str],
+    nested_attributes: &'a [&'a str],
+    exact_facets: &'a [&'a str],
     sender: &'a FieldIdDocidFacetSender<'a, 'b>,
     grenad_parameters: &'a GrenadParameters,
     buckets: usize,
@@ -53,6 +57,9 @@ impl<'a, 'b, 'extractor> Extractor<'extractor> for FacetedExtractorData<'a, 'b>
             FacetedDocidsExtractor::extract_document_change(
                 context,
                 self.attributes_to_extract,
+                self.date_attributes,
+                self.nested_attributes,
+                self.exact_facets,
                 change,
                 self.sender,
             )?
@@ -67,6 +74,9 @@ impl FacetedDocidsExtractor {
     fn extract_document_change(
         context: &DocumentChangeContext<RefCell<BalancedCaches>>,
         attributes_to_extract: &[&str],
+        date_attributes: &[&str],
+        nested_attributes: &[&str],
+        exact_facets: &[&str],
         document_change: DocumentChange,
         sender: &FieldIdDocidFacetSender,
     ) -> Result<()> {
@@ -75,14 +85,20 @@ impl FacetedDocidsExtractor {
         let mut new_fields_ids_map = context.new_fields_ids_map.borrow_mut_or_yield();
         let mut cached_sorter = context.data.borrow_mut_or_yield();
         let mut del_add_facet_value = DelAddFacetValue::new(&context.doc_alloc);
+        let mut value_counts = FacetValueCounts::new(&context.doc_alloc);
+        let mut element_facets = ElementFacetKeys::new(&context.doc_alloc);
         let docid = document_change.docid();
         let res = match document_change {
             DocumentChange::Deletion(inner) => extract_document_facets(
                 attributes_to_extract,
+                date_attributes,
+                nested_attributes,
+                exact_facets,
                 inner.current(rtxn, index, context.db_fields_ids_map)?,
                 inner.external_document_id(),
                 new_fields_ids_map.deref_mut(),
-                &mut |fid, depth, value| {
+                &mut |fid, depth, value, exact| {
+                    value_counts.count(fid, value);
                     Self::facet_fn_with_options(
                         &context.doc_alloc,
                         cached_sorter.deref_mut(),
@@ -93,9 +109,26 @@ impl FacetedDocidsExtractor {
                         fid,
                         depth,
                         value,
+                        exact,
                     )
                 },
-            ),
+                &mut |fid, position, facet| element_facets.insert(fid, position, facet),
+            )
+            .and_then(|()| {
+                value_counts.send_data(
+                    docid,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_del_u32,
+                    &context.doc_alloc,
+                )
+            })
+            .and_then(|()| {
+                element_facets.send_data(
+                    docid,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_del_u32,
+                )
+            }),
             DocumentChange::Update(inner) => {
                 if !inner.has_changed_for_fields(
                     Some(attributes_to_extract),
@@ -108,10 +141,14 @@ impl FacetedDocidsExtractor {
 
                 extract_document_facets(
                     attributes_to_extract,
+                    date_attributes,
+                    nested_attributes,
+                    exact_facets,
                     inner.current(rtxn, index, context.db_fields_ids_map)?,
                     inner.external_document_id(),
                     new_fields_ids_map.deref_mut(),
-                    &mut |fid, depth, value| {
+                    &mut |fid, depth, value, exact| {
+                        value_counts.count(fid, value);
                         Self::facet_fn_with_options(
                             &context.doc_alloc,
                             cached_sorter.deref_mut(),
@@ -122,16 +159,33 @@ impl FacetedDocidsExtractor {
                             fid,
                             depth,
                             value,
+                            exact,
                         )
                     },
+                    &mut |fid, position, facet| element_facets.insert(fid, position, facet),
+                )?;
+                value_counts.send_data(
+                    docid,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_del_u32,
+                    &context.doc_alloc,
+                )?;
+                element_facets.send_data(
+                    docid,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_del_u32,
                 )?;
 
                 extract_document_facets(
                     attributes_to_extract,
+                    date_attributes,
+                    nested_attributes,
+                    exact_facets,
                     inner.merged(rtxn, index, context.db_fields_ids_map)?,
                     inner.external_document_id(),
                     new_fields_ids_map.deref_mut(),
-                    &mut |fid, depth, value| {
+                    &mut |fid, depth, value, exact| {
+                        value_counts.count(fid, value);
                         Self::facet_fn_with_options(
                             &context.doc_alloc,
                             cached_sorter.deref_mut(),
@@ -142,16 +196,37 @@ impl FacetedDocidsExtractor {
                             fid,
                             depth,
                             value,
+                            exact,
                         )
                     },
+                    &mut |fid, position, facet| element_facets.insert(fid, position, facet),
                 )
+                .and_then(|()| {
+                    value_counts.send_data(
+                        docid,
+                        cached_sorter.deref_mut(),
+                        BalancedCaches::insert_add_u32,
+                        &context.doc_alloc,
+                    )
+                })
+                .and_then(|()| {
+                    element_facets.send_data(
+                        docid,
+                        cached_sorter.deref_mut(),
+                        BalancedCaches::insert_add_u32,
+                    )
+                })
             }
             DocumentChange::Insertion(inner) => extract_document_facets(
                 attributes_to_extract,
+                date_attributes,
+                nested_attributes,
+                exact_facets,
                 inner.inserted(),
                 inner.external_document_id(),
                 new_fields_ids_map.deref_mut(),
-                &mut |fid, depth, value| {
+                &mut |fid, depth, value, exact| {
+                    value_counts.count(fid, value);
                     Self::facet_fn_with_options(
                         &context.doc_alloc,
                         cached_sorter.deref_mut(),
@@ -162,9 +237,26 @@ impl FacetedDocidsExtractor {
                         fid,
                         depth,
                         value,
+                        exact,
                     )
                 },
-            ),
+                &mut |fid, position, facet| element_facets.insert(fid, position, facet),
+            )
+            .and_then(|()| {
+                value_counts.send_data(
+                    docid,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_add_u32,
+                    &context.doc_alloc,
+                )
+            })
+            .and_then(|()| {
+                element_facets.send_data(
+                    docid,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_add_u32,
+                )
+            }),
         };
 
         del_add_facet_value.send_data(docid, sender, &context.doc_alloc).unwrap();
@@ -182,6 +274,7 @@ impl FacetedDocidsExtractor {
         fid: FieldId,
         depth: perm_json_p::Depth,
         value: &Value,
+        exact: bool,
     ) -> Result<()> {
         let mut buffer = BVec::new_in(doc_alloc);
         // Exists
@@ -220,9 +313,12 @@ impl FacetedDocidsExtractor {
                 let mut string = BVec::new_in(doc_alloc);
                 string.extend_from_slice(s.as_bytes());
                 facet_fn(del_add_facet_value, fid, string, FacetKind::String);
+                if exact {
+                    del_add_facet_value.exact_fids.insert(fid);
+                }
 
-                let normalized = crate::normalize_facet(s);
-                let truncated = truncate_str(&normalized);
+                let normalized = crate::normalize_facet_unless_exact(s, exact);
+                let truncated = truncate_facet_value(&normalized);
                 buffer.clear();
                 buffer.push(FacetKind::String as u8);
                 buffer.extend_from_slice(&fid.to_be_bytes());
@@ -285,11 +381,17 @@ impl FacetedDocidsExtractor {
 struct DelAddFacetValue<'doc> {
     strings: HashMap<(FieldId, BVec<'doc, u8>), DelAdd, hashbrown::DefaultHashBuilder, &'doc Bump>,
     f64s: HashMap<(FieldId, BVec<'doc, u8>), DelAdd, hashbrown::DefaultHashBuilder, &'doc Bump>,
+    /// The fields whose strings are exact facets, that must not be normalized.
+    exact_fids: hashbrown::HashSet<FieldId, hashbrown::DefaultHashBuilder, &'doc Bump>,
 }
 
 impl<'doc> DelAddFacetValue<'doc> {
     fn new(doc_alloc: &'doc Bump) -> Self {
-        Self { strings: HashMap::new_in(doc_alloc), f64s: HashMap::new_in(doc_alloc) }
+        Self {
+            strings: HashMap::new_in(doc_alloc),
+            f64s: HashMap::new_in(doc_alloc),
+            exact_fids: hashbrown::HashSet::new_in(doc_alloc),
+        }
     }
 
     fn insert_add(&mut self, fid: FieldId, value: BVec<'doc, u8>, kind: FacetKind) {
@@ -334,8 +436,9 @@ impl<'doc> DelAddFacetValue<'doc> {
                 buffer.clear();
                 buffer.extend_from_slice(&fid.to_be_bytes());
                 buffer.extend_from_slice(&docid.to_be_bytes());
-                let normalized = crate::normalize_facet(s);
-                let truncated = truncate_str(&normalized);
+                let exact = self.exact_fids.contains(&fid);
+                let normalized = crate::normalize_facet_unless_exact(s, exact);
+                let truncated = truncate_facet_value(&normalized);
                 buffer.extend_from_slice(truncated.as_bytes());
                 match deladd {
                     DelAdd::Deletion => sender.delete_facet_string(&buffer)?,
@@ -359,16 +462,88 @@ impl<'doc> DelAddFacetValue<'doc> {
     }
 }
 
-/// Truncates a string to the biggest valid LMDB key size.
-fn truncate_str(s: &str) -> &str {
-    let index = s
-        .char_indices()
-        .map(|(idx, _)| idx)
-        .chain(std::iter::once(s.len()))
-        .take_while(|idx| idx <= &MAX_FACET_VALUE_LENGTH)
-        .last();
+/// Counts the values of each facet of a document to compute the size of the facets.
+struct FacetValueCounts<'doc> {
+    counts: HashMap<FieldId, u32, hashbrown::DefaultHashBuilder, &'doc Bump>,
+}
+
+impl<'doc> FacetValueCounts<'doc> {
+    fn new(doc_alloc: &'doc Bump) -> Self {
+        Self { counts: HashMap::new_in(doc_alloc) }
+    }
+
+    /// Registers the facet as existing and counts the value if it is a number, a bool or a string.
+    ///
+    /// The arrays are given element by element, so only their scalar values are counted.
+    /// The values are counted as they are in the document, an empty string counts as one
+    /// value even if it can't be matched by a filter, the same way the settings reindexing
+    /// counts them.
+    fn count(&mut self, fid: FieldId, value: &Value) {
+        let count = self.counts.entry(fid).or_default();
+        if let Value::Number(_) | Value::Bool(_) | Value::String(_) = value {
+            *count += 1;
+        }
+    }
+
+    /// Sends the size of every counted facet and resets the counts.
+    fn send_data<'extractor>(
+        &mut self,
+        docid: DocumentId,
+        cached_sorter: &mut BalancedCaches<'extractor>,
+        cache_fn: impl Fn(&mut BalancedCaches<'extractor>, &[u8], u32) -> Result<()>,
+        doc_alloc: &Bump,
+    ) -> Result<()> {
+        let mut buffer = BVec::new_in(doc_alloc);
+        // Size
+        // key: fid - size
+        for (fid, size) in self.counts.drain() {
+            buffer.clear();
+            buffer.push(FacetKind::Size as u8);
+            buffer.extend_from_slice(&fid.to_be_bytes());
+            buffer.extend_from_slice(&size.to_be_bytes());
+            cache_fn(cached_sorter, &buffer, docid)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the facet values of the elements of the nested fields of a document.
+struct ElementFacetKeys<'doc> {
+    keys: BVec<'doc, BVec<'doc, u8>>,
+    doc_alloc: &'doc Bump,
+}
 
-    &s[..index.unwrap_or(0)]
+impl<'doc> ElementFacetKeys<'doc> {
+    fn new(doc_alloc: &'doc Bump) -> Self {
+        Self { keys: BVec::new_in(doc_alloc), doc_alloc }
+    }
+
+    fn insert(&mut self, fid: FieldId, position: u16, facet: ElementFacet<'_>) -> Result<()> {
+        // Element
+        // key: fid - position - facet
+        let mut key = BVec::new_in(self.doc_alloc);
+        key.push(FacetKind::Element as u8);
+        let bytes = FieldIdElementFacetCodec::bytes_encode(&(fid, position, facet))
+            .map_err(heed::Error::Encoding)?;
+        key.extend_from_slice(&bytes);
+        self.keys.push(key);
+        Ok(())
+    }
+
+    /// Sends the collected element facet values and forgets them.
+    fn send_data<'extractor>(
+        &mut self,
+        docid: DocumentId,
+        cached_sorter: &mut BalancedCaches<'extractor>,
+        cache_fn: impl Fn(&mut BalancedCaches<'extractor>, &[u8], u32) -> Result<()>,
+    ) -> Result<()> {
+        for key in self.keys.drain(..) {
+            cache_fn(cached_sorter, &key, docid)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl FacetedDocidsExtractor {
@@ -388,6 +563,9 @@ impl FacetedDocidsExtractor {
         let attributes_to_extract = Self::attributes_to_extract(&rtxn, index)?;
         let attributes_to_extract: Vec<_> =
             attributes_to_extract.iter().map(|s| s.as_ref()).collect();
+        let date_attributes = index.date_attributes(&rtxn)?;
+        let nested_attributes = index.nested_attributes(&rtxn)?;
+        let exact_facets = index.exact_facets(&rtxn)?;
         let datastore = ThreadLocal::new();
 
         {
@@ -397,6 +575,9 @@ impl FacetedDocidsExtractor {
 
             let extractor = FacetedExtractorData {
                 attributes_to_extract: &attributes_to_extract,
+                date_attributes: &date_attributes,
+                nested_attributes: &nested_attributes,
+                exact_facets: &exact_facets,
                 grenad_parameters: indexing_context.grenad_parameters,
                 buckets: rayon::current_num_threads(),
                 sender,
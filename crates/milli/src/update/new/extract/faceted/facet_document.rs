@@ -1,6 +1,8 @@
 use serde_json::Value;
 
 use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::facet::for_each_element_facet;
+use crate::heed_codec::ElementFacet;
 use crate::update::new::document::Document;
 use crate::update::new::extract::geo::extract_geo_coordinates;
 use crate::update::new::extract::perm_json_p;
@@ -8,10 +10,14 @@ use crate::{FieldId, GlobalFieldsIdsMap, InternalError, Result, UserError};
 
 pub fn extract_document_facets<'doc>(
     attributes_to_extract: &[&str],
+    date_attributes: &[&str],
+    nested_attributes: &[&str],
+    exact_facets: &[&str],
     document: impl Document<'doc>,
     external_document_id: &str,
     field_id_map: &mut GlobalFieldsIdsMap,
-    facet_fn: &mut impl FnMut(FieldId, perm_json_p::Depth, &Value) -> Result<()>,
+    facet_fn: &mut impl FnMut(FieldId, perm_json_p::Depth, &Value, bool) -> Result<()>,
+    element_fn: &mut impl FnMut(FieldId, u16, ElementFacet<'_>) -> Result<()>,
 ) -> Result<()> {
     for res in document.iter_top_level_fields() {
         let (field_name, value) = res?;
@@ -20,7 +26,41 @@ pub fn extract_document_facets<'doc>(
             |name: &str, depth: perm_json_p::Depth, value: &Value| match field_id_map
                 .id_or_insert(name)
             {
-                Some(field_id) => facet_fn(field_id, depth, value),
+                Some(field_id) => {
+                    // the elements of a nested attribute are also extracted one by one
+                    if depth == perm_json_p::Depth::OnBaseKey && nested_attributes.contains(&name) {
+                        extract_element_facets(
+                            name,
+                            field_id,
+                            value,
+                            exact_facets,
+                            field_id_map,
+                            element_fn,
+                        )?;
+                    }
+
+                    // the strings of an exact facet are not normalized
+                    let exact = exact_facets.iter().any(|attr| crate::is_faceted_by(name, attr));
+
+                    // the strings of a date attribute that are valid RFC 3339 dates are extracted as numbers
+                    match value {
+                        Value::String(s)
+                            if date_attributes
+                                .iter()
+                                .any(|attr| crate::is_faceted_by(name, attr)) =>
+                        {
+                            match filter_parser::parse_rfc3339_date(s)
+                                .and_then(serde_json::Number::from_f64)
+                            {
+                                Some(timestamp) => {
+                                    facet_fn(field_id, depth, &Value::Number(timestamp), exact)
+                                }
+                                None => facet_fn(field_id, depth, value, exact),
+                            }
+                        }
+                        _ => facet_fn(field_id, depth, value, exact),
+                    }
+                }
                 None => Err(UserError::AttributeLimitReached.into()),
             };
 
@@ -72,17 +112,43 @@ pub fn extract_document_facets<'doc>(
 
     if attributes_to_extract.contains(&RESERVED_GEO_FIELD_NAME) {
         if let Some(geo_value) = document.geo_field()? {
-            if let Some([lat, lng]) = extract_geo_coordinates(external_document_id, geo_value)? {
+            for [lat, lng] in extract_geo_coordinates(external_document_id, geo_value)? {
                 let (lat_fid, lng_fid) = field_id_map
                     .id_or_insert("_geo.lat")
                     .zip(field_id_map.id_or_insert("_geo.lng"))
                     .ok_or(UserError::AttributeLimitReached)?;
 
-                facet_fn(lat_fid, perm_json_p::Depth::OnBaseKey, &lat.into())?;
-                facet_fn(lng_fid, perm_json_p::Depth::OnBaseKey, &lng.into())?;
+                facet_fn(lat_fid, perm_json_p::Depth::OnBaseKey, &lat.into(), false)?;
+                facet_fn(lng_fid, perm_json_p::Depth::OnBaseKey, &lng.into(), false)?;
             }
         }
     }
 
     Ok(())
 }
+
+/// Extracts the facet values of each element of a nested field, the fields of
+/// the elements being identified by their full path.
+fn extract_element_facets(
+    name: &str,
+    field_id: FieldId,
+    value: &Value,
+    exact_facets: &[&str],
+    field_id_map: &mut GlobalFieldsIdsMap,
+    element_fn: &mut impl FnMut(FieldId, u16, ElementFacet<'_>) -> Result<()>,
+) -> Result<()> {
+    let is_exact = |path: &str| {
+        let name = format!("{name}.{path}");
+        exact_facets.iter().any(|attr| crate::is_faceted_by(&name, attr))
+    };
+    for_each_element_facet(name, value, &is_exact, &mut |position, path, facet| {
+        let field_id = if path.is_empty() {
+            field_id
+        } else {
+            field_id_map
+                .id_or_insert(&format!("{name}.{path}"))
+                .ok_or(UserError::AttributeLimitReached)?
+        };
+        element_fn(field_id, position, facet)
+    })
+}
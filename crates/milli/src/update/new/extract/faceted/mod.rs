@@ -11,6 +11,8 @@ pub enum FacetKind {
     Null = 2,
     Empty = 3,
     Exists,
+    Size,
+    Element,
 }
 
 impl From<u8> for FacetKind {
@@ -21,6 +23,8 @@ impl From<u8> for FacetKind {
             2 => Self::Null,
             3 => Self::Empty,
             4 => Self::Exists,
+            5 => Self::Size,
+            6 => Self::Element,
             _ => unreachable!(),
         }
     }
@@ -9,7 +9,7 @@ use heed::RoTxn;
 use serde_json::value::RawValue;
 use serde_json::Value;
 
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::GeoError;
 use crate::update::new::document::Document;
 use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
@@ -17,10 +17,17 @@ use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::thread_local::MostlySend;
 use crate::update::new::DocumentChange;
 use crate::update::GrenadParameters;
-use crate::{lat_lng_to_xyz, DocumentId, GeoPoint, Index, InternalError, Result};
+use crate::{
+    lat_lng_to_xyz, DocumentId, GeoJsonGeometry, GeoJsonShape, GeoPoint, Index, InternalError,
+    Result,
+};
 
 pub struct GeoExtractor {
     grenad_parameters: GrenadParameters,
+    /// Whether the `_geo` field is sortable or filterable.
+    pub points: bool,
+    /// Whether the `_geojson` field is filterable.
+    pub geojson: bool,
 }
 
 impl GeoExtractor {
@@ -30,9 +37,11 @@ impl GeoExtractor {
         grenad_parameters: GrenadParameters,
     ) -> Result<Option<Self>> {
         let is_sortable = index.sortable_fields(rtxn)?.contains(RESERVED_GEO_FIELD_NAME);
-        let is_filterable = index.filterable_fields(rtxn)?.contains(RESERVED_GEO_FIELD_NAME);
-        if is_sortable || is_filterable {
-            Ok(Some(GeoExtractor { grenad_parameters }))
+        let filterable_fields = index.filterable_fields(rtxn)?;
+        let points = is_sortable || filterable_fields.contains(RESERVED_GEO_FIELD_NAME);
+        let geojson = filterable_fields.contains(RESERVED_GEOJSON_FIELD_NAME);
+        if points || geojson {
+            Ok(Some(GeoExtractor { grenad_parameters, points, geojson }))
         } else {
             Ok(None)
         }
@@ -56,6 +65,19 @@ impl From<ExtractedGeoPoint> for GeoPoint {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ExtractedGeoJson {
+    pub docid: DocumentId,
+    pub geometry: GeoJsonGeometry,
+}
+
+impl From<&ExtractedGeoJson> for GeoJsonShape {
+    /// Indexes the geometry by its bounding box.
+    fn from(value: &ExtractedGeoJson) -> Self {
+        GeoJsonShape::new(value.geometry.bounding_box(), value.docid)
+    }
+}
+
 pub struct GeoExtractorData<'extractor> {
     /// The set of documents ids that were removed. If a document sees its geo
     /// point being updated, we first put it in the deleted and then in the inserted.
@@ -67,11 +89,27 @@ pub struct GeoExtractorData<'extractor> {
     /// Contains a packed list of `ExtractedGeoPoint` of the inserted geo points
     /// data structures if we have spilled to disk.
     spilled_inserted: Option<BufWriter<File>>,
+    /// The GeoJSON geometries that were removed and inserted, they are not stored
+    /// in the bump allocator as they own heap allocated positions.
+    removed_geojson: Vec<ExtractedGeoJson>,
+    inserted_geojson: Vec<ExtractedGeoJson>,
+    /// Contains a list of bincode serialized `ExtractedGeoJson` if we have spilled to disk.
+    spilled_removed_geojson: Option<BufWriter<File>>,
+    spilled_inserted_geojson: Option<BufWriter<File>>,
 }
 
 impl<'extractor> GeoExtractorData<'extractor> {
     pub fn freeze(self) -> Result<FrozenGeoExtractorData<'extractor>> {
-        let GeoExtractorData { removed, inserted, spilled_removed, spilled_inserted } = self;
+        let GeoExtractorData {
+            removed,
+            inserted,
+            spilled_removed,
+            spilled_inserted,
+            removed_geojson,
+            inserted_geojson,
+            spilled_removed_geojson,
+            spilled_inserted_geojson,
+        } = self;
 
         Ok(FrozenGeoExtractorData {
             removed: removed.into_bump_slice(),
@@ -82,6 +120,14 @@ impl<'extractor> GeoExtractorData<'extractor> {
             spilled_inserted: spilled_inserted
                 .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
                 .transpose()?,
+            removed_geojson,
+            inserted_geojson,
+            spilled_removed_geojson: spilled_removed_geojson
+                .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
+                .transpose()?,
+            spilled_inserted_geojson: spilled_inserted_geojson
+                .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
+                .transpose()?,
         })
     }
 }
@@ -93,6 +139,10 @@ pub struct FrozenGeoExtractorData<'extractor> {
     pub inserted: &'extractor [ExtractedGeoPoint],
     pub spilled_removed: Option<BufReader<File>>,
     pub spilled_inserted: Option<BufReader<File>>,
+    pub removed_geojson: Vec<ExtractedGeoJson>,
+    pub inserted_geojson: Vec<ExtractedGeoJson>,
+    pub spilled_removed_geojson: Option<BufReader<File>>,
+    pub spilled_inserted_geojson: Option<BufReader<File>>,
 }
 
 impl<'extractor> FrozenGeoExtractorData<'extractor> {
@@ -115,6 +165,24 @@ impl<'extractor> FrozenGeoExtractorData<'extractor> {
             .map(Ok)
             .chain(iterator_over_spilled_geopoints(&mut self.spilled_inserted)?))
     }
+
+    pub fn iter_and_clear_removed_geojson(
+        &mut self,
+    ) -> io::Result<impl IntoIterator<Item = Result<ExtractedGeoJson>> + '_> {
+        Ok(mem::take(&mut self.removed_geojson)
+            .into_iter()
+            .map(Ok)
+            .chain(iterator_over_spilled_geojson(&mut self.spilled_removed_geojson)?))
+    }
+
+    pub fn iter_and_clear_inserted_geojson(
+        &mut self,
+    ) -> io::Result<impl IntoIterator<Item = Result<ExtractedGeoJson>> + '_> {
+        Ok(mem::take(&mut self.inserted_geojson)
+            .into_iter()
+            .map(Ok)
+            .chain(iterator_over_spilled_geojson(&mut self.spilled_inserted_geojson)?))
+    }
 }
 
 fn iterator_over_spilled_geopoints(
@@ -138,6 +206,26 @@ fn iterator_over_spilled_geopoints(
     }))
 }
 
+fn iterator_over_spilled_geojson(
+    spilled: &mut Option<BufReader<File>>,
+) -> io::Result<impl IntoIterator<Item = Result<ExtractedGeoJson>> + '_> {
+    let mut spilled = spilled.take();
+    if let Some(spilled) = &mut spilled {
+        spilled.rewind()?;
+    }
+
+    Ok(iter::from_fn(move || match &mut spilled {
+        Some(file) => match bincode::deserialize_from(file) {
+            Ok(geojson) => Some(Ok(geojson)),
+            Err(e) => match *e {
+                bincode::ErrorKind::Io(ref e) if e.kind() == ErrorKind::UnexpectedEof => None,
+                _ => Some(Err(InternalError::BincodeError(e).into())),
+            },
+        },
+        None => None,
+    }))
+}
+
 impl<'extractor> Extractor<'extractor> for GeoExtractor {
     type Data = RefCell<GeoExtractorData<'extractor>>;
 
@@ -147,6 +235,10 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
             inserted: bumpalo::collections::Vec::new_in(extractor_alloc),
             spilled_inserted: None,
             spilled_removed: None,
+            removed_geojson: Vec::new(),
+            inserted_geojson: Vec::new(),
+            spilled_removed_geojson: None,
+            spilled_inserted_geojson: None,
         }))
     }
 
@@ -168,6 +260,10 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                 // We must spill as we allocated too much memory
                 data_ref.spilled_removed = tempfile::tempfile().map(BufWriter::new).map(Some)?;
                 data_ref.spilled_inserted = tempfile::tempfile().map(BufWriter::new).map(Some)?;
+                data_ref.spilled_removed_geojson =
+                    tempfile::tempfile().map(BufWriter::new).map(Some)?;
+                data_ref.spilled_inserted_geojson =
+                    tempfile::tempfile().map(BufWriter::new).map(Some)?;
             }
 
             match change? {
@@ -175,16 +271,36 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let docid = deletion.docid();
                     let external_id = deletion.external_document_id();
                     let current = deletion.current(rtxn, index, db_fields_ids_map)?;
-                    let current_geo = current
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    if let Some(lat_lng) = current_geo.flatten() {
-                        let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                        match &mut data_ref.spilled_removed {
-                            Some(file) => file.write_all(bytes_of(&geopoint))?,
-                            None => data_ref.removed.push(geopoint),
+
+                    if self.points {
+                        let current_geo = current
+                            .geo_field()?
+                            .map(|geo| extract_geo_coordinates(external_id, geo))
+                            .transpose()?;
+
+                        for lat_lng in current_geo.into_iter().flatten() {
+                            let geopoint = ExtractedGeoPoint { docid, lat_lng };
+                            match &mut data_ref.spilled_removed {
+                                Some(file) => file.write_all(bytes_of(&geopoint))?,
+                                None => data_ref.removed.push(geopoint),
+                            }
+                        }
+                    }
+
+                    if self.geojson {
+                        let current_geojson = current
+                            .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                            .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                            .transpose()?
+                            .flatten();
+
+                        if let Some(geometry) = current_geojson {
+                            let geojson = ExtractedGeoJson { docid, geometry };
+                            match &mut data_ref.spilled_removed_geojson {
+                                Some(file) => bincode::serialize_into(file, &geojson)
+                                    .map_err(InternalError::BincodeError)?,
+                                None => data_ref.removed_geojson.push(geojson),
+                            }
                         }
                     }
                 }
@@ -192,35 +308,74 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let current = update.current(rtxn, index, db_fields_ids_map)?;
                     let external_id = update.external_document_id();
                     let docid = update.docid();
+                    // A partial update only contains the fields it modifies, we must read
+                    // the merged version of the document to know whether the `_geo` and
+                    // `_geojson` fields were left untouched or actually removed.
+                    let merged = update.merged(rtxn, index, db_fields_ids_map)?;
+
+                    if self.points {
+                        let current_geo = current
+                            .geo_field()?
+                            .map(|geo| extract_geo_coordinates(external_id, geo))
+                            .transpose()?;
+
+                        let updated_geo = merged
+                            .geo_field()?
+                            .map(|geo| extract_geo_coordinates(external_id, geo))
+                            .transpose()?;
+
+                        if current_geo != updated_geo {
+                            // If the current and new geo points are different it means that
+                            // we need to replace the current by the new points and therefore
+                            // delete the current points from the RTree.
+                            for lat_lng in current_geo.into_iter().flatten() {
+                                let geopoint = ExtractedGeoPoint { docid, lat_lng };
+                                match &mut data_ref.spilled_removed {
+                                    Some(file) => file.write_all(bytes_of(&geopoint))?,
+                                    None => data_ref.removed.push(geopoint),
+                                }
+                            }
 
-                    let current_geo = current
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    let updated_geo = update
-                        .updated()
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    if current_geo != updated_geo {
-                        // If the current and new geo points are different it means that
-                        // we need to replace the current by the new point and therefore
-                        // delete the current point from the RTree.
-                        if let Some(lat_lng) = current_geo.flatten() {
-                            let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                            match &mut data_ref.spilled_removed {
-                                Some(file) => file.write_all(bytes_of(&geopoint))?,
-                                None => data_ref.removed.push(geopoint),
+                            for lat_lng in updated_geo.into_iter().flatten() {
+                                let geopoint = ExtractedGeoPoint { docid, lat_lng };
+                                match &mut data_ref.spilled_inserted {
+                                    Some(file) => file.write_all(bytes_of(&geopoint))?,
+                                    None => data_ref.inserted.push(geopoint),
+                                }
                             }
                         }
+                    }
 
-                        if let Some(lat_lng) = updated_geo.flatten() {
-                            let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                            match &mut data_ref.spilled_inserted {
-                                Some(file) => file.write_all(bytes_of(&geopoint))?,
-                                None => data_ref.inserted.push(geopoint),
+                    if self.geojson {
+                        let current_geojson = current
+                            .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                            .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                            .transpose()?
+                            .flatten();
+
+                        let merged_geojson = merged
+                            .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                            .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                            .transpose()?
+                            .flatten();
+
+                        if current_geojson != merged_geojson {
+                            if let Some(geometry) = current_geojson {
+                                let geojson = ExtractedGeoJson { docid, geometry };
+                                match &mut data_ref.spilled_removed_geojson {
+                                    Some(file) => bincode::serialize_into(file, &geojson)
+                                        .map_err(InternalError::BincodeError)?,
+                                    None => data_ref.removed_geojson.push(geojson),
+                                }
+                            }
+
+                            if let Some(geometry) = merged_geojson {
+                                let geojson = ExtractedGeoJson { docid, geometry };
+                                match &mut data_ref.spilled_inserted_geojson {
+                                    Some(file) => bincode::serialize_into(file, &geojson)
+                                        .map_err(InternalError::BincodeError)?,
+                                    None => data_ref.inserted_geojson.push(geojson),
+                                }
                             }
                         }
                     }
@@ -229,17 +384,37 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let external_id = insertion.external_document_id();
                     let docid = insertion.docid();
 
-                    let inserted_geo = insertion
-                        .inserted()
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    if let Some(lat_lng) = inserted_geo.flatten() {
-                        let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                        match &mut data_ref.spilled_inserted {
-                            Some(file) => file.write_all(bytes_of(&geopoint))?,
-                            None => data_ref.inserted.push(geopoint),
+                    if self.points {
+                        let inserted_geo = insertion
+                            .inserted()
+                            .geo_field()?
+                            .map(|geo| extract_geo_coordinates(external_id, geo))
+                            .transpose()?;
+
+                        for lat_lng in inserted_geo.into_iter().flatten() {
+                            let geopoint = ExtractedGeoPoint { docid, lat_lng };
+                            match &mut data_ref.spilled_inserted {
+                                Some(file) => file.write_all(bytes_of(&geopoint))?,
+                                None => data_ref.inserted.push(geopoint),
+                            }
+                        }
+                    }
+
+                    if self.geojson {
+                        let inserted_geojson = insertion
+                            .inserted()
+                            .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                            .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                            .transpose()?
+                            .flatten();
+
+                        if let Some(geometry) = inserted_geojson {
+                            let geojson = ExtractedGeoJson { docid, geometry };
+                            match &mut data_ref.spilled_inserted_geojson {
+                                Some(file) => bincode::serialize_into(file, &geojson)
+                                    .map_err(InternalError::BincodeError)?,
+                                None => data_ref.inserted_geojson.push(geojson),
+                            }
                         }
                     }
                 }
@@ -250,15 +425,24 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
     }
 }
 
-/// Extracts and validates the latitude and latitude from a document geo field.
+/// Extracts and validates the latitudes and longitudes from a document geo field.
 ///
-/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }`.
-pub fn extract_geo_coordinates(
-    external_id: &str,
-    raw_value: &RawValue,
-) -> Result<Option<[f64; 2]>> {
-    let mut geo = match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
-        Value::Null => return Ok(None),
+/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }` or an array of those
+/// when the document is located at several places.
+pub fn extract_geo_coordinates(external_id: &str, raw_value: &RawValue) -> Result<Vec<[f64; 2]>> {
+    match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| extract_geo_point_coordinates(external_id, value))
+            .collect(),
+        value => extract_geo_point_coordinates(external_id, value).map(|point| vec![point]),
+    }
+}
+
+/// Extracts and validates the latitude and longitude of a single geo point.
+fn extract_geo_point_coordinates(external_id: &str, value: Value) -> Result<[f64; 2]> {
+    let mut geo = match value {
         Value::Object(map) => map,
         value => {
             return Err(
@@ -294,7 +478,7 @@ pub fn extract_geo_coordinates(
     };
 
     match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
-        (Ok(lat), Ok(lng)) => Ok(Some([lat, lng])),
+        (Ok(lat), Ok(lng)) => Ok([lat, lng]),
         (Ok(_), Err(value)) => {
             Err(GeoError::BadLongitude { document_id: Value::from(external_id), value }.into())
         }
@@ -310,6 +494,21 @@ pub fn extract_geo_coordinates(
     }
 }
 
+/// Extracts and validates the GeoJSON geometry of a document `_geojson` field.
+///
+/// A `null` value means that the document doesn't have any geometry.
+pub fn extract_geojson_geometry(
+    external_id: &str,
+    raw_value: &RawValue,
+) -> Result<Option<GeoJsonGeometry>> {
+    match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
+        Value::Null => Ok(None),
+        value => GeoJsonGeometry::from_value(&value).map(Some).map_err(|reason| {
+            GeoError::BadGeoJson { document_id: Value::from(external_id), reason }.into()
+        }),
+    }
+}
+
 /// Extracts and validate that a serde JSON Value is actually a finite f64.
 pub fn extract_finite_float_from_value(value: Value) -> result::Result<f64, Value> {
     let number = match value {
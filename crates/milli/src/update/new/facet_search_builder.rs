@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeSet, HashMap};
 
@@ -22,15 +23,18 @@ pub struct FacetSearchBuilder<'indexer> {
     normalized_facet_string_docids_sorter: Sorter<MergeDeladdBtreesetString>,
     global_fields_ids_map: GlobalFieldsIdsMap<'indexer>,
     localized_attributes_rules: Vec<LocalizedAttributesRule>,
+    exact_facets: Vec<String>,
     // Buffered data below
     buffer: Vec<u8>,
     localized_field_ids: HashMap<FieldId, Option<Vec<Language>>>,
+    exact_field_ids: HashMap<FieldId, bool>,
 }
 
 impl<'indexer> FacetSearchBuilder<'indexer> {
     pub fn new(
         global_fields_ids_map: GlobalFieldsIdsMap<'indexer>,
         localized_attributes_rules: Vec<LocalizedAttributesRule>,
+        exact_facets: Vec<String>,
     ) -> Self {
         let registered_facets = HashMap::new();
         let normalized_facet_string_docids_sorter = create_sorter(
@@ -49,7 +53,9 @@ impl<'indexer> FacetSearchBuilder<'indexer> {
             buffer: Vec::new(),
             global_fields_ids_map,
             localized_attributes_rules,
+            exact_facets,
             localized_field_ids: HashMap::new(),
+            exact_field_ids: HashMap::new(),
         }
     }
 
@@ -64,8 +70,9 @@ impl<'indexer> FacetSearchBuilder<'indexer> {
             self.registered_facets.entry(field_id).and_modify(|count| *count += 1).or_insert(1);
         }
 
+        let exact = self.is_exact(field_id);
         let locales = self.locales(field_id);
-        let hyper_normalized_value = normalize_facet_string(left_bound, locales);
+        let hyper_normalized_value = normalize_facet_string(left_bound, locales, exact);
 
         let set = BTreeSet::from_iter(std::iter::once(left_bound));
 
@@ -101,6 +108,15 @@ impl<'indexer> FacetSearchBuilder<'indexer> {
         self.localized_field_ids.get(&field_id).unwrap().as_deref()
     }
 
+    fn is_exact(&mut self, field_id: FieldId) -> bool {
+        *self.exact_field_ids.entry(field_id).or_insert_with(|| {
+            let Some(field_name) = self.global_fields_ids_map.name(field_id) else {
+                unreachable!("Field id {field_id} not found in the global fields ids map");
+            };
+            self.exact_facets.iter().any(|facet| crate::is_faceted_by(field_name, facet))
+        })
+    }
+
     #[tracing::instrument(level = "trace", skip_all, target = "indexing::facet_fst")]
     pub fn merge_and_write(self, index: &Index, wtxn: &mut RwTxn, rtxn: &RoTxn) -> Result<()> {
         tracing::trace!("merge facet strings for facet search: {:?}", self.registered_facets);
@@ -221,29 +237,30 @@ fn merge_btreesets(
 }
 
 /// Normalizes the facet string and truncates it to the max length.
-fn normalize_facet_string(facet_string: &str, locales: Option<&[Language]>) -> String {
-    let options: NormalizerOption = NormalizerOption { lossy: true, ..Default::default() };
-    let mut detection = StrDetection::new(facet_string, locales);
-
-    let script = detection.script();
-    // Detect the language of the facet string only if several locales are explicitly provided.
-    let language = match locales {
-        Some(&[language]) => Some(language),
-        Some(multiple_locales) if multiple_locales.len() > 1 => detection.language(),
-        _ => None,
-    };
-
-    let token = Token {
-        lemma: std::borrow::Cow::Borrowed(facet_string),
-        script,
-        language,
-        ..Default::default()
+///
+/// The facet strings of an exact facet are only truncated, they are searched case-sensitively.
+fn normalize_facet_string(facet_string: &str, locales: Option<&[Language]>, exact: bool) -> String {
+    let normalized = if exact {
+        Cow::Borrowed(facet_string)
+    } else {
+        let options: NormalizerOption = NormalizerOption { lossy: true, ..Default::default() };
+        let mut detection = StrDetection::new(facet_string, locales);
+
+        let script = detection.script();
+        // Detect the language of the facet string only if several locales are explicitly provided.
+        let language = match locales {
+            Some(&[language]) => Some(language),
+            Some(multiple_locales) if multiple_locales.len() > 1 => detection.language(),
+            _ => None,
+        };
+
+        let token =
+            Token { lemma: Cow::Borrowed(facet_string), script, language, ..Default::default() };
+        token.normalize(&options).lemma
     };
 
     // truncate the facet string to the max length
-    token
-        .normalize(&options)
-        .lemma
+    normalized
         .char_indices()
         .take_while(|(idx, _)| *idx < MAX_FACET_VALUE_LENGTH)
         .map(|(_, c)| c)
@@ -285,6 +285,7 @@ where
 
         merge_and_send_rtree(
             datastore,
+            &extractor,
             &rtxn,
             index,
             extractor_sender.geo(),
@@ -117,9 +117,11 @@ fn compute_facet_search_database(
 ) -> Result<()> {
     let rtxn = index.read_txn()?;
     let localized_attributes_rules = index.localized_attributes_rules(&rtxn)?;
+    let exact_facets = index.exact_facets(&rtxn)?.into_iter().map(String::from).collect();
     let mut facet_search_builder = FacetSearchBuilder::new(
         global_fields_ids_map,
         localized_attributes_rules.unwrap_or_default(),
+        exact_facets,
     );
 
     let previous_facet_id_string_docids = index
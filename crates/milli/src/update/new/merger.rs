@@ -10,14 +10,15 @@ use roaring::RoaringBitmap;
 use super::channel::*;
 use super::extract::{
     merge_caches_sorted, transpose_and_freeze_caches, BalancedCaches, DelAddRoaringBitmap,
-    FacetKind, GeoExtractorData,
+    ExtractedGeoJson, FacetKind, GeoExtractor, GeoExtractorData,
 };
 use crate::update::facet::new_incremental::FacetFieldIdChange;
-use crate::{CboRoaringBitmapCodec, FieldId, GeoPoint, Index, InternalError, Result};
+use crate::{CboRoaringBitmapCodec, FieldId, GeoJsonShape, GeoPoint, Index, InternalError, Result};
 
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::merge")]
 pub fn merge_and_send_rtree<'extractor, MSP>(
     datastore: impl IntoIterator<Item = RefCell<GeoExtractorData<'extractor>>>,
+    extractor: &GeoExtractor,
     rtxn: &RoTxn,
     index: &Index,
     geo_sender: GeoSender<'_, '_>,
@@ -28,6 +29,11 @@ where
 {
     let mut rtree = index.geo_rtree(rtxn)?.unwrap_or_default();
     let mut faceted = index.geo_faceted_documents_ids(rtxn)?;
+    // a document located at several places is seen once per point
+    let mut removed_docids = RoaringBitmap::new();
+    let mut inserted_docids = RoaringBitmap::new();
+    let mut geojson_rtree = index.geojson_rtree(rtxn)?.unwrap_or_default();
+    let mut removed_geometries = RoaringBitmap::new();
 
     for data in datastore {
         if must_stop_processing() {
@@ -39,25 +45,62 @@ where
             let extracted_geo_point = result?;
             let removed = rtree.remove(&GeoPoint::from(extracted_geo_point));
             debug_assert!(removed.is_some());
-            let removed = faceted.remove(extracted_geo_point.docid);
-            debug_assert!(removed);
+            if removed_docids.insert(extracted_geo_point.docid) {
+                let removed = faceted.remove(extracted_geo_point.docid);
+                debug_assert!(removed);
+            }
         }
 
         for result in frozen.iter_and_clear_inserted()? {
             let extracted_geo_point = result?;
             rtree.insert(GeoPoint::from(extracted_geo_point));
-            let inserted = faceted.insert(extracted_geo_point.docid);
-            debug_assert!(inserted);
+            if inserted_docids.insert(extracted_geo_point.docid) {
+                let inserted = faceted.insert(extracted_geo_point.docid);
+                debug_assert!(inserted);
+            }
+        }
+
+        for result in frozen.iter_and_clear_removed_geojson()? {
+            let extracted_geojson = result?;
+            let removed = geojson_rtree.remove(&GeoJsonShape::from(&extracted_geojson));
+            debug_assert!(removed.is_some());
+            removed_geometries.insert(extracted_geojson.docid);
+        }
+
+        for result in frozen.iter_and_clear_inserted_geojson()? {
+            let extracted_geojson = result?;
+            geojson_rtree.insert(GeoJsonShape::from(&extracted_geojson));
+            let ExtractedGeoJson { docid, geometry } = extracted_geojson;
+            geo_sender.write_geojson_geometry(docid, &geometry)?;
+            removed_geometries.remove(docid);
         }
     }
 
-    let mut file = tempfile::tempfile()?;
-    bincode::serialize_into(&mut file, &rtree).map_err(InternalError::BincodeError)?;
-    file.sync_all()?;
+    // The geometries of the updated documents are overwritten, we only delete the ones
+    // of the documents that lost their geometry so that a deletion is never applied
+    // after the write of a new geometry.
+    for docid in removed_geometries {
+        geo_sender.delete_geojson_geometry(docid)?;
+    }
+
+    if extractor.points {
+        let mut file = tempfile::tempfile()?;
+        bincode::serialize_into(&mut file, &rtree).map_err(InternalError::BincodeError)?;
+        file.sync_all()?;
+
+        let rtree_mmap = unsafe { Mmap::map(&file)? };
+        geo_sender.set_rtree(rtree_mmap).unwrap();
+        geo_sender.set_geo_faceted(&faceted)?;
+    }
+
+    if extractor.geojson {
+        let mut file = tempfile::tempfile()?;
+        bincode::serialize_into(&mut file, &geojson_rtree).map_err(InternalError::BincodeError)?;
+        file.sync_all()?;
 
-    let rtree_mmap = unsafe { Mmap::map(&file)? };
-    geo_sender.set_rtree(rtree_mmap).unwrap();
-    geo_sender.set_geo_faceted(&faceted)?;
+        let rtree_mmap = unsafe { Mmap::map(&file)? };
+        geo_sender.set_geojson_rtree(rtree_mmap).unwrap();
+    }
 
     Ok(())
 }
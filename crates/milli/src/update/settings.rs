@@ -14,8 +14,10 @@ use time::OffsetDateTime;
 use super::del_add::DelAddOperation;
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
-use crate::constants::{RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
-use crate::criterion::Criterion;
+use crate::constants::{
+    RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
+use crate::criterion::{Criterion, CriterionError};
 use crate::error::UserError;
 use crate::index::{
     IndexEmbeddingConfig, PrefixSearch, DEFAULT_MIN_WORD_LEN_ONE_TYPO,
@@ -31,7 +33,10 @@ use crate::vector::settings::{
     WriteBackToDocuments,
 };
 use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FieldsIdsMap, Index, LocalizedAttributesRule, LocalizedFieldIds, Result};
+use crate::{
+    CurationRule, FieldId, FieldsIdsMap, Filter, Index, LocalizedAttributesRule, LocalizedFieldIds,
+    Result,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -171,8 +176,17 @@ pub struct Settings<'a, 't, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    /// Attributes in which the words can be matched by their middle or their end.
+    infix_searchable_attributes: Setting<HashSet<String>>,
+    /// Attributes whose RFC 3339 string values are indexed as numeric facets.
+    date_attributes: Setting<HashSet<String>>,
+    /// Arrays of objects whose elements are filtered one by one.
+    nested_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<OrderByMap>,
+    hierarchical_facets: Setting<BTreeMap<String, String>>,
+    /// Attributes whose string facet values are kept exact, without being normalized.
+    exact_facets: Setting<HashSet<String>>,
     pagination_max_total_hits: Setting<usize>,
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
@@ -180,6 +194,7 @@ pub struct Settings<'a, 't, 'i> {
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
+    curation_rules: Setting<Vec<CurationRule>>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -208,8 +223,13 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
+            nested_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
+            hierarchical_facets: Setting::NotSet,
+            exact_facets: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
@@ -217,6 +237,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             localized_attributes_rules: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
+            curation_rules: Setting::NotSet,
             indexer_config,
         }
     }
@@ -367,6 +388,30 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_infix_searchable_attributes(&mut self, attrs: HashSet<String>) {
+        self.infix_searchable_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_infix_searchable_attributes(&mut self) {
+        self.infix_searchable_attributes = Setting::Reset;
+    }
+
+    pub fn set_date_attributes(&mut self, attrs: HashSet<String>) {
+        self.date_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_date_attributes(&mut self) {
+        self.date_attributes = Setting::Reset;
+    }
+
+    pub fn set_nested_attributes(&mut self, attrs: HashSet<String>) {
+        self.nested_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_nested_attributes(&mut self) {
+        self.nested_attributes = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -383,6 +428,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.sort_facet_values_by = Setting::Reset;
     }
 
+    pub fn set_hierarchical_facets(&mut self, value: BTreeMap<String, String>) {
+        self.hierarchical_facets = Setting::Set(value);
+    }
+
+    pub fn reset_hierarchical_facets(&mut self) {
+        self.hierarchical_facets = Setting::Reset;
+    }
+
+    pub fn set_exact_facets(&mut self, attrs: HashSet<String>) {
+        self.exact_facets = Setting::Set(attrs);
+    }
+
+    pub fn reset_exact_facets(&mut self) {
+        self.exact_facets = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -439,6 +500,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.facet_search = Setting::Reset;
     }
 
+    pub fn set_curation_rules(&mut self, value: Vec<CurationRule>) {
+        self.curation_rules = Setting::Set(value);
+    }
+
+    pub fn reset_curation_rules(&mut self) {
+        self.curation_rules = Setting::Reset;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -757,6 +826,82 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    fn update_infix_searchable_attributes(&mut self) -> Result<bool> {
+        match self.infix_searchable_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.infix_searchable_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_infix_searchable_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_infix_searchable_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_date_attributes(&mut self) -> Result<bool> {
+        match self.date_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.date_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_date_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_date_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_exact_facets(&mut self) -> Result<bool> {
+        match self.exact_facets {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.exact_facets(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_exact_facets(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_exact_facets(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_nested_attributes(&mut self) -> Result<bool> {
+        match self.nested_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.nested_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_nested_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_nested_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_filterable(&mut self) -> Result<()> {
         match self.filterable_fields {
             Setting::Set(ref fields) => {
@@ -792,6 +937,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
     }
 
     fn update_criteria(&mut self) -> Result<()> {
+        self.validate_boost_criteria()?;
         match &self.criteria {
             Setting::Set(criteria) => {
                 self.index.put_criteria(self.wtxn, criteria)?;
@@ -804,6 +950,40 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    /// Refuses the boost ranking rules filtering on attributes that are not filterable,
+    /// checked against the filterable attributes the index will have after this update.
+    fn validate_boost_criteria(&self) -> Result<()> {
+        if self.criteria.is_not_set() && self.filterable_fields.is_not_set() {
+            return Ok(());
+        }
+
+        let criteria = match &self.criteria {
+            Setting::Set(criteria) => criteria.clone(),
+            Setting::Reset => return Ok(()),
+            Setting::NotSet => self.index.criteria(self.wtxn)?,
+        };
+        let filterable_fields = match &self.filterable_fields {
+            Setting::Set(fields) => fields.clone(),
+            Setting::Reset => HashSet::new(),
+            Setting::NotSet => self.index.filterable_fields(self.wtxn)?,
+        };
+
+        for criterion in &criteria {
+            if let Criterion::Boost(filter) = criterion {
+                if let Some(parsed) = Filter::from_str(filter)? {
+                    parsed.check_filterable_fields(&filterable_fields).map_err(|error| {
+                        CriterionError::InvalidBoost {
+                            name: criterion.to_string(),
+                            reason: error.to_string(),
+                        }
+                    })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn update_primary_key(&mut self) -> Result<()> {
         match self.primary_key {
             Setting::Set(ref primary_key) => {
@@ -943,6 +1123,28 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_hierarchical_facets(&mut self) -> Result<()> {
+        match self.hierarchical_facets.as_ref() {
+            Setting::Set(value) => {
+                if let Some((attribute, _)) =
+                    value.iter().find(|(_, separator)| separator.is_empty())
+                {
+                    return Err(UserError::InvalidHierarchicalFacetSeparator {
+                        attribute: attribute.clone(),
+                    }
+                    .into());
+                }
+                self.index.put_hierarchical_facets(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_hierarchical_facets(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -1227,6 +1429,23 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_curation_rules(&mut self) -> Result<()> {
+        match &self.curation_rules {
+            Setting::Set(new) => {
+                let old = self.index.curation_rules(self.wtxn)?;
+                if &old != new {
+                    self.index.put_curation_rules(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_curation_rules(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1246,8 +1465,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
+        self.update_hierarchical_facets()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_infix_searchable_attributes()?;
+        self.update_curation_rules()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1259,6 +1481,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_synonyms()?;
         self.update_searchable()?;
         self.update_exact_attributes()?;
+        self.update_date_attributes()?;
+        self.update_nested_attributes()?;
+        self.update_exact_facets()?;
         self.update_proximity_precision()?;
         self.update_prefix_search()?;
         self.update_facet_search()?;
@@ -1457,6 +1682,9 @@ impl InnerIndexSettingsDiff {
     pub fn global_facet_settings_changed(&self) -> bool {
         self.old.localized_faceted_fields_ids != self.new.localized_faceted_fields_ids
             || self.old.facet_search != self.new.facet_search
+            || self.old.date_fields_ids != self.new.date_fields_ids
+            || self.old.nested_fields_ids != self.new.nested_fields_ids
+            || self.old.exact_facets_fields_ids != self.new.exact_facets_fields_ids
     }
 
     pub fn reindex_facets(&self) -> bool {
@@ -1476,6 +1704,11 @@ impl InnerIndexSettingsDiff {
             || (!self.settings_update_only && self.new.geo_fields_ids.is_some())
     }
 
+    pub fn run_geojson_indexing(&self) -> bool {
+        self.old.geojson_fid != self.new.geojson_fid
+            || (!self.settings_update_only && self.new.geojson_fid.is_some())
+    }
+
     pub fn modified_faceted_fields(&self) -> HashSet<String> {
         &self.old.user_defined_faceted_fields ^ &self.new.user_defined_faceted_fields
     }
@@ -1492,10 +1725,14 @@ pub(crate) struct InnerIndexSettings {
     pub faceted_fields_ids: HashSet<FieldId>,
     pub searchable_fields_ids: Vec<FieldId>,
     pub exact_attributes: HashSet<FieldId>,
+    pub date_fields_ids: HashSet<FieldId>,
+    pub nested_fields_ids: HashSet<FieldId>,
+    pub exact_facets_fields_ids: HashSet<FieldId>,
     pub proximity_precision: ProximityPrecision,
     pub embedding_configs: EmbeddingConfigs,
     pub existing_fields: HashSet<String>,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
+    pub geojson_fid: Option<FieldId>,
     pub non_searchable_fields_ids: Vec<FieldId>,
     pub non_faceted_fields_ids: Vec<FieldId>,
     pub localized_searchable_fields_ids: LocalizedFieldIds,
@@ -1522,6 +1759,9 @@ impl InnerIndexSettings {
         let mut searchable_fields_ids = index.searchable_fields_ids(rtxn)?;
         let mut faceted_fields_ids = index.faceted_fields_ids(rtxn)?;
         let exact_attributes = index.exact_attributes_ids(rtxn)?;
+        let date_fields_ids = index.date_attributes_ids(rtxn)?;
+        let nested_fields_ids = index.nested_attributes_ids(rtxn)?;
+        let exact_facets_fields_ids = index.exact_facets_ids(rtxn)?;
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
         let embedding_configs = match embedding_configs {
             Some(embedding_configs) => embedding_configs,
@@ -1552,6 +1792,11 @@ impl InnerIndexSettings {
             }
             None => None,
         };
+        // the geometries are only indexed when `_geojson` is filterable
+        let geojson_fid = match fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) {
+            Some(fid) if index.filterable_fields_ids(rtxn)?.contains(&fid) => Some(fid),
+            _ => None,
+        };
         let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
         let localized_searchable_fields_ids = LocalizedFieldIds::new(
             &localized_attributes_rules,
@@ -1578,10 +1823,14 @@ impl InnerIndexSettings {
             faceted_fields_ids,
             searchable_fields_ids,
             exact_attributes,
+            date_fields_ids,
+            nested_fields_ids,
+            exact_facets_fields_ids,
             proximity_precision,
             embedding_configs,
             existing_fields,
             geo_fields_ids,
+            geojson_fid,
             non_searchable_fields_ids: vectors_fids.clone(),
             non_faceted_fields_ids: vectors_fids.clone(),
             localized_searchable_fields_ids,
@@ -2788,8 +3037,13 @@ mod tests {
                     min_word_len_one_typo,
                     exact_words,
                     exact_attributes,
+                    infix_searchable_attributes,
+                    date_attributes,
+                    nested_attributes,
                     max_values_per_facet,
                     sort_facet_values_by,
+                    hierarchical_facets,
+                    exact_facets,
                     pagination_max_total_hits,
                     proximity_precision,
                     embedder_settings,
@@ -2797,6 +3051,7 @@ mod tests {
                     localized_attributes_rules,
                     prefix_search,
                     facet_search,
+                    curation_rules,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
@@ -2815,8 +3070,13 @@ mod tests {
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
+                assert!(matches!(infix_searchable_attributes, Setting::NotSet));
+                assert!(matches!(date_attributes, Setting::NotSet));
+                assert!(matches!(nested_attributes, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(sort_facet_values_by, Setting::NotSet));
+                assert!(matches!(hierarchical_facets, Setting::NotSet));
+                assert!(matches!(exact_facets, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
                 assert!(matches!(proximity_precision, Setting::NotSet));
                 assert!(matches!(embedder_settings, Setting::NotSet));
@@ -2824,6 +3084,7 @@ mod tests {
                 assert!(matches!(localized_attributes_rules, Setting::NotSet));
                 assert!(matches!(prefix_search, Setting::NotSet));
                 assert!(matches!(facet_search, Setting::NotSet));
+                assert!(matches!(curation_rules, Setting::NotSet));
             })
             .unwrap();
     }